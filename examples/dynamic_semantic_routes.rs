@@ -1,9 +1,11 @@
+#[cfg(feature = "serpapi")]
 use langchain_rust::{
     embedding::openai::OpenAiEmbedder,
     semantic_router::{AggregationMethod, RouteLayerBuilder, Router},
     tools::{SerpApi, Tool},
 };
 
+#[cfg(feature = "serpapi")]
 #[tokio::main]
 async fn main() {
     let tool = SerpApi::default();
@@ -49,3 +51,10 @@ async fn main() {
         println!("{:?}", tool_output);
     }
 }
+
+#[cfg(not(feature = "serpapi"))]
+fn main() {
+    println!("This example requires the 'serpapi' feature to be enabled.");
+    println!("Please run the command as follows:");
+    println!("cargo run --example dynamic_semantic_routes --features=serpapi");
+}
@@ -1,8 +1,17 @@
+#[cfg(feature = "claude")]
 use langchain_rust::{language_models::llm::LLM, llm::Claude};
 
+#[cfg(feature = "claude")]
 #[tokio::main]
 async fn main() {
     let claude = Claude::default().with_model("claude-3-opus-20240229");
     let response = claude.invoke("hola").await.unwrap();
     println!("{}", response);
 }
+
+#[cfg(not(feature = "claude"))]
+fn main() {
+    println!("This example requires the 'claude' feature to be enabled.");
+    println!("Please run the command as follows:");
+    println!("cargo run --example llm_anthropic_claude --features=claude");
+}
@@ -1,18 +1,25 @@
-use std::{error::Error, sync::Arc};
+#[cfg(all(feature = "serpapi", feature = "duckduckgo"))]
+use std::sync::Arc;
 
+#[cfg(all(feature = "serpapi", feature = "duckduckgo"))]
 use async_trait::async_trait;
+#[cfg(all(feature = "serpapi", feature = "duckduckgo"))]
 use langchain_rust::{
     agent::{AgentExecutor, OpenAiToolAgentBuilder},
     chain::{options::ChainCallOptions, Chain},
+    error::LangChainError,
     llm::openai::OpenAI,
     memory::SimpleMemory,
     prompt_args,
     tools::{CommandExecutor, DuckDuckGoSearchResults, SerpApi, Tool},
 };
 
+#[cfg(all(feature = "serpapi", feature = "duckduckgo"))]
 use serde_json::Value;
+#[cfg(all(feature = "serpapi", feature = "duckduckgo"))]
 struct Date {}
 
+#[cfg(all(feature = "serpapi", feature = "duckduckgo"))]
 #[async_trait]
 impl Tool for Date {
     fn name(&self) -> String {
@@ -21,11 +28,12 @@ impl Tool for Date {
     fn description(&self) -> String {
         "Useful when you need to get the date,input is  a query".to_string()
     }
-    async fn run(&self, _input: Value) -> Result<String, Box<dyn Error>> {
+    async fn run(&self, _input: Value) -> Result<String, LangChainError> {
         Ok("25  of november of 2025".to_string())
     }
 }
 
+#[cfg(all(feature = "serpapi", feature = "duckduckgo"))]
 #[tokio::main]
 async fn main() {
     let llm = OpenAI::default();
@@ -58,3 +66,10 @@ async fn main() {
         Err(e) => panic!("Error invoking LLMChain: {:?}", e),
     }
 }
+
+#[cfg(not(all(feature = "serpapi", feature = "duckduckgo")))]
+fn main() {
+    println!("This example requires the 'serpapi' and 'duckduckgo' features to be enabled.");
+    println!("Please run the command as follows:");
+    println!("cargo run --example open_ai_tools_agent --features=serpapi,duckduckgo");
+}
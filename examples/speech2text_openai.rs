@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use langchain_rust::error::LangChainError;
 use langchain_rust::tools::{SpeechStorage, Text2SpeechOpenAI, Tool};
 
 #[allow(dead_code)]
@@ -8,7 +9,7 @@ struct XStorage {}
 
 #[async_trait]
 impl SpeechStorage for XStorage {
-    async fn save(&self, path: &str, _data: &[u8]) -> Result<String, Box<dyn std::error::Error>> {
+    async fn save(&self, path: &str, _data: &[u8]) -> Result<String, LangChainError> {
         println!("Saving to: {}", path);
         Ok(path.to_string())
     }
@@ -1,15 +1,21 @@
+#[cfg(feature = "html")]
 use std::{io::Cursor, process::Stdio};
 
+#[cfg(feature = "html")]
 use futures::StreamExt;
+#[cfg(feature = "html")]
 use langchain_rust::{
     document_loaders::{HtmlLoader, Loader},
     schemas::Document,
     text_splitter::{PlainTextSplitter, PlainTextSplitterOptions, TextSplitter},
     tools::{Text2SpeechOpenAI, Tool},
 };
+#[cfg(feature = "html")]
 use tokio::{io::AsyncReadExt, process::Command};
+#[cfg(feature = "html")]
 use url::Url;
 
+#[cfg(feature = "html")]
 #[tokio::main]
 async fn main() {
     // URL to generate audio from.
@@ -145,3 +151,10 @@ async fn main() {
     let path = std::path::Path::new(&output_path).canonicalize().unwrap();
     println!("Final audio saved at: {:?}", path);
 }
+
+#[cfg(not(feature = "html"))]
+fn main() {
+    println!("This example requires the 'html' feature to be enabled.");
+    println!("Please run the command as follows:");
+    println!("cargo run --example text_to_speech --features=html");
+}
@@ -1,5 +1,7 @@
+#[cfg(feature = "wolfram")]
 use langchain_rust::tools::{Tool, Wolfram};
 
+#[cfg(feature = "wolfram")]
 #[tokio::main]
 async fn main() {
     let wolfram = Wolfram::default().with_excludes(&["Plot"]);
@@ -8,3 +10,10 @@ async fn main() {
 
     println!("{}", result.unwrap());
 }
+
+#[cfg(not(feature = "wolfram"))]
+fn main() {
+    println!("This example requires the 'wolfram' feature to be enabled.");
+    println!("Please run the command as follows:");
+    println!("cargo run --example wolfram_tool --features=wolfram");
+}
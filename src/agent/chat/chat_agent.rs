@@ -6,11 +6,7 @@ use serde_json::json;
 use crate::{
     agent::{agent::Agent, chat::prompt::FORMAT_INSTRUCTIONS, AgentError},
     chain::chain_trait::Chain,
-    message_formatter,
-    prompt::{
-        HumanMessagePromptTemplate, MessageFormatterStruct, MessageOrTemplate, PromptArgs,
-        PromptFromatter,
-    },
+    prompt::{ChatPromptTemplate, PromptArgs, PromptFromatter},
     prompt_args,
     schemas::{
         agent::{AgentAction, AgentEvent},
@@ -33,7 +29,7 @@ impl ConversationalAgent {
         tools: &[Arc<dyn Tool>],
         suffix: &str,
         prefix: &str,
-    ) -> Result<MessageFormatterStruct, AgentError> {
+    ) -> Result<ChatPromptTemplate, AgentError> {
         let tool_string = tools
             .iter()
             .map(|tool| format!("> {}: {}", tool.name(), tool.description()))
@@ -54,18 +50,11 @@ impl ConversationalAgent {
         };
 
         let sufix_prompt = sufix_prompt.format(input_variables_fstring)?;
-        let formatter = message_formatter![
-            MessageOrTemplate::Message(Message::new_system_message(prefix)),
-            MessageOrTemplate::MessagesPlaceholder("chat_history".to_string()),
-            MessageOrTemplate::Template(
-                HumanMessagePromptTemplate::new(template_jinja2!(
-                    &sufix_prompt.to_string(),
-                    "input"
-                ))
-                .into()
-            ),
-            MessageOrTemplate::MessagesPlaceholder("agent_scratchpad".to_string()),
-        ];
+        let formatter = ChatPromptTemplate::new()
+            .with_message(Message::new_system_message(prefix))
+            .with_messages_placeholder("chat_history")
+            .with_human_template(template_jinja2!(&sufix_prompt.to_string(), "input"))
+            .with_messages_placeholder("agent_scratchpad");
         Ok(formatter)
     }
 
@@ -104,9 +93,9 @@ impl Agent for ConversationalAgent {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "openai"))]
 mod tests {
-    use std::{error::Error, sync::Arc};
+    use std::sync::Arc;
 
     use async_trait::async_trait;
     use serde_json::Value;
@@ -114,6 +103,7 @@ mod tests {
     use crate::{
         agent::{chat::builder::ConversationalAgentBuilder, executor::AgentExecutor},
         chain::chain_trait::Chain,
+        error::LangChainError,
         llm::openai::{OpenAI, OpenAIModel},
         memory::SimpleMemory,
         prompt_args,
@@ -130,7 +120,7 @@ mod tests {
         fn description(&self) -> String {
             "Usefull to make calculations".to_string()
         }
-        async fn run(&self, _input: Value) -> Result<String, Box<dyn Error>> {
+        async fn run(&self, _input: Value) -> Result<String, LangChainError> {
             Ok("25".to_string())
         }
     }
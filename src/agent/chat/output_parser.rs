@@ -26,7 +26,7 @@ impl ChatOutputParser {
 
 impl ChatOutputParser {
     pub fn parse(&self, text: &str) -> Result<AgentEvent, AgentError> {
-        log::debug!("Parsing to Agent Action: {}", text);
+        tracing::debug!("Parsing to Agent Action: {}", text);
         match parse_json_markdown(text) {
             Some(value) => {
                 // Deserialize the Value into AgentOutput
@@ -45,7 +45,7 @@ impl ChatOutputParser {
                 }
             }
             None => {
-                log::debug!("No JSON found or malformed JSON in text: {}", text);
+                tracing::debug!("No JSON found or malformed JSON in text: {}", text);
                 Ok(AgentEvent::Finish(AgentFinish {
                     output: text.to_string(),
                 }))
@@ -5,10 +5,11 @@ use serde_json::json;
 use tokio::sync::Mutex;
 
 use super::{agent::Agent, AgentError};
-use crate::schemas::{LogTools, Message};
+use crate::schemas::{FunctionCallResponse, LogTools, Message, ToolCall};
 use crate::{
+    callbacks::trace_chain,
     chain::{chain_trait::Chain, ChainError},
-    language_models::GenerateResult,
+    language_models::{CancellationToken, GenerateResult},
     memory::SimpleMemory,
     prompt::PromptArgs,
     schemas::{
@@ -25,6 +26,7 @@ where
     agent: A,
     max_iterations: Option<i32>,
     break_if_error: bool,
+    cancellation_token: Option<CancellationToken>,
     pub memory: Option<Arc<Mutex<dyn BaseMemory>>>,
 }
 
@@ -37,6 +39,7 @@ where
             agent,
             max_iterations: Some(10),
             break_if_error: false,
+            cancellation_token: None,
             memory: None,
         }
     }
@@ -56,10 +59,17 @@ where
         self
     }
 
+    /// Stops the agent loop once the token is cancelled, dropping the planning or tool run in
+    /// flight; the call then fails with `ChainError::Cancelled` and nothing is saved to memory.
+    pub fn with_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+        self.cancellation_token = Some(cancellation_token);
+        self
+    }
+
     fn get_name_to_tools(&self) -> HashMap<String, Arc<dyn Tool>> {
         let mut name_to_tool = HashMap::new();
         for tool in self.agent.get_tools().iter() {
-            log::debug!("Loading Tool:{}", tool.name());
+            tracing::debug!("Loading Tool:{}", tool.name());
             name_to_tool.insert(tool.name().trim().replace(" ", "_"), tool.clone());
         }
         name_to_tool
@@ -72,98 +82,127 @@ where
     A: Agent + Send + Sync,
 {
     async fn call(&self, input_variables: PromptArgs) -> Result<GenerateResult, ChainError> {
-        let mut input_variables = input_variables.clone();
-        let name_to_tools = self.get_name_to_tools();
-        let mut steps: Vec<(AgentAction, String)> = Vec::new();
-        log::debug!("steps: {:?}", steps);
-        if let Some(memory) = &self.memory {
-            let memory = memory.lock().await;
-            input_variables.insert("chat_history".to_string(), json!(memory.messages()));
-        } else {
-            input_variables.insert(
-                "chat_history".to_string(),
-                json!(SimpleMemory::new().messages()),
-            );
-        }
+        let run = trace_chain(
+            "AgentExecutor",
+            input_variables,
+            |input_variables| async move {
+                let mut input_variables = input_variables.clone();
+                let name_to_tools = self.get_name_to_tools();
+                let mut steps: Vec<(AgentAction, String)> = Vec::new();
+                tracing::debug!("steps: {:?}", steps);
+                if let Some(memory) = &self.memory {
+                    let memory = memory.lock().await;
+                    input_variables.insert("chat_history".to_string(), json!(memory.messages()));
+                } else {
+                    input_variables.insert(
+                        "chat_history".to_string(),
+                        json!(SimpleMemory::new().messages()),
+                    );
+                }
 
-        loop {
-            let agent_event = self
-                .agent
-                .plan(&steps, input_variables.clone())
-                .await
-                .map_err(|e| ChainError::AgentError(format!("Error in agent planning: {}", e)))?;
-            match agent_event {
-                AgentEvent::Action(actions) => {
-                    for action in actions {
-                        log::debug!("Action: {:?}", action.tool_input);
-                        let tool = name_to_tools
-                            .get(&action.tool)
-                            .ok_or_else(|| {
-                                AgentError::ToolError(format!("Tool {} not found", action.tool))
-                            })
-                            .map_err(|e| ChainError::AgentError(e.to_string()))?;
-
-                        let observation_result = tool.call(&action.tool_input).await;
-
-                        let observation = match observation_result {
-                            Ok(result) => result,
-                            Err(err) => {
-                                log::info!(
-                                    "The tool return the following error: {}",
-                                    err.to_string()
-                                );
-                                if self.break_if_error {
-                                    return Err(ChainError::AgentError(
-                                        AgentError::ToolError(err.to_string()).to_string(),
+                loop {
+                    let agent_event = self
+                        .agent
+                        .plan(&steps, input_variables.clone())
+                        .await
+                        .map_err(|e| {
+                            ChainError::AgentError(format!("Error in agent planning: {}", e))
+                        })?;
+                    match agent_event {
+                        AgentEvent::Action(actions) => {
+                            for action in actions {
+                                tracing::debug!("Action: {:?}", action.tool_input);
+                                let tool = name_to_tools
+                                    .get(&action.tool)
+                                    .ok_or_else(|| {
+                                        AgentError::ToolError(format!(
+                                            "Tool {} not found",
+                                            action.tool
+                                        ))
+                                    })
+                                    .map_err(|e| ChainError::AgentError(e.to_string()))?;
+
+                                let observation_result = tool.call(&action.tool_input).await;
+
+                                let observation = match observation_result {
+                                    Ok(result) => result,
+                                    Err(err) => {
+                                        tracing::info!(
+                                            "The tool return the following error: {}",
+                                            err.to_string()
+                                        );
+                                        if self.break_if_error {
+                                            return Err(ChainError::AgentError(
+                                                AgentError::ToolError(err.to_string()).to_string(),
+                                            ));
+                                        } else {
+                                            format!("The tool return the following error: {}", err)
+                                        }
+                                    }
+                                };
+
+                                steps.push((action, observation));
+                            }
+                        }
+                        AgentEvent::Finish(finish) => {
+                            if let Some(memory) = &self.memory {
+                                let mut memory = memory.lock().await;
+
+                                memory.add_user_message(match &input_variables["input"] {
+                                    // This avoids adding extra quotes to the user input in the history.
+                                    serde_json::Value::String(s) => s,
+                                    x => x, // this the json encoded value.
+                                });
+
+                                let mut tools_ai_message_seen: HashMap<String, ()> =
+                                    HashMap::default();
+                                for (action, observation) in steps {
+                                    let LogTools { tool_id, tools } =
+                                        serde_json::from_str(&action.log)?;
+                                    let tool_calls: Vec<FunctionCallResponse> =
+                                        serde_json::from_str(&tools)?;
+                                    if tools_ai_message_seen.insert(tools, ()).is_none() {
+                                        memory.add_message(
+                                            Message::new_ai_message("").with_tool_calls(
+                                                tool_calls
+                                                    .into_iter()
+                                                    .map(ToolCall::from)
+                                                    .collect(),
+                                            ),
+                                        );
+                                    }
+                                    memory.add_message(Message::new_tool_message(
+                                        observation,
+                                        tool_id,
                                     ));
-                                } else {
-                                    format!("The tool return the following error: {}", err)
                                 }
-                            }
-                        };
 
-                        steps.push((action, observation));
-                    }
-                }
-                AgentEvent::Finish(finish) => {
-                    if let Some(memory) = &self.memory {
-                        let mut memory = memory.lock().await;
-
-                        memory.add_user_message(match &input_variables["input"] {
-                            // This avoids adding extra quotes to the user input in the history.
-                            serde_json::Value::String(s) => s,
-                            x => x, // this the json encoded value.
-                        });
-
-                        let mut tools_ai_message_seen: HashMap<String, ()> = HashMap::default();
-                        for (action, observation) in steps {
-                            let LogTools { tool_id, tools } = serde_json::from_str(&action.log)?;
-                            let tools_value: serde_json::Value = serde_json::from_str(&tools)?;
-                            if tools_ai_message_seen.insert(tools, ()).is_none() {
-                                memory.add_message(
-                                    Message::new_ai_message("").with_tool_calls(tools_value),
-                                );
+                                memory.add_ai_message(&finish.output);
                             }
-                            memory.add_message(Message::new_tool_message(observation, tool_id));
+                            return Ok(GenerateResult {
+                                generation: finish.output,
+                                ..Default::default()
+                            });
                         }
+                    }
 
-                        memory.add_ai_message(&finish.output);
+                    if let Some(max_iterations) = self.max_iterations {
+                        if steps.len() >= max_iterations as usize {
+                            return Ok(GenerateResult {
+                                generation: "Max iterations reached".to_string(),
+                                ..Default::default()
+                            });
+                        }
                     }
-                    return Ok(GenerateResult {
-                        generation: finish.output,
-                        ..Default::default()
-                    });
-                }
-            }
-
-            if let Some(max_iterations) = self.max_iterations {
-                if steps.len() >= max_iterations as usize {
-                    return Ok(GenerateResult {
-                        generation: "Max iterations reached".to_string(),
-                        ..Default::default()
-                    });
                 }
-            }
+            },
+        );
+        match &self.cancellation_token {
+            Some(token) => token
+                .run_until_cancelled(run)
+                .await
+                .unwrap_or(Err(ChainError::Cancelled)),
+            None => run.await,
         }
     }
 
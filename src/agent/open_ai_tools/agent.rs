@@ -7,11 +7,10 @@ use serde_json::json;
 use crate::{
     agent::{Agent, AgentError},
     chain::Chain,
-    fmt_message, fmt_placeholder, fmt_template, message_formatter,
-    prompt::{HumanMessagePromptTemplate, MessageFormatterStruct, PromptArgs},
+    prompt::{ChatPromptTemplate, PromptArgs},
     schemas::{
         agent::{AgentAction, AgentEvent, AgentFinish, LogTools},
-        messages::Message,
+        messages::{Message, ToolCall},
         FunctionCallResponse,
     },
     template_jinja2,
@@ -24,16 +23,12 @@ pub struct OpenAiToolAgent {
 }
 
 impl OpenAiToolAgent {
-    pub fn create_prompt(prefix: &str) -> Result<MessageFormatterStruct, AgentError> {
-        let prompt = message_formatter![
-            fmt_message!(Message::new_system_message(prefix)),
-            fmt_placeholder!("chat_history"),
-            fmt_template!(HumanMessagePromptTemplate::new(template_jinja2!(
-                "{{input}}",
-                "input"
-            ))),
-            fmt_placeholder!("agent_scratchpad")
-        ];
+    pub fn create_prompt(prefix: &str) -> Result<ChatPromptTemplate, AgentError> {
+        let prompt = ChatPromptTemplate::new()
+            .with_message(Message::new_system_message(prefix))
+            .with_messages_placeholder("chat_history")
+            .with_human_template(template_jinja2!("{{input}}", "input"))
+            .with_messages_placeholder("agent_scratchpad");
 
         Ok(prompt)
     }
@@ -55,7 +50,10 @@ impl OpenAiToolAgent {
             // the scratchpad before the related observations.  There can also be multiple
             // different actions in the same thought chain.
             if tools_ai_message_seen.insert(tools, ()).is_none() {
-                thoughts.push(Message::new_ai_message("").with_tool_calls(json!(tools_vec)));
+                thoughts.push(
+                    Message::new_ai_message("")
+                        .with_tool_calls(tools_vec.into_iter().map(ToolCall::from).collect()),
+                );
             }
 
             // Add a tool message for each observation. Observation is the ouput of the tool call.
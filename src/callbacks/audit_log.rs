@@ -0,0 +1,358 @@
+use std::{
+    sync::{Arc, OnceLock},
+    time::SystemTime,
+};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use regex::Regex;
+use serde_json::{json, Value};
+use sqlx::{Pool, Postgres, Sqlite};
+use tokio::sync::{mpsc, oneshot};
+use uuid::Uuid;
+
+use crate::{
+    error::LangChainError,
+    language_models::GenerateResult,
+    platform::spawn,
+    prompt::PromptArgs,
+    schemas::{Document, Message},
+};
+
+use super::{CallbackHandler, RunInfo};
+
+/// The database the audit log is written to.
+#[derive(Clone)]
+pub enum AuditDatabase {
+    Postgres(Pool<Postgres>),
+    Sqlite(Pool<Sqlite>),
+}
+
+impl From<Pool<Postgres>> for AuditDatabase {
+    fn from(pool: Pool<Postgres>) -> Self {
+        AuditDatabase::Postgres(pool)
+    }
+}
+
+impl From<Pool<Sqlite>> for AuditDatabase {
+    fn from(pool: Pool<Sqlite>) -> Self {
+        AuditDatabase::Sqlite(pool)
+    }
+}
+
+/// Rewrites a string before it is written to the audit log, e.g. to mask personal data.
+pub type Redactor = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// One row of the audit log.
+struct AuditEntry {
+    id: Uuid,
+    run: RunInfo,
+    event: &'static str,
+    content: Value,
+    created_at: DateTime<Utc>,
+}
+
+enum AuditCommand {
+    Write(Box<AuditEntry>),
+    Flush(oneshot::Sender<()>),
+}
+
+/// Persists every prompt, response, tool call and retrieval, with the run metadata, to a
+/// Postgres or SQLite table for compliance.
+///
+/// Each run produces a `start` row with its inputs and an `end` or `error` row with its
+/// outputs. Content and metadata pass through the redactors, in order, before being
+/// written. Rows are written from a background task, started on the first event, so the
+/// handler must be used inside a tokio runtime; call `flush` to wait until queued rows are
+/// written. Write failures are logged with `tracing` and the rows dropped.
+///
+/// # Usage
+/// ```rust,ignore
+/// let pool = sqlx::PgPool::connect(&std::env::var("DATABASE_URL")?).await?;
+/// let audit = Arc::new(
+///     AuditLogHandler::new(pool)
+///         .with_redact_regex(Regex::new(r"\b\d{16}\b")?, "[CARD]"),
+/// );
+/// audit.initialize().await?;
+/// let manager = CallbackManager::new().with_shared_handler(audit.clone());
+/// ```
+pub struct AuditLogHandler {
+    database: AuditDatabase,
+    table: String,
+    redactors: Vec<Redactor>,
+    sender: OnceLock<mpsc::UnboundedSender<AuditCommand>>,
+}
+
+impl AuditLogHandler {
+    pub fn new<D: Into<AuditDatabase>>(database: D) -> Self {
+        Self {
+            database: database.into(),
+            table: "langchain_audit_log".to_string(),
+            redactors: Vec::new(),
+            sender: OnceLock::new(),
+        }
+    }
+
+    pub fn with_table<S: Into<String>>(mut self, table: S) -> Self {
+        self.table = table.into();
+        self
+    }
+
+    pub fn with_redactor<F>(mut self, redactor: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.redactors.push(Arc::new(redactor));
+        self
+    }
+
+    /// Replaces every match of `pattern` with `replacement`.
+    pub fn with_redact_regex<S: Into<String>>(self, pattern: Regex, replacement: S) -> Self {
+        let replacement = replacement.into();
+        self.with_redactor(move |text| pattern.replace_all(text, replacement.as_str()).into_owned())
+    }
+
+    /// Creates the audit table if it does not exist.
+    pub async fn initialize(&self) -> Result<(), sqlx::Error> {
+        let query = format!(
+            r#"
+            CREATE TABLE IF NOT EXISTS {}
+            (
+              id TEXT PRIMARY KEY,
+              run_id TEXT NOT NULL,
+              parent_run_id TEXT,
+              root_run_id TEXT NOT NULL,
+              run_type TEXT NOT NULL,
+              name TEXT NOT NULL,
+              event TEXT NOT NULL,
+              content TEXT NOT NULL,
+              metadata TEXT NOT NULL,
+              created_at TEXT NOT NULL
+            )
+            "#,
+            self.table
+        );
+        match &self.database {
+            AuditDatabase::Postgres(pool) => sqlx::query(&query).execute(pool).await.map(|_| ()),
+            AuditDatabase::Sqlite(pool) => sqlx::query(&query).execute(pool).await.map(|_| ()),
+        }
+    }
+
+    /// Waits until the rows queued so far are written.
+    pub async fn flush(&self) {
+        let Some(sender) = self.sender.get() else {
+            return;
+        };
+        let (done, wait) = oneshot::channel();
+        if sender.send(AuditCommand::Flush(done)).is_ok() {
+            let _ = wait.await;
+        }
+    }
+
+    fn redact(&self, value: Value) -> Value {
+        match value {
+            Value::String(text) => Value::String(
+                self.redactors
+                    .iter()
+                    .fold(text, |text, redactor| redactor(&text)),
+            ),
+            Value::Array(values) => {
+                Value::Array(values.into_iter().map(|value| self.redact(value)).collect())
+            }
+            Value::Object(object) => Value::Object(
+                object
+                    .into_iter()
+                    .map(|(key, value)| (key, self.redact(value)))
+                    .collect(),
+            ),
+            value => value,
+        }
+    }
+
+    fn record(&self, run: &RunInfo, event: &'static str, content: Value) {
+        let mut run = run.clone();
+        run.metadata = run
+            .metadata
+            .into_iter()
+            .map(|(key, value)| (key, self.redact(value)))
+            .collect();
+        let entry = AuditEntry {
+            id: Uuid::new_v4(),
+            run,
+            event,
+            content: self.redact(content),
+            created_at: SystemTime::now().into(),
+        };
+
+        let sender = self.sender.get_or_init(|| {
+            let (sender, receiver) = mpsc::unbounded_channel();
+            spawn(write_entries(
+                self.database.clone(),
+                self.table.clone(),
+                receiver,
+            ));
+            sender
+        });
+        let _ = sender.send(AuditCommand::Write(Box::new(entry)));
+    }
+}
+
+impl CallbackHandler for AuditLogHandler {
+    fn on_llm_start(&self, run: &RunInfo, messages: &[Message]) {
+        self.record(run, "start", json!({ "messages": messages }));
+    }
+
+    fn on_llm_end(&self, run: &RunInfo, result: &GenerateResult) {
+        self.record(run, "end", json!(result));
+    }
+
+    fn on_chain_start(&self, run: &RunInfo, inputs: &PromptArgs) {
+        self.record(run, "start", json!(inputs));
+    }
+
+    fn on_chain_end(&self, run: &RunInfo, result: &GenerateResult) {
+        self.record(run, "end", json!(result));
+    }
+
+    fn on_tool_start(&self, run: &RunInfo, input: &str) {
+        self.record(run, "start", json!({ "input": input }));
+    }
+
+    fn on_tool_end(&self, run: &RunInfo, output: &str) {
+        self.record(run, "end", json!({ "output": output }));
+    }
+
+    fn on_retriever_start(&self, run: &RunInfo, query: &str) {
+        self.record(run, "start", json!({ "query": query }));
+    }
+
+    fn on_retriever_end(&self, run: &RunInfo, documents: &[Document]) {
+        self.record(run, "end", json!({ "documents": documents }));
+    }
+
+    fn on_error(&self, run: &RunInfo, error: &str) {
+        self.record(run, "error", json!({ "error": error }));
+    }
+}
+
+async fn write_entries(
+    database: AuditDatabase,
+    table: String,
+    mut receiver: mpsc::UnboundedReceiver<AuditCommand>,
+) {
+    while let Some(command) = receiver.recv().await {
+        match command {
+            AuditCommand::Write(entry) => {
+                if let Err(e) = write_entry(&database, &table, &entry).await {
+                    tracing::warn!(error = %e, run_id = %entry.run.run_id, "Failed to write audit log entry");
+                }
+            }
+            AuditCommand::Flush(done) => {
+                let _ = done.send(());
+            }
+        }
+    }
+}
+
+async fn write_entry(
+    database: &AuditDatabase,
+    table: &str,
+    entry: &AuditEntry,
+) -> Result<(), sqlx::Error> {
+    let query = format!(
+        "INSERT INTO {table} (id, run_id, parent_run_id, root_run_id, run_type, name, event, content, metadata, created_at) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)"
+    );
+    let run = &entry.run;
+    let values = [
+        Some(entry.id.to_string()),
+        Some(run.run_id.to_string()),
+        run.parent_run_id.map(|id| id.to_string()),
+        Some(run.root_run_id.to_string()),
+        Some(run.run_type.as_str().to_string()),
+        Some(run.name.clone()),
+        Some(entry.event.to_string()),
+        Some(entry.content.to_string()),
+        Some(json!(run.metadata).to_string()),
+        Some(
+            entry
+                .created_at
+                .to_rfc3339_opts(SecondsFormat::Micros, true),
+        ),
+    ];
+
+    match database {
+        AuditDatabase::Postgres(pool) => {
+            let mut query = sqlx::query(&query);
+            for value in values {
+                query = query.bind(value);
+            }
+            query.execute(pool).await?;
+        }
+        AuditDatabase::Sqlite(pool) => {
+            let mut query = sqlx::query(&query);
+            for value in values {
+                query = query.bind(value);
+            }
+            query.execute(pool).await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{sqlite::SqlitePoolOptions, Row};
+
+    use crate::callbacks::{trace_chain, trace_tool, CallbackManager};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_audit_log_sqlite() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let audit = Arc::new(
+            AuditLogHandler::new(pool.clone())
+                .with_redact_regex(Regex::new(r"\S+@\S+").unwrap(), "[EMAIL]"),
+        );
+        audit.initialize().await.unwrap();
+
+        let manager = CallbackManager::new()
+            .with_shared_handler(audit.clone())
+            .with_metadata("user", "jane@example.com");
+        manager
+            .scope(trace_chain("Agent", PromptArgs::new(), |_| async {
+                let _ = trace_tool("send_mail", "to bob@example.com", async {
+                    Ok::<String, LangChainError>("sent".to_string())
+                })
+                .await;
+                Ok(GenerateResult::default())
+            }))
+            .await
+            .unwrap();
+        audit.flush().await;
+
+        let rows = sqlx::query(
+            "SELECT name, event, content, metadata FROM langchain_audit_log ORDER BY rowid",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        let rows: Vec<(String, String, String, String)> = rows
+            .iter()
+            .map(|row| (row.get(0), row.get(1), row.get(2), row.get(3)))
+            .collect();
+
+        assert_eq!(rows.len(), 4);
+        assert_eq!(
+            (rows[1].0.as_str(), rows[1].1.as_str()),
+            ("send_mail", "start")
+        );
+        assert_eq!(rows[1].2, r#"{"input":"to [EMAIL]"}"#);
+        assert_eq!(rows[1].3, r#"{"user":"[EMAIL]"}"#);
+        assert_eq!((rows[3].0.as_str(), rows[3].1.as_str()), ("Agent", "end"));
+    }
+}
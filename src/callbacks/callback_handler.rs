@@ -0,0 +1,106 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    language_models::GenerateResult,
+    prompt::PromptArgs,
+    schemas::{Document, Message},
+};
+
+/// The kind of component a run belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunType {
+    Llm,
+    Chain,
+    Tool,
+    Retriever,
+}
+
+impl RunType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RunType::Llm => "llm",
+            RunType::Chain => "chain",
+            RunType::Tool => "tool",
+            RunType::Retriever => "retriever",
+        }
+    }
+}
+
+/// Identifies one execution of a component. Runs started while another run is in progress
+/// are its children, so a chain calling an LLM and a tool produces a tree of runs sharing
+/// the same `root_run_id`.
+#[derive(Debug, Clone)]
+pub struct RunInfo {
+    pub run_id: Uuid,
+    pub parent_run_id: Option<Uuid>,
+    pub root_run_id: Uuid,
+    pub run_type: RunType,
+    /// Name of the component, e.g. `LLMChain` or the tool name.
+    pub name: String,
+    pub start_time: SystemTime,
+    /// Metadata of the `CallbackManager` plus run specific entries such as `model`.
+    pub metadata: HashMap<String, Value>,
+    started_at: Instant,
+}
+
+impl RunInfo {
+    pub(crate) fn new(
+        run_type: RunType,
+        name: &str,
+        parent: Option<&RunInfo>,
+        metadata: HashMap<String, Value>,
+    ) -> Self {
+        let run_id = Uuid::new_v4();
+        Self {
+            run_id,
+            parent_run_id: parent.map(|parent| parent.run_id),
+            root_run_id: parent.map_or(run_id, |parent| parent.root_run_id),
+            run_type,
+            name: name.to_string(),
+            start_time: SystemTime::now(),
+            metadata,
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Time elapsed since the run started.
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+}
+
+/// Receives the events of LLM, chain, tool and retriever runs.
+///
+/// Every method has an empty default implementation, so handlers only implement the events
+/// they need. Methods are called synchronously on the task running the component and must
+/// not block: handlers doing IO should buffer events and send them from a background task.
+pub trait CallbackHandler: Send + Sync {
+    fn on_llm_start(&self, _run: &RunInfo, _messages: &[Message]) {}
+
+    fn on_llm_new_token(&self, _run: &RunInfo, _token: &str) {}
+
+    fn on_llm_end(&self, _run: &RunInfo, _result: &GenerateResult) {}
+
+    fn on_chain_start(&self, _run: &RunInfo, _inputs: &PromptArgs) {}
+
+    fn on_chain_end(&self, _run: &RunInfo, _result: &GenerateResult) {}
+
+    fn on_tool_start(&self, _run: &RunInfo, _input: &str) {}
+
+    fn on_tool_end(&self, _run: &RunInfo, _output: &str) {}
+
+    fn on_retriever_start(&self, _run: &RunInfo, _query: &str) {}
+
+    fn on_retriever_end(&self, _run: &RunInfo, _documents: &[Document]) {}
+
+    /// Called instead of the end event when a run of any type fails.
+    fn on_error(&self, _run: &RunInfo, _error: &str) {}
+}
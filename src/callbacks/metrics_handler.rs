@@ -0,0 +1,225 @@
+use metrics::{counter, histogram, Label};
+use serde_json::Value;
+
+use crate::{
+    language_models::GenerateResult,
+    prompt::PromptArgs,
+    schemas::{Document, Message},
+};
+
+use super::{CallbackHandler, RunInfo, RunType};
+
+/// Records metrics of LLM, chain, tool and retriever runs through the `metrics` facade, so
+/// any installed recorder, such as a Prometheus exporter, exposes them.
+///
+/// | Metric | Type | Labels |
+/// |--------|------|--------|
+/// | `langchain_requests_total` | counter | `run_type`, `name`, `model` |
+/// | `langchain_errors_total` | counter | `run_type`, `name`, `model` |
+/// | `langchain_latency_seconds` | histogram | `run_type`, `name`, `model` |
+/// | `langchain_tokens_total` | counter | `name`, `model`, `token_type` (`prompt` or `completion`) |
+///
+/// `model` is only set on LLM runs. Metadata keys given to `with_label` are added as
+/// labels; keep them to values of low cardinality such as a feature name.
+///
+/// # Usage
+/// ```rust,ignore
+/// metrics_exporter_prometheus::PrometheusBuilder::new().install()?;
+/// let manager = CallbackManager::new().with_handler(MetricsHandler::new());
+/// ```
+pub struct MetricsHandler {
+    prefix: String,
+    labels: Vec<String>,
+}
+
+impl MetricsHandler {
+    pub fn new() -> Self {
+        Self {
+            prefix: "langchain".to_string(),
+            labels: Vec::new(),
+        }
+    }
+
+    /// Sets the prefix of the metric names, `langchain` by default.
+    pub fn with_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Adds the metadata `key` of the runs as a label.
+    pub fn with_label<S: Into<String>>(mut self, key: S) -> Self {
+        self.labels.push(key.into());
+        self
+    }
+
+    fn name(&self, metric: &str) -> String {
+        format!("{}_{}", self.prefix, metric)
+    }
+
+    fn run_labels(&self, run: &RunInfo) -> Vec<Label> {
+        let mut labels = vec![
+            Label::new("run_type", run.run_type.as_str()),
+            Label::new("name", run.name.clone()),
+        ];
+        if run.run_type == RunType::Llm {
+            labels.push(Label::new("model", metadata_value(run, "model")));
+        }
+        for key in &self.labels {
+            labels.push(Label::new(key.clone(), metadata_value(run, key)));
+        }
+        labels
+    }
+
+    fn start(&self, run: &RunInfo) {
+        counter!(self.name("requests_total"), self.run_labels(run)).increment(1);
+    }
+
+    fn end(&self, run: &RunInfo) {
+        histogram!(self.name("latency_seconds"), self.run_labels(run))
+            .record(run.elapsed().as_secs_f64());
+    }
+}
+
+impl Default for MetricsHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CallbackHandler for MetricsHandler {
+    fn on_llm_start(&self, run: &RunInfo, _messages: &[Message]) {
+        self.start(run);
+    }
+
+    fn on_llm_end(&self, run: &RunInfo, result: &GenerateResult) {
+        self.end(run);
+        let Some(tokens) = &result.tokens else {
+            return;
+        };
+        for (token_type, count) in [
+            ("prompt", tokens.prompt_tokens),
+            ("completion", tokens.completion_tokens),
+        ] {
+            let mut labels = self.run_labels(run);
+            labels.retain(|label| label.key() != "run_type");
+            labels.push(Label::new("token_type", token_type));
+            counter!(self.name("tokens_total"), labels).increment(count as u64);
+        }
+    }
+
+    fn on_chain_start(&self, run: &RunInfo, _inputs: &PromptArgs) {
+        self.start(run);
+    }
+
+    fn on_chain_end(&self, run: &RunInfo, _result: &GenerateResult) {
+        self.end(run);
+    }
+
+    fn on_tool_start(&self, run: &RunInfo, _input: &str) {
+        self.start(run);
+    }
+
+    fn on_tool_end(&self, run: &RunInfo, _output: &str) {
+        self.end(run);
+    }
+
+    fn on_retriever_start(&self, run: &RunInfo, _query: &str) {
+        self.start(run);
+    }
+
+    fn on_retriever_end(&self, run: &RunInfo, _documents: &[Document]) {
+        self.end(run);
+    }
+
+    fn on_error(&self, run: &RunInfo, _error: &str) {
+        counter!(self.name("errors_total"), self.run_labels(run)).increment(1);
+        self.end(run);
+    }
+}
+
+fn metadata_value(run: &RunInfo, key: &str) -> String {
+    match run.metadata.get(key) {
+        Some(Value::String(value)) => value.clone(),
+        Some(value) => value.to_string(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    use crate::language_models::TokenUsage;
+
+    use super::*;
+
+    #[test]
+    fn test_metrics_handler() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let handler = MetricsHandler::new().with_label("feature");
+
+        let metadata = HashMap::from([
+            ("model".to_string(), Value::from("gpt-4o-mini")),
+            ("feature".to_string(), Value::from("search")),
+        ]);
+        let llm = RunInfo::new(RunType::Llm, "OpenAI", None, metadata);
+        let tool = RunInfo::new(RunType::Tool, "calculator", None, HashMap::new());
+
+        metrics::with_local_recorder(&recorder, || {
+            handler.on_llm_start(&llm, &[]);
+            handler.on_llm_end(
+                &llm,
+                &GenerateResult {
+                    generation: String::new(),
+                    tokens: Some(TokenUsage::new(12, 3)),
+                },
+            );
+            handler.on_tool_start(&tool, "1 + 1");
+            handler.on_error(&tool, "boom");
+        });
+
+        let metrics: Vec<(String, Vec<String>, DebugValue)> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _, _, value)| {
+                let key = key.key();
+                let labels = key
+                    .labels()
+                    .map(|label| format!("{}={}", label.key(), label.value()))
+                    .collect();
+                (key.name().to_string(), labels, value)
+            })
+            .collect();
+        let find = |name: &str, label: &str| {
+            metrics
+                .iter()
+                .find(|(n, labels, _)| n == name && labels.iter().any(|l| l == label))
+                .map(|(_, _, value)| value)
+        };
+
+        assert_eq!(
+            find("langchain_requests_total", "model=gpt-4o-mini"),
+            Some(&DebugValue::Counter(1))
+        );
+        assert_eq!(
+            find("langchain_tokens_total", "token_type=prompt"),
+            Some(&DebugValue::Counter(12))
+        );
+        assert_eq!(
+            find("langchain_tokens_total", "feature=search"),
+            Some(&DebugValue::Counter(12))
+        );
+        assert_eq!(
+            find("langchain_errors_total", "name=calculator"),
+            Some(&DebugValue::Counter(1))
+        );
+        assert!(matches!(
+            find("langchain_latency_seconds", "run_type=tool"),
+            Some(DebugValue::Histogram(values)) if values.len() == 1
+        ));
+    }
+}
@@ -0,0 +1,29 @@
+mod callback_handler;
+pub use callback_handler::*;
+
+mod run_context;
+pub use run_context::*;
+
+#[cfg(feature = "audit-log")]
+mod audit_log;
+#[cfg(feature = "audit-log")]
+pub use audit_log::*;
+
+#[cfg(feature = "metrics")]
+mod metrics_handler;
+#[cfg(feature = "metrics")]
+pub use metrics_handler::*;
+
+mod stream_events;
+pub use stream_events::*;
+
+mod token_cost_handler;
+pub use token_cost_handler::*;
+
+mod trace_exporter;
+pub use trace_exporter::*;
+
+#[cfg(feature = "opentelemetry")]
+mod otel_handler;
+#[cfg(feature = "opentelemetry")]
+pub use otel_handler::*;
@@ -0,0 +1,259 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, PoisonError},
+};
+
+use opentelemetry::{
+    global::{self, BoxedTracer},
+    trace::{SpanKind, Status, TraceContextExt, Tracer},
+    Context, KeyValue,
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    language_models::GenerateResult,
+    prompt::PromptArgs,
+    schemas::{Document, Message},
+};
+
+use super::{CallbackHandler, RunInfo, RunType};
+
+/// Emits an OpenTelemetry span for every LLM call, chain step, tool invocation and
+/// retrieval, nested the same way as the runs.
+///
+/// Spans carry the run type and id, the `CallbackManager` metadata, the model and token
+/// usage of LLM calls following the `gen_ai` semantic conventions, and the latency in
+/// milliseconds. Root runs are children of the current OpenTelemetry context, so they show up
+/// under the span of the request being served. Prompts and outputs are only recorded when
+/// enabled with `with_record_content`, as they may contain personal data.
+///
+/// # Usage
+/// ```rust,ignore
+/// let manager = CallbackManager::new().with_handler(OpenTelemetryHandler::new());
+/// let answer = manager.scope(chain.invoke(input_variables)).await?;
+/// ```
+pub struct OpenTelemetryHandler {
+    tracer: BoxedTracer,
+    record_content: bool,
+    contexts: Mutex<HashMap<Uuid, Context>>,
+}
+
+impl OpenTelemetryHandler {
+    /// Creates a handler using the tracer of the global tracer provider.
+    pub fn new() -> Self {
+        Self {
+            tracer: global::tracer("langchain-rust"),
+            record_content: false,
+            contexts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_tracer<T>(mut self, tracer: T) -> Self
+    where
+        T: Tracer + Send + Sync + 'static,
+        T::Span: Send + Sync + 'static,
+    {
+        self.tracer = BoxedTracer::new(Box::new(tracer));
+        self
+    }
+
+    /// Records prompts, inputs and outputs as span attributes.
+    pub fn with_record_content(mut self, record_content: bool) -> Self {
+        self.record_content = record_content;
+        self
+    }
+
+    fn start_span(&self, run: &RunInfo, mut attributes: Vec<KeyValue>) {
+        let mut contexts = self.contexts.lock().unwrap_or_else(PoisonError::into_inner);
+        let parent = run
+            .parent_run_id
+            .and_then(|parent_run_id| contexts.get(&parent_run_id).cloned())
+            .unwrap_or_else(Context::current);
+
+        attributes.push(KeyValue::new("langchain.run_type", run.run_type.as_str()));
+        attributes.push(KeyValue::new("langchain.run_id", run.run_id.to_string()));
+        for (key, value) in &run.metadata {
+            let value = match value {
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+            if key == "model" {
+                attributes.push(KeyValue::new("gen_ai.request.model", value));
+            } else {
+                attributes.push(KeyValue::new(format!("langchain.metadata.{key}"), value));
+            }
+        }
+
+        let kind = match run.run_type {
+            RunType::Llm => SpanKind::Client,
+            _ => SpanKind::Internal,
+        };
+        let span = self
+            .tracer
+            .span_builder(run.name.clone())
+            .with_kind(kind)
+            .with_attributes(attributes)
+            .start_with_context(&self.tracer, &parent);
+        contexts.insert(run.run_id, parent.with_span(span));
+    }
+
+    fn end_span(&self, run: &RunInfo, mut attributes: Vec<KeyValue>, status: Status) {
+        let context = self
+            .contexts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&run.run_id);
+        let Some(context) = context else {
+            return;
+        };
+
+        attributes.push(KeyValue::new(
+            "langchain.latency_ms",
+            run.elapsed().as_millis() as i64,
+        ));
+        let span = context.span();
+        span.set_attributes(attributes);
+        span.set_status(status);
+        span.end();
+    }
+
+    fn content(&self, key: &'static str, value: impl FnOnce() -> String) -> Vec<KeyValue> {
+        if self.record_content {
+            vec![KeyValue::new(key, value())]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl Default for OpenTelemetryHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CallbackHandler for OpenTelemetryHandler {
+    fn on_llm_start(&self, run: &RunInfo, messages: &[Message]) {
+        let attributes = self.content("langchain.input", || {
+            serde_json::to_string(messages).unwrap_or_default()
+        });
+        self.start_span(run, attributes);
+    }
+
+    fn on_llm_end(&self, run: &RunInfo, result: &GenerateResult) {
+        let mut attributes = self.content("langchain.output", || result.generation.clone());
+        if let Some(tokens) = &result.tokens {
+            attributes.push(KeyValue::new(
+                "gen_ai.usage.input_tokens",
+                tokens.prompt_tokens as i64,
+            ));
+            attributes.push(KeyValue::new(
+                "gen_ai.usage.output_tokens",
+                tokens.completion_tokens as i64,
+            ));
+        }
+        self.end_span(run, attributes, Status::Ok);
+    }
+
+    fn on_chain_start(&self, run: &RunInfo, inputs: &PromptArgs) {
+        let attributes = self.content("langchain.input", || {
+            serde_json::to_string(inputs).unwrap_or_default()
+        });
+        self.start_span(run, attributes);
+    }
+
+    fn on_chain_end(&self, run: &RunInfo, result: &GenerateResult) {
+        let attributes = self.content("langchain.output", || result.generation.clone());
+        self.end_span(run, attributes, Status::Ok);
+    }
+
+    fn on_tool_start(&self, run: &RunInfo, input: &str) {
+        let mut attributes = self.content("langchain.input", || input.to_string());
+        attributes.push(KeyValue::new("gen_ai.tool.name", run.name.clone()));
+        self.start_span(run, attributes);
+    }
+
+    fn on_tool_end(&self, run: &RunInfo, output: &str) {
+        let attributes = self.content("langchain.output", || output.to_string());
+        self.end_span(run, attributes, Status::Ok);
+    }
+
+    fn on_retriever_start(&self, run: &RunInfo, query: &str) {
+        let attributes = self.content("langchain.input", || query.to_string());
+        self.start_span(run, attributes);
+    }
+
+    fn on_retriever_end(&self, run: &RunInfo, documents: &[Document]) {
+        let attributes = vec![KeyValue::new(
+            "langchain.retriever.documents",
+            documents.len() as i64,
+        )];
+        self.end_span(run, attributes, Status::Ok);
+    }
+
+    fn on_error(&self, run: &RunInfo, error: &str) {
+        self.end_span(run, Vec::new(), Status::error(error.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_sdk::{testing::trace::InMemorySpanExporter, trace::TracerProvider};
+
+    use crate::{
+        callbacks::{trace_chain, trace_llm, CallbackManager},
+        language_models::TokenUsage,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_otel_handler() {
+        let exporter = InMemorySpanExporter::default();
+        let provider = TracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let handler = OpenTelemetryHandler::new().with_tracer(provider.tracer("test"));
+        let manager = CallbackManager::new()
+            .with_shared_handler(Arc::new(handler))
+            .with_metadata("customer", "acme");
+
+        manager
+            .scope(trace_chain("LLMChain", PromptArgs::new(), |_| async {
+                let result = trace_llm("OpenAI", "gpt-4o-mini", &[], async {
+                    Ok(GenerateResult {
+                        generation: "Hello".to_string(),
+                        tokens: Some(TokenUsage::new(3, 1)),
+                    })
+                })
+                .await?;
+                Ok(result)
+            }))
+            .await
+            .unwrap();
+
+        let spans = exporter.get_finished_spans().unwrap();
+        assert_eq!(spans.len(), 2);
+        let (llm, chain) = (&spans[0], &spans[1]);
+        assert_eq!(llm.name, "OpenAI");
+        assert_eq!(chain.name, "LLMChain");
+        assert_eq!(llm.parent_span_id, chain.span_context.span_id());
+        assert_eq!(llm.span_kind, SpanKind::Client);
+
+        let attribute = |key: &str| {
+            llm.attributes
+                .iter()
+                .find(|kv| kv.key.as_str() == key)
+                .map(|kv| kv.value.to_string())
+        };
+        assert_eq!(attribute("gen_ai.request.model").unwrap(), "gpt-4o-mini");
+        assert_eq!(attribute("gen_ai.usage.input_tokens").unwrap(), "3");
+        assert_eq!(attribute("langchain.metadata.customer").unwrap(), "acme");
+        assert!(attribute("langchain.latency_ms").is_some());
+        assert!(attribute("langchain.output").is_none());
+    }
+}
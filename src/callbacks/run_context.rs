@@ -0,0 +1,414 @@
+use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use serde_json::Value;
+use tracing::{field, Instrument, Span};
+
+use crate::{
+    chain::ChainError,
+    error::LangChainError,
+    language_models::{GenerateResult, LLMError},
+    prompt::PromptArgs,
+    schemas::{Document, Message, StreamData},
+};
+
+use super::{CallbackHandler, RunInfo, RunType};
+
+tokio::task_local! {
+    static RUN_CONTEXT: RunContext;
+}
+
+#[derive(Clone)]
+struct RunContext {
+    manager: CallbackManager,
+    run: Option<RunInfo>,
+}
+
+/// Holds the callback handlers that receive the events of every component run inside
+/// `CallbackManager::scope`, along with metadata attached to all of those runs.
+///
+/// The run context is task local: components spawned on other tasks with `tokio::spawn`
+/// need their own `scope`.
+///
+/// # Usage
+/// ```rust,ignore
+/// let manager = CallbackManager::new()
+///     .with_handler(MyHandler::default())
+///     .with_metadata("customer_id", "acme");
+/// let result = manager.scope(chain.call(prompt_args! { "input" => "Hi" })).await?;
+/// ```
+#[derive(Clone, Default)]
+pub struct CallbackManager {
+    handlers: Vec<Arc<dyn CallbackHandler>>,
+    metadata: HashMap<String, Value>,
+}
+
+impl CallbackManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_handler<H: CallbackHandler + 'static>(mut self, handler: H) -> Self {
+        self.handlers.push(Arc::new(handler));
+        self
+    }
+
+    /// Adds a handler that is also kept by the caller, e.g. to read what it aggregated.
+    pub fn with_shared_handler(mut self, handler: Arc<dyn CallbackHandler>) -> Self {
+        self.handlers.push(handler);
+        self
+    }
+
+    /// Adds metadata to every run started in the scope of this manager.
+    pub fn with_metadata<K: Into<String>, V: Into<Value>>(mut self, key: K, value: V) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn handlers(&self) -> &[Arc<dyn CallbackHandler>] {
+        &self.handlers
+    }
+
+    /// Runs `future` with the handlers of this manager receiving the events of the
+    /// components it runs. Scopes nest: inside another scope the handlers and metadata of
+    /// both managers apply, and new runs stay children of the enclosing run.
+    pub async fn scope<F: Future>(&self, future: F) -> F::Output {
+        let context = match RUN_CONTEXT.try_with(Clone::clone) {
+            Ok(outer) => {
+                let mut manager = outer.manager;
+                manager.handlers.extend(self.handlers.iter().cloned());
+                manager.metadata.extend(self.metadata.clone());
+                RunContext {
+                    manager,
+                    run: outer.run,
+                }
+            }
+            Err(_) => RunContext {
+                manager: self.clone(),
+                run: None,
+            },
+        };
+        RUN_CONTEXT.scope(context, future).await
+    }
+}
+
+/// Returns the run in progress on the current task, if any.
+pub fn current_run() -> Option<RunInfo> {
+    RUN_CONTEXT
+        .try_with(|context| context.run.clone())
+        .ok()
+        .flatten()
+}
+
+struct ActiveRun {
+    manager: CallbackManager,
+    info: RunInfo,
+}
+
+impl ActiveRun {
+    /// Starts a child of the current run, or returns `None` when no handler is listening.
+    fn start(run_type: RunType, name: &str, metadata: HashMap<String, Value>) -> Option<Self> {
+        RUN_CONTEXT
+            .try_with(|context| {
+                if context.manager.handlers.is_empty() {
+                    return None;
+                }
+                let mut run_metadata = context.manager.metadata.clone();
+                run_metadata.extend(metadata);
+                Some(ActiveRun {
+                    manager: context.manager.clone(),
+                    info: RunInfo::new(run_type, name, context.run.as_ref(), run_metadata),
+                })
+            })
+            .ok()
+            .flatten()
+    }
+
+    fn emit<F: Fn(&dyn CallbackHandler, &RunInfo)>(&self, event: F) {
+        for handler in &self.manager.handlers {
+            event(handler.as_ref(), &self.info);
+        }
+    }
+
+    async fn scope<F: Future>(&self, future: F) -> F::Output {
+        let context = RunContext {
+            manager: self.manager.clone(),
+            run: Some(self.info.clone()),
+        };
+        RUN_CONTEXT.scope(context, future).await
+    }
+}
+
+/// A tracing span for a run, carrying its id when callbacks are active.
+fn run_span(run_type: RunType, name: &str, run: Option<&ActiveRun>) -> Span {
+    let run_id = run.map(|run| field::display(run.info.run_id));
+    match run_type {
+        RunType::Llm => tracing::info_span!("llm", name, model = field::Empty, run_id),
+        RunType::Chain => tracing::info_span!("chain", name, run_id),
+        RunType::Tool => tracing::info_span!("tool", name, run_id),
+        RunType::Retriever => tracing::info_span!("retriever", name, run_id),
+    }
+}
+
+/// Runs an LLM call as an `llm` run, emitting its start, end and error events.
+pub async fn trace_llm<F>(
+    name: &str,
+    model: &str,
+    messages: &[Message],
+    future: F,
+) -> Result<GenerateResult, LLMError>
+where
+    F: Future<Output = Result<GenerateResult, LLMError>>,
+{
+    let metadata = HashMap::from([("model".to_string(), Value::from(model))]);
+    let run = ActiveRun::start(RunType::Llm, name, metadata);
+    let span = run_span(RunType::Llm, name, run.as_ref());
+    span.record("model", model);
+    let Some(run) = run else {
+        return future.instrument(span).await;
+    };
+
+    run.emit(|handler, info| handler.on_llm_start(info, messages));
+    let result = run.scope(future).instrument(span).await;
+    match &result {
+        Ok(generation) => run.emit(|handler, info| handler.on_llm_end(info, generation)),
+        Err(e) => run.emit(|handler, info| handler.on_error(info, &e.to_string())),
+    }
+    result
+}
+
+/// Wraps an LLM stream as an `llm` run: each chunk is emitted as a new token and the end
+/// event carries the whole generation once the stream is exhausted.
+pub fn trace_llm_stream(
+    name: &str,
+    model: &str,
+    messages: &[Message],
+    llm_stream: Pin<Box<dyn Stream<Item = Result<StreamData, LLMError>> + Send>>,
+) -> Pin<Box<dyn Stream<Item = Result<StreamData, LLMError>> + Send>> {
+    let metadata = HashMap::from([("model".to_string(), Value::from(model))]);
+    let Some(run) = ActiveRun::start(RunType::Llm, name, metadata) else {
+        return llm_stream;
+    };
+    run.emit(|handler, info| handler.on_llm_start(info, messages));
+
+    Box::pin(stream! {
+        let mut llm_stream = llm_stream;
+        let mut result = GenerateResult::default();
+        let mut failed = false;
+        while let Some(item) = llm_stream.next().await {
+            match &item {
+                Ok(data) => {
+                    if !data.content.is_empty() {
+                        run.emit(|handler, info| handler.on_llm_new_token(info, &data.content));
+                        result.generation.push_str(&data.content);
+                    }
+                    if data.tokens.is_some() {
+                        result.tokens = data.tokens.clone();
+                    }
+                }
+                Err(e) => {
+                    failed = true;
+                    run.emit(|handler, info| handler.on_error(info, &e.to_string()));
+                }
+            }
+            yield item;
+        }
+        if !failed {
+            run.emit(|handler, info| handler.on_llm_end(info, &result));
+        }
+    })
+}
+
+/// Emits a token of the `llm` run in progress, for LLMs streaming inside `generate`.
+pub fn emit_llm_new_token(token: &str) {
+    let _ = RUN_CONTEXT.try_with(|context| {
+        if let Some(run) = context
+            .run
+            .as_ref()
+            .filter(|run| run.run_type == RunType::Llm)
+        {
+            for handler in &context.manager.handlers {
+                handler.on_llm_new_token(run, token);
+            }
+        }
+    });
+}
+
+/// Runs a chain call as a `chain` run, emitting its start, end and error events. Runs
+/// started by `call`, such as LLM calls, become children of the chain run.
+pub async fn trace_chain<F, Fut>(
+    name: &str,
+    inputs: PromptArgs,
+    call: F,
+) -> Result<GenerateResult, ChainError>
+where
+    F: FnOnce(PromptArgs) -> Fut,
+    Fut: Future<Output = Result<GenerateResult, ChainError>>,
+{
+    let run = ActiveRun::start(RunType::Chain, name, HashMap::new());
+    let span = run_span(RunType::Chain, name, run.as_ref());
+    let Some(run) = run else {
+        return call(inputs).instrument(span).await;
+    };
+
+    run.emit(|handler, info| handler.on_chain_start(info, &inputs));
+    let result = run.scope(call(inputs)).instrument(span).await;
+    match &result {
+        Ok(generation) => run.emit(|handler, info| handler.on_chain_end(info, generation)),
+        Err(e) => run.emit(|handler, info| handler.on_error(info, &e.to_string())),
+    }
+    result
+}
+
+/// Runs a tool call as a `tool` run, emitting its start, end and error events.
+pub async fn trace_tool<F>(name: &str, input: &str, future: F) -> Result<String, LangChainError>
+where
+    F: Future<Output = Result<String, LangChainError>>,
+{
+    let run = ActiveRun::start(RunType::Tool, name, HashMap::new());
+    let span = run_span(RunType::Tool, name, run.as_ref());
+    let Some(run) = run else {
+        return future.instrument(span).await;
+    };
+
+    run.emit(|handler, info| handler.on_tool_start(info, input));
+    let result = run.scope(future).instrument(span).await;
+    match &result {
+        Ok(output) => run.emit(|handler, info| handler.on_tool_end(info, output)),
+        Err(e) => run.emit(|handler, info| handler.on_error(info, &e.to_string())),
+    }
+    result
+}
+
+/// Runs a retrieval as a `retriever` run, emitting its start, end and error events.
+pub async fn trace_retriever<F>(
+    name: &str,
+    query: &str,
+    future: F,
+) -> Result<Vec<Document>, LangChainError>
+where
+    F: Future<Output = Result<Vec<Document>, LangChainError>>,
+{
+    let run = ActiveRun::start(RunType::Retriever, name, HashMap::new());
+    let span = run_span(RunType::Retriever, name, run.as_ref());
+    let Some(run) = run else {
+        return future.instrument(span).await;
+    };
+
+    run.emit(|handler, info| handler.on_retriever_start(info, query));
+    let result = run.scope(future).instrument(span).await;
+    match &result {
+        Ok(documents) => run.emit(|handler, info| handler.on_retriever_end(info, documents)),
+        Err(e) => run.emit(|handler, info| handler.on_error(info, &e.to_string())),
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingHandler {
+        events: Mutex<Vec<(String, Option<Uuid>, Uuid)>>,
+    }
+
+    impl RecordingHandler {
+        fn record(&self, event: &str, run: &RunInfo) {
+            self.events
+                .lock()
+                .unwrap()
+                .push((event.to_string(), run.parent_run_id, run.run_id));
+        }
+    }
+
+    impl CallbackHandler for RecordingHandler {
+        fn on_llm_start(&self, run: &RunInfo, _messages: &[Message]) {
+            self.record("llm_start", run);
+        }
+        fn on_llm_new_token(&self, run: &RunInfo, token: &str) {
+            self.record(&format!("token:{token}"), run);
+        }
+        fn on_llm_end(&self, run: &RunInfo, _result: &GenerateResult) {
+            self.record("llm_end", run);
+        }
+        fn on_chain_start(&self, run: &RunInfo, _inputs: &PromptArgs) {
+            self.record("chain_start", run);
+        }
+        fn on_chain_end(&self, run: &RunInfo, _result: &GenerateResult) {
+            self.record("chain_end", run);
+        }
+        fn on_tool_start(&self, run: &RunInfo, _input: &str) {
+            self.record("tool_start", run);
+        }
+        fn on_tool_end(&self, run: &RunInfo, _output: &str) {
+            self.record("tool_end", run);
+        }
+        fn on_error(&self, run: &RunInfo, _error: &str) {
+            self.record("error", run);
+        }
+    }
+
+    async fn fake_llm() -> Result<GenerateResult, LLMError> {
+        emit_llm_new_token("Hi");
+        Ok(GenerateResult {
+            generation: "Hi".to_string(),
+            tokens: None,
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_nested_runs() {
+        let handler = Arc::new(RecordingHandler::default());
+        let manager = CallbackManager::new()
+            .with_shared_handler(handler.clone())
+            .with_metadata("customer", "acme");
+
+        manager
+            .scope(trace_chain("Outer", PromptArgs::new(), |_| async {
+                assert_eq!(current_run().unwrap().metadata["customer"], "acme");
+                trace_llm("FakeLLM", "fake-1", &[], fake_llm()).await?;
+                let _ = trace_tool("failing_tool", "input", async {
+                    Err::<String, LangChainError>("boom".into())
+                })
+                .await;
+                Ok(GenerateResult::default())
+            }))
+            .await
+            .unwrap();
+
+        let events = handler.events.lock().unwrap();
+        let names: Vec<&str> = events.iter().map(|(event, ..)| event.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "chain_start",
+                "llm_start",
+                "token:Hi",
+                "llm_end",
+                "tool_start",
+                "error",
+                "chain_end"
+            ]
+        );
+
+        let chain_id = events[0].2;
+        assert_eq!(events[0].1, None);
+        assert_eq!(events[1].1, Some(chain_id));
+        assert_eq!(events[4].1, Some(chain_id));
+        assert_eq!(events[6].2, chain_id);
+    }
+
+    #[tokio::test]
+    async fn test_no_events_outside_scope() {
+        let result = trace_llm("FakeLLM", "fake-1", &[], fake_llm()).await;
+        assert!(result.is_ok());
+        assert!(current_run().is_none());
+    }
+}
@@ -0,0 +1,228 @@
+use std::{future::Future, pin::Pin};
+
+use async_stream::stream;
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::{
+    chain::ChainError,
+    language_models::{GenerateResult, TokenUsage},
+    schemas::Document,
+};
+
+use super::{CallbackHandler, CallbackManager, RunInfo};
+
+/// An event of a chain or agent run, meant to be forwarded to a client as it happens.
+///
+/// Events serialize to JSON objects tagged by `type`, e.g.
+/// `{"type":"token_chunk","run_id":"...","content":"Hel"}`, ready to send as WebSocket
+/// messages or, with `to_sse`, as server-sent events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamEvent {
+    /// A chunk of text streamed by an LLM. Only LLMs called with `stream`, or configured
+    /// with a streaming function, produce chunks.
+    TokenChunk { run_id: Uuid, content: String },
+    ToolStart {
+        run_id: Uuid,
+        tool: String,
+        input: String,
+    },
+    ToolEnd {
+        run_id: Uuid,
+        tool: String,
+        output: String,
+    },
+    RetrievalResult {
+        run_id: Uuid,
+        documents: Vec<Document>,
+    },
+    /// The run finished; always the last event.
+    Done {
+        output: String,
+        tokens: Option<TokenUsage>,
+    },
+    /// The run failed; always the last event.
+    Error { message: String },
+}
+
+impl StreamEvent {
+    /// The `type` tag of the event.
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            StreamEvent::TokenChunk { .. } => "token_chunk",
+            StreamEvent::ToolStart { .. } => "tool_start",
+            StreamEvent::ToolEnd { .. } => "tool_end",
+            StreamEvent::RetrievalResult { .. } => "retrieval_result",
+            StreamEvent::Done { .. } => "done",
+            StreamEvent::Error { .. } => "error",
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Formats the event as a server-sent event frame, named after its type.
+    pub fn to_sse(&self) -> String {
+        format!("event: {}\ndata: {}\n\n", self.event_type(), self.to_json())
+    }
+}
+
+/// Sends the events of the runs in its scope to a channel.
+///
+/// `stream_events` covers the common case of streaming one call; use the handler directly
+/// to collect the events of runs spawned on other tasks.
+pub struct StreamEventHandler {
+    sender: mpsc::UnboundedSender<StreamEvent>,
+}
+
+impl StreamEventHandler {
+    pub fn new() -> (Self, mpsc::UnboundedReceiver<StreamEvent>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        (Self { sender }, receiver)
+    }
+
+    fn send(&self, event: StreamEvent) {
+        let _ = self.sender.send(event);
+    }
+}
+
+impl CallbackHandler for StreamEventHandler {
+    fn on_llm_new_token(&self, run: &RunInfo, token: &str) {
+        self.send(StreamEvent::TokenChunk {
+            run_id: run.run_id,
+            content: token.to_string(),
+        });
+    }
+
+    fn on_tool_start(&self, run: &RunInfo, input: &str) {
+        self.send(StreamEvent::ToolStart {
+            run_id: run.run_id,
+            tool: run.name.clone(),
+            input: input.to_string(),
+        });
+    }
+
+    fn on_tool_end(&self, run: &RunInfo, output: &str) {
+        self.send(StreamEvent::ToolEnd {
+            run_id: run.run_id,
+            tool: run.name.clone(),
+            output: output.to_string(),
+        });
+    }
+
+    fn on_retriever_end(&self, run: &RunInfo, documents: &[Document]) {
+        self.send(StreamEvent::RetrievalResult {
+            run_id: run.run_id,
+            documents: documents.to_vec(),
+        });
+    }
+}
+
+/// Runs `future`, usually a chain or agent call, and streams its events as they happen,
+/// ending with `Done` or `Error`.
+///
+/// # Usage
+/// ```rust,ignore
+/// let events = stream_events(chain.call(prompt_args! { "input" => "Hi" }));
+/// while let Some(event) = events.next().await {
+///     sse_sender.send(event.to_sse()).await?;
+/// }
+/// ```
+pub fn stream_events<'a, F>(future: F) -> Pin<Box<dyn Stream<Item = StreamEvent> + Send + 'a>>
+where
+    F: Future<Output = Result<GenerateResult, ChainError>> + Send + 'a,
+{
+    Box::pin(stream! {
+        let (handler, mut receiver) = StreamEventHandler::new();
+        let manager = CallbackManager::new().with_handler(handler);
+        let run = manager.scope(future);
+        tokio::pin!(run);
+
+        let result = loop {
+            tokio::select! {
+                biased;
+                Some(event) = receiver.recv() => yield event,
+                result = &mut run => break result,
+            }
+        };
+        while let Ok(event) = receiver.try_recv() {
+            yield event;
+        }
+        yield match result {
+            Ok(result) => StreamEvent::Done {
+                output: result.generation,
+                tokens: result.tokens,
+            },
+            Err(e) => StreamEvent::Error {
+                message: e.to_string(),
+            },
+        };
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use crate::{
+        callbacks::{emit_llm_new_token, trace_llm, trace_tool},
+        error::LangChainError,
+        language_models::LLMError,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_stream_events() {
+        let events: Vec<StreamEvent> = stream_events(async {
+            trace_tool("search", "rust", async {
+                Ok::<String, LangChainError>("found".to_string())
+            })
+            .await
+            .map_err(|e| ChainError::OtherError(e.to_string()))?;
+            let result = trace_llm("OpenAI", "gpt-4o-mini", &[], async {
+                emit_llm_new_token("Hel");
+                emit_llm_new_token("lo");
+                Ok::<_, LLMError>(GenerateResult {
+                    generation: "Hello".to_string(),
+                    tokens: None,
+                    ..Default::default()
+                })
+            })
+            .await?;
+            Ok(result)
+        })
+        .collect()
+        .await;
+
+        let types: Vec<&str> = events.iter().map(StreamEvent::event_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                "tool_start",
+                "tool_end",
+                "token_chunk",
+                "token_chunk",
+                "done"
+            ]
+        );
+        assert!(events[2]
+            .to_sse()
+            .starts_with("event: token_chunk\ndata: {\"type\":\"token_chunk\""));
+        assert_eq!(
+            events[4].to_json(),
+            r#"{"type":"done","output":"Hello","tokens":null}"#
+        );
+
+        let events: Vec<StreamEvent> =
+            stream_events(async { Err(ChainError::OtherError("boom".to_string())) })
+                .collect()
+                .await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type(), "error");
+    }
+}
@@ -0,0 +1,316 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, PoisonError},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    language_models::{GenerateResult, TokenUsage},
+    prompt::PromptArgs,
+    schemas::Message,
+};
+
+use super::{CallbackHandler, RunInfo};
+
+/// Price of a model in currency units per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub input_per_million: f64,
+    pub output_per_million: f64,
+}
+
+impl ModelPrice {
+    pub fn new(input_per_million: f64, output_per_million: f64) -> Self {
+        Self {
+            input_per_million,
+            output_per_million,
+        }
+    }
+
+    pub fn cost(&self, tokens: &TokenUsage) -> f64 {
+        (tokens.prompt_tokens as f64 * self.input_per_million
+            + tokens.completion_tokens as f64 * self.output_per_million)
+            / 1_000_000.0
+    }
+}
+
+/// Token usage and cost aggregated over a set of LLM calls.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageSummary {
+    pub llm_calls: usize,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+    pub cost: f64,
+    /// Tokens of calls to models missing from the price table, not included in `cost`.
+    pub unpriced_tokens: u64,
+}
+
+impl UsageSummary {
+    fn add(&mut self, tokens: &TokenUsage, cost: Option<f64>) {
+        self.llm_calls += 1;
+        self.prompt_tokens += tokens.prompt_tokens as u64;
+        self.completion_tokens += tokens.completion_tokens as u64;
+        self.total_tokens += tokens.total_tokens as u64;
+        match cost {
+            Some(cost) => self.cost += cost,
+            None => self.unpriced_tokens += tokens.total_tokens as u64,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Usage {
+    total: UsageSummary,
+    runs: HashMap<Uuid, UsageSummary>,
+    groups: HashMap<String, HashMap<String, UsageSummary>>,
+    parents: HashMap<Uuid, Option<Uuid>>,
+}
+
+/// Aggregates the token usage of LLM calls and computes their cost from a per model price
+/// table.
+///
+/// Usage is available globally, per run, where a chain or agent run includes the LLM calls
+/// of all its children, and per value of the metadata keys given to `with_group_by`, e.g. a
+/// customer or feature set with `CallbackManager::with_metadata`. Models are priced by the
+/// longest entry of the price table their name starts with, so `gpt-4o` also prices
+/// `gpt-4o-2024-08-06`.
+///
+/// # Usage
+/// ```rust,ignore
+/// let costs = Arc::new(
+///     TokenCostHandler::new()
+///         .with_price("gpt-4o-mini", ModelPrice::new(0.15, 0.6))
+///         .with_group_by("customer"),
+/// );
+/// let manager = CallbackManager::new()
+///     .with_shared_handler(costs.clone())
+///     .with_metadata("customer", "acme");
+/// manager.scope(chain.invoke(input_variables)).await?;
+/// println!("{:?}", costs.usage_by("customer")["acme"]);
+/// ```
+#[derive(Default)]
+pub struct TokenCostHandler {
+    prices: HashMap<String, ModelPrice>,
+    group_by: Vec<String>,
+    usage: Mutex<Usage>,
+}
+
+impl TokenCostHandler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_price<S: Into<String>>(mut self, model: S, price: ModelPrice) -> Self {
+        self.prices.insert(model.into(), price);
+        self
+    }
+
+    pub fn with_prices(mut self, prices: HashMap<String, ModelPrice>) -> Self {
+        self.prices.extend(prices);
+        self
+    }
+
+    /// Aggregates usage per value of the metadata `key`. The `model` key is set on every LLM
+    /// run, so `with_group_by("model")` gives the usage per model.
+    pub fn with_group_by<S: Into<String>>(mut self, key: S) -> Self {
+        self.group_by.push(key.into());
+        self
+    }
+
+    /// Returns the price of `model`, matching the longest price table entry it starts with.
+    pub fn price(&self, model: &str) -> Option<ModelPrice> {
+        self.prices
+            .iter()
+            .filter(|(name, _)| model.starts_with(name.as_str()))
+            .max_by_key(|(name, _)| name.len())
+            .map(|(_, price)| *price)
+    }
+
+    /// Usage of every LLM call seen by the handler.
+    pub fn total_usage(&self) -> UsageSummary {
+        self.lock().total.clone()
+    }
+
+    /// Usage of a run and its children.
+    pub fn run_usage(&self, run_id: &Uuid) -> Option<UsageSummary> {
+        self.lock().runs.get(run_id).cloned()
+    }
+
+    /// Returns the usage of a run and its children and stops keeping it, for long running
+    /// services that read the usage of each request once.
+    pub fn take_run_usage(&self, run_id: &Uuid) -> Option<UsageSummary> {
+        self.lock().runs.remove(run_id)
+    }
+
+    /// Usage per value of the metadata `key`, which must have been given to `with_group_by`.
+    pub fn usage_by(&self, key: &str) -> HashMap<String, UsageSummary> {
+        self.lock().groups.get(key).cloned().unwrap_or_default()
+    }
+
+    /// Clears all the aggregated usage.
+    pub fn reset(&self) {
+        *self.lock() = Usage::default();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Usage> {
+        self.usage.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn start_run(&self, run: &RunInfo) {
+        self.lock().parents.insert(run.run_id, run.parent_run_id);
+    }
+
+    fn end_run(&self, run: &RunInfo) {
+        self.lock().parents.remove(&run.run_id);
+    }
+}
+
+impl CallbackHandler for TokenCostHandler {
+    fn on_llm_start(&self, run: &RunInfo, _messages: &[Message]) {
+        self.start_run(run);
+    }
+
+    fn on_llm_end(&self, run: &RunInfo, result: &GenerateResult) {
+        let Some(tokens) = &result.tokens else {
+            self.end_run(run);
+            return;
+        };
+        let model = run
+            .metadata
+            .get("model")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let cost = self.price(model).map(|price| price.cost(tokens));
+        if cost.is_none() {
+            tracing::debug!("No price for model `{}`, cost not counted", model);
+        }
+
+        let mut usage = self.lock();
+        usage.total.add(tokens, cost);
+
+        let mut run_id = Some(run.run_id);
+        while let Some(id) = run_id {
+            usage.runs.entry(id).or_default().add(tokens, cost);
+            run_id = usage.parents.get(&id).copied().flatten();
+        }
+
+        for key in &self.group_by {
+            let Some(value) = run.metadata.get(key) else {
+                continue;
+            };
+            let value = match value {
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+            usage
+                .groups
+                .entry(key.clone())
+                .or_default()
+                .entry(value)
+                .or_default()
+                .add(tokens, cost);
+        }
+        usage.parents.remove(&run.run_id);
+    }
+
+    fn on_chain_start(&self, run: &RunInfo, _inputs: &PromptArgs) {
+        self.start_run(run);
+    }
+
+    fn on_chain_end(&self, run: &RunInfo, _result: &GenerateResult) {
+        self.end_run(run);
+    }
+
+    fn on_tool_start(&self, run: &RunInfo, _input: &str) {
+        self.start_run(run);
+    }
+
+    fn on_tool_end(&self, run: &RunInfo, _output: &str) {
+        self.end_run(run);
+    }
+
+    fn on_error(&self, run: &RunInfo, _error: &str) {
+        self.end_run(run);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        callbacks::{current_run, trace_chain, trace_llm, CallbackManager},
+        language_models::LLMError,
+    };
+
+    use super::*;
+
+    async fn generate(
+        prompt_tokens: u32,
+        completion_tokens: u32,
+    ) -> Result<GenerateResult, LLMError> {
+        Ok(GenerateResult {
+            generation: String::new(),
+            tokens: Some(TokenUsage::new(prompt_tokens, completion_tokens)),
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_token_cost_handler() {
+        let handler = Arc::new(
+            TokenCostHandler::new()
+                .with_price("gpt-4o", ModelPrice::new(2.5, 10.0))
+                .with_price("gpt-4o-mini", ModelPrice::new(0.15, 0.6))
+                .with_group_by("customer")
+                .with_group_by("model"),
+        );
+        assert_eq!(
+            handler.price("gpt-4o-2024-08-06"),
+            Some(ModelPrice::new(2.5, 10.0))
+        );
+        assert_eq!(
+            handler.price("gpt-4o-mini-2024-07-18"),
+            Some(ModelPrice::new(0.15, 0.6))
+        );
+
+        let manager = CallbackManager::new()
+            .with_shared_handler(handler.clone())
+            .with_metadata("customer", "acme");
+        let chain_run_id = manager
+            .scope(trace_chain("Chain", PromptArgs::new(), |_| async {
+                trace_llm("OpenAI", "gpt-4o", &[], generate(1_000_000, 0)).await?;
+                trace_llm("OpenAI", "gpt-4o-mini", &[], generate(0, 1_000_000)).await?;
+                trace_llm("Ollama", "llama3.2", &[], generate(10, 5)).await?;
+                Ok(GenerateResult {
+                    generation: current_run().unwrap().run_id.to_string(),
+                    tokens: None,
+                    ..Default::default()
+                })
+            }))
+            .await
+            .unwrap()
+            .generation;
+
+        let total = handler.total_usage();
+        assert_eq!(total.llm_calls, 3);
+        assert_eq!(total.prompt_tokens, 1_000_010);
+        assert!((total.cost - 3.1).abs() < 1e-9);
+        assert_eq!(total.unpriced_tokens, 15);
+
+        let chain_run_id = Uuid::parse_str(&chain_run_id).unwrap();
+        assert_eq!(handler.run_usage(&chain_run_id), Some(total.clone()));
+        assert_eq!(handler.usage_by("customer")["acme"], total);
+        assert_eq!(
+            handler.usage_by("model")["gpt-4o-mini"].completion_tokens,
+            1_000_000
+        );
+
+        handler.reset();
+        assert_eq!(handler.total_usage(), UsageSummary::default());
+    }
+}
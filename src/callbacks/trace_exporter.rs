@@ -0,0 +1,540 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock, PoisonError},
+    time::{Duration, SystemTime},
+};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use reqwest::Client;
+use serde_json::{json, Value};
+use tokio::sync::{mpsc, oneshot};
+use uuid::Uuid;
+
+use crate::{
+    language_models::{GenerateResult, TokenUsage},
+    platform::{send, spawn},
+    prompt::PromptArgs,
+    schemas::{Document, Message},
+};
+
+use super::{CallbackHandler, RunInfo, RunType};
+
+/// Connection settings of a Langfuse project. Defaults are read from the `LANGFUSE_HOST`,
+/// `LANGFUSE_PUBLIC_KEY` and `LANGFUSE_SECRET_KEY` environment variables.
+#[derive(Debug, Clone)]
+pub struct LangfuseConfig {
+    pub host: String,
+    pub public_key: String,
+    pub secret_key: String,
+}
+
+impl LangfuseConfig {
+    pub fn with_host<S: Into<String>>(mut self, host: S) -> Self {
+        self.host = host.into();
+        self
+    }
+
+    pub fn with_public_key<S: Into<String>>(mut self, public_key: S) -> Self {
+        self.public_key = public_key.into();
+        self
+    }
+
+    pub fn with_secret_key<S: Into<String>>(mut self, secret_key: S) -> Self {
+        self.secret_key = secret_key.into();
+        self
+    }
+}
+
+impl Default for LangfuseConfig {
+    fn default() -> Self {
+        Self {
+            host: std::env::var("LANGFUSE_HOST")
+                .unwrap_or_else(|_| "https://cloud.langfuse.com".to_string()),
+            public_key: std::env::var("LANGFUSE_PUBLIC_KEY").unwrap_or_default(),
+            secret_key: std::env::var("LANGFUSE_SECRET_KEY").unwrap_or_default(),
+        }
+    }
+}
+
+/// Connection settings of a LangSmith project. Defaults are read from the
+/// `LANGSMITH_ENDPOINT`, `LANGSMITH_API_KEY` and `LANGSMITH_PROJECT` environment variables.
+#[derive(Debug, Clone)]
+pub struct LangSmithConfig {
+    pub endpoint: String,
+    pub api_key: String,
+    pub project: String,
+}
+
+impl LangSmithConfig {
+    pub fn with_endpoint<S: Into<String>>(mut self, endpoint: S) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+
+    pub fn with_api_key<S: Into<String>>(mut self, api_key: S) -> Self {
+        self.api_key = api_key.into();
+        self
+    }
+
+    pub fn with_project<S: Into<String>>(mut self, project: S) -> Self {
+        self.project = project.into();
+        self
+    }
+}
+
+impl Default for LangSmithConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: std::env::var("LANGSMITH_ENDPOINT")
+                .unwrap_or_else(|_| "https://api.smith.langchain.com".to_string()),
+            api_key: std::env::var("LANGSMITH_API_KEY").unwrap_or_default(),
+            project: std::env::var("LANGSMITH_PROJECT").unwrap_or_else(|_| "default".to_string()),
+        }
+    }
+}
+
+/// The tracing platform runs are exported to.
+#[derive(Debug, Clone)]
+pub enum TraceBackend {
+    Langfuse(LangfuseConfig),
+    LangSmith(LangSmithConfig),
+}
+
+impl From<LangfuseConfig> for TraceBackend {
+    fn from(config: LangfuseConfig) -> Self {
+        TraceBackend::Langfuse(config)
+    }
+}
+
+impl From<LangSmithConfig> for TraceBackend {
+    fn from(config: LangSmithConfig) -> Self {
+        TraceBackend::LangSmith(config)
+    }
+}
+
+/// A finished run, as sent to the tracing platform.
+#[derive(Debug, Clone)]
+struct RunRecord {
+    run_id: Uuid,
+    parent_run_id: Option<Uuid>,
+    trace_id: Uuid,
+    dotted_order: String,
+    run_type: RunType,
+    name: String,
+    start_time: DateTime<Utc>,
+    end_time: DateTime<Utc>,
+    inputs: Value,
+    outputs: Option<Value>,
+    error: Option<String>,
+    tokens: Option<TokenUsage>,
+    metadata: HashMap<String, Value>,
+}
+
+enum ExportCommand {
+    Run(Box<RunRecord>),
+    Flush(oneshot::Sender<()>),
+}
+
+/// Exports runs with their inputs, outputs, token usage and nesting to Langfuse or
+/// LangSmith over their HTTP ingestion APIs.
+///
+/// Finished runs are queued and sent in batches from a background task, started on the
+/// first event, so the exporter must be used inside a tokio runtime. A batch is sent when
+/// it reaches `batch_size` runs or every `flush_interval`; call `flush` before shutting
+/// down to send the runs still queued. Export failures are logged and the runs dropped.
+///
+/// # Usage
+/// ```rust,ignore
+/// let exporter = Arc::new(TraceExporter::new(LangfuseConfig::default()));
+/// let manager = CallbackManager::new().with_shared_handler(exporter.clone());
+/// let answer = manager.scope(chain.invoke(input_variables)).await?;
+/// exporter.flush().await;
+/// ```
+pub struct TraceExporter {
+    backend: TraceBackend,
+    client: Client,
+    batch_size: usize,
+    flush_interval: Duration,
+    in_progress: Mutex<HashMap<Uuid, (String, Value)>>,
+    sender: OnceLock<mpsc::UnboundedSender<ExportCommand>>,
+}
+
+impl TraceExporter {
+    pub fn new<B: Into<TraceBackend>>(backend: B) -> Self {
+        Self {
+            backend: backend.into(),
+            client: Client::new(),
+            batch_size: 50,
+            flush_interval: Duration::from_secs(5),
+            in_progress: Mutex::new(HashMap::new()),
+            sender: OnceLock::new(),
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn with_flush_interval(mut self, flush_interval: Duration) -> Self {
+        self.flush_interval = flush_interval;
+        self
+    }
+
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Sends the queued runs and waits until they are exported.
+    pub async fn flush(&self) {
+        let Some(sender) = self.sender.get() else {
+            return;
+        };
+        let (done, wait) = oneshot::channel();
+        if sender.send(ExportCommand::Flush(done)).is_ok() {
+            let _ = wait.await;
+        }
+    }
+
+    fn start_run(&self, run: &RunInfo, inputs: Value) {
+        let mut in_progress = self
+            .in_progress
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let order = format!(
+            "{}{}",
+            DateTime::<Utc>::from(run.start_time).format("%Y%m%dT%H%M%S%6fZ"),
+            run.run_id
+        );
+        let dotted_order = match run
+            .parent_run_id
+            .and_then(|parent_run_id| in_progress.get(&parent_run_id))
+        {
+            Some((parent_order, _)) => format!("{parent_order}.{order}"),
+            None => order,
+        };
+        in_progress.insert(run.run_id, (dotted_order, inputs));
+    }
+
+    fn end_run(
+        &self,
+        run: &RunInfo,
+        outputs: Option<Value>,
+        tokens: Option<TokenUsage>,
+        error: Option<String>,
+    ) {
+        let started = self
+            .in_progress
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&run.run_id);
+        let Some((dotted_order, inputs)) = started else {
+            return;
+        };
+
+        let record = RunRecord {
+            run_id: run.run_id,
+            parent_run_id: run.parent_run_id,
+            trace_id: run.root_run_id,
+            dotted_order,
+            run_type: run.run_type,
+            name: run.name.clone(),
+            start_time: run.start_time.into(),
+            end_time: SystemTime::now().into(),
+            inputs,
+            outputs,
+            error,
+            tokens,
+            metadata: run.metadata.clone(),
+        };
+        let sender = self.sender.get_or_init(|| {
+            let (sender, receiver) = mpsc::unbounded_channel();
+            spawn(export_runs(
+                self.backend.clone(),
+                self.client.clone(),
+                receiver,
+                self.batch_size,
+                self.flush_interval,
+            ));
+            sender
+        });
+        let _ = sender.send(ExportCommand::Run(Box::new(record)));
+    }
+}
+
+impl CallbackHandler for TraceExporter {
+    fn on_llm_start(&self, run: &RunInfo, messages: &[Message]) {
+        self.start_run(run, json!({ "messages": messages }));
+    }
+
+    fn on_llm_end(&self, run: &RunInfo, result: &GenerateResult) {
+        let outputs = json!({ "generation": result.generation });
+        self.end_run(run, Some(outputs), result.tokens.clone(), None);
+    }
+
+    fn on_chain_start(&self, run: &RunInfo, inputs: &PromptArgs) {
+        self.start_run(run, json!(inputs));
+    }
+
+    fn on_chain_end(&self, run: &RunInfo, result: &GenerateResult) {
+        let outputs = json!({ "output": result.generation });
+        self.end_run(run, Some(outputs), result.tokens.clone(), None);
+    }
+
+    fn on_tool_start(&self, run: &RunInfo, input: &str) {
+        self.start_run(run, json!({ "input": input }));
+    }
+
+    fn on_tool_end(&self, run: &RunInfo, output: &str) {
+        self.end_run(run, Some(json!({ "output": output })), None, None);
+    }
+
+    fn on_retriever_start(&self, run: &RunInfo, query: &str) {
+        self.start_run(run, json!({ "query": query }));
+    }
+
+    fn on_retriever_end(&self, run: &RunInfo, documents: &[Document]) {
+        self.end_run(run, Some(json!({ "documents": documents })), None, None);
+    }
+
+    fn on_error(&self, run: &RunInfo, error: &str) {
+        self.end_run(run, None, None, Some(error.to_string()));
+    }
+}
+
+async fn export_runs(
+    backend: TraceBackend,
+    client: Client,
+    mut receiver: mpsc::UnboundedReceiver<ExportCommand>,
+    batch_size: usize,
+    flush_interval: Duration,
+) {
+    let mut batch: Vec<RunRecord> = Vec::new();
+    let mut ticker = tokio::time::interval(flush_interval);
+    loop {
+        tokio::select! {
+            command = receiver.recv() => match command {
+                Some(ExportCommand::Run(record)) => {
+                    batch.push(*record);
+                    if batch.len() >= batch_size {
+                        send_batch(&backend, &client, std::mem::take(&mut batch)).await;
+                    }
+                }
+                Some(ExportCommand::Flush(done)) => {
+                    send_batch(&backend, &client, std::mem::take(&mut batch)).await;
+                    let _ = done.send(());
+                }
+                None => {
+                    send_batch(&backend, &client, std::mem::take(&mut batch)).await;
+                    return;
+                }
+            },
+            _ = ticker.tick() => {
+                send_batch(&backend, &client, std::mem::take(&mut batch)).await;
+            }
+        }
+    }
+}
+
+async fn send_batch(backend: &TraceBackend, client: &Client, batch: Vec<RunRecord>) {
+    if batch.is_empty() {
+        return;
+    }
+
+    let request = match backend {
+        TraceBackend::Langfuse(config) => client
+            .post(format!(
+                "{}/api/public/ingestion",
+                config.host.trim_end_matches('/')
+            ))
+            .basic_auth(&config.public_key, Some(&config.secret_key))
+            .json(&json!({ "batch": batch.iter().flat_map(langfuse_events).collect::<Vec<_>>() })),
+        TraceBackend::LangSmith(config) => client
+            .post(format!(
+                "{}/runs/batch",
+                config.endpoint.trim_end_matches('/')
+            ))
+            .header("x-api-key", &config.api_key)
+            .json(&json!({
+                "post": batch
+                    .iter()
+                    .map(|record| langsmith_run(record, &config.project))
+                    .collect::<Vec<_>>()
+            })),
+    };
+
+    let result = send(async {
+        let response = request.send().await?;
+        if response.status().is_success() {
+            return Ok(None);
+        }
+        Ok::<_, reqwest::Error>(Some(response.text().await.unwrap_or_default()))
+    })
+    .await;
+    match result {
+        Ok(Some(error)) => tracing::warn!("Failed to export {} runs: {}", batch.len(), error),
+        Err(e) => tracing::warn!("Failed to export {} runs: {}", batch.len(), e),
+        Ok(None) => {}
+    }
+}
+
+fn timestamp(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+fn langfuse_events(record: &RunRecord) -> Vec<Value> {
+    let mut events = Vec::new();
+    if record.parent_run_id.is_none() {
+        events.push(json!({
+            "id": Uuid::new_v4(),
+            "type": "trace-create",
+            "timestamp": timestamp(&record.end_time),
+            "body": {
+                "id": record.trace_id,
+                "name": record.name,
+                "timestamp": timestamp(&record.start_time),
+                "input": record.inputs,
+                "output": record.outputs,
+                "metadata": record.metadata,
+            },
+        }));
+    }
+
+    let mut body = json!({
+        "id": record.run_id,
+        "traceId": record.trace_id,
+        "parentObservationId": record.parent_run_id,
+        "name": record.name,
+        "startTime": timestamp(&record.start_time),
+        "endTime": timestamp(&record.end_time),
+        "input": record.inputs,
+        "output": record.outputs,
+        "metadata": record.metadata,
+        "level": if record.error.is_some() { "ERROR" } else { "DEFAULT" },
+        "statusMessage": record.error,
+    });
+    let event_type = match record.run_type {
+        RunType::Llm => {
+            body["model"] = record.metadata.get("model").cloned().unwrap_or_default();
+            if let Some(tokens) = &record.tokens {
+                body["usage"] = json!({
+                    "input": tokens.prompt_tokens,
+                    "output": tokens.completion_tokens,
+                    "total": tokens.total_tokens,
+                });
+            }
+            "generation-create"
+        }
+        _ => "span-create",
+    };
+    events.push(json!({
+        "id": Uuid::new_v4(),
+        "type": event_type,
+        "timestamp": timestamp(&record.end_time),
+        "body": body,
+    }));
+    events
+}
+
+fn langsmith_run(record: &RunRecord, project: &str) -> Value {
+    let mut outputs = record.outputs.clone();
+    if let (Some(Value::Object(outputs)), Some(tokens)) = (outputs.as_mut(), &record.tokens) {
+        outputs.insert("llm_output".to_string(), json!({ "token_usage": tokens }));
+    }
+    json!({
+        "id": record.run_id,
+        "trace_id": record.trace_id,
+        "parent_run_id": record.parent_run_id,
+        "dotted_order": record.dotted_order,
+        "name": record.name,
+        "run_type": record.run_type.as_str(),
+        "start_time": timestamp(&record.start_time),
+        "end_time": timestamp(&record.end_time),
+        "inputs": record.inputs,
+        "outputs": outputs,
+        "error": record.error,
+        "extra": { "metadata": record.metadata },
+        "session_name": project,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use mockito::Matcher;
+
+    use crate::callbacks::{trace_chain, trace_llm, CallbackManager};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_langfuse_export() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/public/ingestion")
+            .match_header("authorization", Matcher::Regex("^Basic ".to_string()))
+            .match_body(Matcher::AllOf(vec![
+                Matcher::Regex(r#""type":"generation-create""#.to_string()),
+                Matcher::Regex(r#""usage":\{"input":3,"output":1,"total":4\}"#.to_string()),
+            ]))
+            .with_status(207)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let config = LangfuseConfig::default()
+            .with_host(server.url())
+            .with_public_key("pk")
+            .with_secret_key("sk");
+        let exporter = Arc::new(TraceExporter::new(config));
+        let manager = CallbackManager::new().with_shared_handler(exporter.clone());
+
+        manager
+            .scope(trace_chain("LLMChain", PromptArgs::new(), |_| async {
+                let result = trace_llm("OpenAI", "gpt-4o-mini", &[], async {
+                    Ok(GenerateResult {
+                        generation: "Hello".to_string(),
+                        tokens: Some(TokenUsage::new(3, 1)),
+                        ..Default::default()
+                    })
+                })
+                .await?;
+                Ok(result)
+            }))
+            .await
+            .unwrap();
+        exporter.flush().await;
+
+        mock.assert_async().await;
+    }
+
+    #[test]
+    fn test_langsmith_run() {
+        let trace_id = Uuid::new_v4();
+        let start_time = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+        let record = RunRecord {
+            run_id: Uuid::new_v4(),
+            parent_run_id: Some(trace_id),
+            trace_id,
+            dotted_order: "parent.child".to_string(),
+            run_type: RunType::Tool,
+            name: "calculator".to_string(),
+            start_time,
+            end_time: start_time,
+            inputs: json!({ "input": "1 + 1" }),
+            outputs: None,
+            error: Some("boom".to_string()),
+            tokens: None,
+            metadata: HashMap::new(),
+        };
+
+        let run = langsmith_run(&record, "my-project");
+        assert_eq!(run["run_type"], "tool");
+        assert_eq!(run["parent_run_id"], json!(trace_id));
+        assert_eq!(run["start_time"], "2023-11-14T22:13:20.000000Z");
+        assert_eq!(run["error"], "boom");
+        assert_eq!(run["session_name"], "my-project");
+    }
+}
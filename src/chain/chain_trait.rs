@@ -1,10 +1,19 @@
-use std::{collections::HashMap, pin::Pin};
+use std::{collections::HashMap, pin::Pin, time::Duration};
 
 use async_trait::async_trait;
 use futures::Stream;
 use serde_json::{json, Value};
 
-use crate::{language_models::GenerateResult, prompt::PromptArgs, schemas::StreamData};
+use crate::{
+    language_models::{CancellationToken, GenerateResult},
+    moderation::{ModerationChain, Moderator},
+    prompt::PromptArgs,
+    resilience::{RetryPolicy, WithFallbacks, WithRetry, WithTimeout},
+    schemas::StreamData,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::platform::block_on;
 
 use super::ChainError;
 
@@ -78,6 +87,61 @@ pub trait Chain: Sync + Send {
             .map(|result| result.generation)
     }
 
+    /// Calls the `Chain` from synchronous code, blocking the thread until it completes. Like
+    /// the other blocking methods, this runs the call on a runtime shared across the crate, so
+    /// no async runtime is needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics when called from within an async runtime; await `call` there.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn call_blocking(&self, input_variables: PromptArgs) -> Result<GenerateResult, ChainError> {
+        block_on(self.call(input_variables))
+    }
+
+    /// Invokes the `Chain` from synchronous code, blocking the thread until it completes.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// fn main() {
+    ///     let chain = LLMChainBuilder::new().prompt(prompt).llm(OpenAI::default()).build()?;
+    ///     let answer = chain.invoke_blocking(prompt_args! { "input" => "Hi" })?;
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when called from within an async runtime; await `invoke` there.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn invoke_blocking(&self, input_variables: PromptArgs) -> Result<String, ChainError> {
+        block_on(self.invoke(input_variables))
+    }
+
+    /// Call the `Chain` until it completes or `token` is cancelled, in which case it fails
+    /// with `ChainError::Cancelled`. The call is dropped on cancellation, which aborts the
+    /// LLM requests and tool runs it has in flight; dropping the future of any other method
+    /// does the same.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let token = CancellationToken::new();
+    /// // Cancel from elsewhere, e.g. when the client of an HTTP handler disconnects.
+    /// let cancel = token.clone();
+    /// let result = chain.call_with_cancellation(input_variables, &token).await;
+    /// ```
+    async fn call_with_cancellation(
+        &self,
+        input_variables: PromptArgs,
+        token: &CancellationToken,
+    ) -> Result<GenerateResult, ChainError> {
+        token
+            .run_until_cancelled(self.call(input_variables))
+            .await
+            .unwrap_or(Err(ChainError::Cancelled))
+    }
+
     /// Execute the `Chain` and return the result of the generation process
     /// along with additional information like token consumption formatted as a `HashMap`.
     /// The input is a set of variables passed as a `PromptArgs` hashmap.
@@ -114,7 +178,7 @@ pub trait Chain: Sync + Send {
         &self,
         input_variables: PromptArgs,
     ) -> Result<HashMap<String, Value>, ChainError> {
-        log::info!("Using default implementation");
+        tracing::info!("Using default implementation");
         let result = self.call(input_variables.clone()).await?;
         let mut output = HashMap::new();
         let output_key = self
@@ -176,23 +240,56 @@ pub trait Chain: Sync + Send {
         _input_variables: PromptArgs,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamData, ChainError>> + Send>>, ChainError>
     {
-        log::warn!("stream not implemented for this chain");
+        tracing::warn!("stream not implemented for this chain");
         unimplemented!()
     }
 
     // Get the input keys of the prompt
     fn get_input_keys(&self) -> Vec<String> {
-        log::info!("Using default implementation");
+        tracing::info!("Using default implementation");
         vec![]
     }
 
     fn get_output_keys(&self) -> Vec<String> {
-        log::info!("Using default implementation");
+        tracing::info!("Using default implementation");
         vec![
             String::from(DEFAULT_OUTPUT_KEY),
             String::from(DEFAULT_RESULT_KEY),
         ]
     }
+
+    /// Retries the calls that fail with a transient error, following `policy`.
+    fn with_retry(self, policy: RetryPolicy) -> WithRetry<Self>
+    where
+        Self: Sized,
+    {
+        WithRetry::new(self, policy)
+    }
+
+    /// Calls the `fallbacks` in order when a call fails.
+    fn with_fallbacks(self, fallbacks: Vec<Box<dyn Chain>>) -> WithFallbacks<Self, Box<dyn Chain>>
+    where
+        Self: Sized,
+    {
+        WithFallbacks::new(self, fallbacks)
+    }
+
+    /// Fails the calls that take longer than `timeout` with `ChainError::Timeout`.
+    fn with_timeout(self, timeout: Duration) -> WithTimeout<Self>
+    where
+        Self: Sized,
+    {
+        WithTimeout::new(self, timeout)
+    }
+
+    /// Classifies the inputs and outputs with `moderator`, blocking the ones that violate the
+    /// default policy. See `ModerationChain` to change the policy.
+    fn with_moderation<M: Moderator + 'static>(self, moderator: M) -> ModerationChain<Self>
+    where
+        Self: Sized,
+    {
+        ModerationChain::new(self, moderator)
+    }
 }
 
 impl<C> From<C> for Box<dyn Chain>
@@ -203,3 +300,94 @@ where
         Box::new(chain)
     }
 }
+
+/// The generation in the outputs of `Chain::execute`: the generation of the result, or the
+/// default output.
+pub(crate) fn output_generation(output: &HashMap<String, Value>) -> Option<String> {
+    output
+        .get(DEFAULT_RESULT_KEY)
+        .and_then(|result| serde_json::from_value::<GenerateResult>(result.clone()).ok())
+        .map(|result| result.generation)
+        .or_else(|| {
+            output
+                .get(DEFAULT_OUTPUT_KEY)
+                .and_then(|value| value.as_str().map(String::from))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        chain::LLMChainBuilder,
+        language_models::{llm::LLM, LLMError},
+        prompt::HumanMessagePromptTemplate,
+        prompt_args,
+        schemas::Message,
+        template_fstring,
+    };
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct EchoLLM;
+
+    #[async_trait]
+    impl LLM for EchoLLM {
+        async fn generate(&self, messages: &[Message]) -> Result<GenerateResult, LLMError> {
+            // Yields to the runtime, as a real request would.
+            tokio::task::yield_now().await;
+            Ok(GenerateResult {
+                tokens: None,
+                generation: format!("echo: {}", messages[0].content),
+                ..Default::default()
+            })
+        }
+
+        async fn stream(
+            &self,
+            _messages: &[Message],
+        ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamData, LLMError>> + Send>>, LLMError>
+        {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_blocking() {
+        assert_eq!(EchoLLM.invoke_blocking("Hi").unwrap(), "echo: Hi");
+
+        let chain = LLMChainBuilder::new()
+            .prompt(HumanMessagePromptTemplate::new(template_fstring!(
+                "Say {word}",
+                "word"
+            )))
+            .llm(EchoLLM)
+            .build()
+            .unwrap();
+        assert_eq!(
+            chain
+                .invoke_blocking(prompt_args! { "word" => "hello" })
+                .unwrap(),
+            "echo: Say hello"
+        );
+
+        // The runtime is shared, so calls from several threads do not conflict.
+        let chain: Arc<dyn Chain> = Arc::new(chain);
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let chain = chain.clone();
+                std::thread::spawn(move || {
+                    chain
+                        .call_blocking(prompt_args! { "word" => i })
+                        .unwrap()
+                        .generation
+                })
+            })
+            .collect();
+        for (i, handle) in handles.into_iter().enumerate() {
+            assert_eq!(handle.join().unwrap(), format!("echo: Say {}", i));
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::{HashMap, HashSet};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::schemas::Document;
+
+/// A span of text, by byte offsets.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextSpan {
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+}
+
+/// A retrieved document an answer relies on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Citation {
+    /// The number of the document in the context, from 1, which citation markers refer to.
+    pub marker: usize,
+    pub document_id: Option<String>,
+    /// The metadata of the document, such as its source.
+    pub metadata: HashMap<String, Value>,
+    /// The sentence of the document that best matches the cited claim, if any word matches.
+    pub span: Option<TextSpan>,
+}
+
+/// Numbers the documents as `[1] ...`, `[2] ...`, so the model can cite them with markers.
+pub(crate) fn number_documents(documents: &[Document]) -> Vec<Document> {
+    documents
+        .iter()
+        .enumerate()
+        .map(|(i, document)| Document {
+            page_content: format!("[{}] {}", i + 1, document.page_content),
+            ..document.clone()
+        })
+        .collect()
+}
+
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Splits `text` into trimmed sentences, ending at `.`, `!`, `?` or a newline.
+fn sentences(text: &str) -> Vec<TextSpan> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    let mut push = |start: usize, end: usize| {
+        let sentence = &text[start..end];
+        let trimmed = sentence.trim();
+        if !trimmed.is_empty() {
+            let start = start + (sentence.len() - sentence.trim_start().len());
+            spans.push(TextSpan {
+                start,
+                end: start + trimmed.len(),
+                text: trimmed.to_string(),
+            });
+        }
+    };
+    for (i, c) in text.char_indices() {
+        if matches!(c, '.' | '!' | '?' | '\n') {
+            push(start, i + c.len_utf8());
+            start = i + c.len_utf8();
+        }
+    }
+    push(start, text.len());
+    spans
+}
+
+/// The sentence of `document` sharing the most words with `claim`.
+fn match_span(document: &Document, claim: &str) -> Option<TextSpan> {
+    let claim = words(claim);
+    sentences(&document.page_content)
+        .into_iter()
+        .map(|sentence| {
+            let overlap = words(&sentence.text).intersection(&claim).count();
+            (overlap, sentence)
+        })
+        .filter(|(overlap, _)| *overlap > 0)
+        .fold(
+            None,
+            |best: Option<(usize, TextSpan)>, (overlap, sentence)| match best {
+                Some((best_overlap, _)) if best_overlap >= overlap => best,
+                _ => Some((overlap, sentence)),
+            },
+        )
+        .map(|(_, sentence)| sentence)
+}
+
+fn citation(marker: usize, document: &Document, claim: &str) -> Citation {
+    Citation {
+        marker,
+        document_id: document.id.clone(),
+        metadata: document.metadata.clone(),
+        span: match_span(document, claim),
+    }
+}
+
+/// Resolves the citation markers of `answer`, such as `[1]` or `[1, 3]`, to the documents
+/// numbered from 1 in the context, one citation per cited document in order of first citation.
+/// The span of each citation matches the claim the marker ends.
+///
+/// When the answer has no markers, every document sharing words with the answer is cited.
+pub fn resolve_citations(answer: &str, documents: &[Document]) -> Vec<Citation> {
+    let re = Regex::new(r"\[(\d+(?:\s*,\s*\d+)*)\]").unwrap();
+    let mut citations: Vec<Citation> = Vec::new();
+    for captures in re.captures_iter(answer) {
+        let position = captures.get(0).map_or(0, |m| m.start());
+        let claim = claim_before(answer, position);
+        let claim = re.replace_all(claim, "");
+        for marker in captures[1].split(',').filter_map(|n| n.trim().parse().ok()) {
+            if marker == 0
+                || marker > documents.len()
+                || citations.iter().any(|citation| citation.marker == marker)
+            {
+                continue;
+            }
+            citations.push(citation(marker, &documents[marker - 1], &claim));
+        }
+    }
+    if citations.is_empty() {
+        citations = documents
+            .iter()
+            .enumerate()
+            .map(|(i, document)| citation(i + 1, document, answer))
+            .filter(|citation| citation.span.is_some())
+            .collect();
+    }
+    citations
+}
+
+/// The sentence ending at `position`, where a marker cites it. Markers are written before or
+/// after the final period, so a trailing sentence end is part of the claim.
+fn claim_before(answer: &str, position: usize) -> &str {
+    let prefix = answer[..position].trim_end();
+    let body = prefix.trim_end_matches(['.', '!', '?']);
+    let start = body.rfind(['.', '!', '?', '\n']).map_or(0, |i| i + 1);
+    &prefix[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn documents() -> Vec<Document> {
+        let mut editor = Document::new("Luis codes every day. His favorite text editor is Nvim.");
+        editor.id = Some("editor".to_string());
+        editor
+            .metadata
+            .insert("source".to_string(), Value::from("editors.md"));
+        vec![
+            editor,
+            Document::new("Luis lives in Peru. He is 24 years old."),
+        ]
+    }
+
+    #[test]
+    fn test_resolve_citations() {
+        let answer = "Luis is 24 years old [2]. His favorite editor is Nvim. [1] [7]";
+        let citations = resolve_citations(answer, &documents());
+
+        assert_eq!(citations.len(), 2);
+        assert_eq!(citations[0].marker, 2);
+        assert_eq!(
+            citations[0].span.as_ref().unwrap().text,
+            "He is 24 years old."
+        );
+        assert_eq!(citations[1].document_id.as_deref(), Some("editor"));
+        assert_eq!(citations[1].metadata["source"], "editors.md");
+        let span = citations[1].span.clone().unwrap();
+        assert_eq!(span.text, "His favorite text editor is Nvim.");
+        assert_eq!(
+            &documents()[0].page_content[span.start..span.end],
+            span.text
+        );
+    }
+
+    #[test]
+    fn test_resolve_citations_without_markers() {
+        let citations = resolve_citations("Peru.", &documents());
+        assert_eq!(citations.len(), 1);
+        assert_eq!(citations[0].marker, 2);
+        assert_eq!(
+            citations[0].span.as_ref().unwrap().text,
+            "Luis lives in Peru."
+        );
+    }
+}
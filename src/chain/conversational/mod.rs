@@ -7,6 +7,7 @@ use futures_util::{pin_mut, StreamExt};
 use tokio::sync::Mutex;
 
 use crate::{
+    callbacks::trace_chain,
     language_models::GenerateResult,
     prompt::PromptArgs,
     prompt_args,
@@ -60,23 +61,30 @@ impl ConversationalChain {
 #[async_trait]
 impl Chain for ConversationalChain {
     async fn call(&self, input_variables: PromptArgs) -> Result<GenerateResult, ChainError> {
-        let input_variable = &input_variables
-            .get(&self.input_key)
-            .ok_or(ChainError::MissingInputVariable(self.input_key.clone()))?;
-        let human_message = Message::new_human_message(input_variable);
-
-        let history = {
-            let memory = self.memory.lock().await;
-            memory.to_string()
-        };
-        let mut input_variables = input_variables;
-        input_variables.insert("history".to_string(), history.into());
-        let result = self.llm.call(input_variables.clone()).await?;
-
-        let mut memory = self.memory.lock().await;
-        memory.add_message(human_message);
-        memory.add_message(Message::new_ai_message(&result.generation));
-        Ok(result)
+        trace_chain(
+            "ConversationalChain",
+            input_variables,
+            |input_variables| async move {
+                let input_variable = &input_variables
+                    .get(&self.input_key)
+                    .ok_or(ChainError::MissingInputVariable(self.input_key.clone()))?;
+                let human_message = Message::new_human_message(input_variable);
+
+                let history = {
+                    let memory = self.memory.lock().await;
+                    memory.to_string()
+                };
+                let mut input_variables = input_variables;
+                input_variables.insert("history".to_string(), history.into());
+                let result = self.llm.call(input_variables.clone()).await?;
+
+                let mut memory = self.memory.lock().await;
+                memory.add_message(human_message);
+                memory.add_message(Message::new_ai_message(&result.generation));
+                Ok(result)
+            },
+        )
+        .await
     }
 
     async fn stream(
@@ -133,7 +141,7 @@ impl Chain for ConversationalChain {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "openai"))]
 mod tests {
     use crate::{
         chain::conversational::builder::ConversationalChainBuilder,
@@ -3,12 +3,14 @@ use tokio::sync::Mutex;
 
 use crate::{
     chain::{
-        Chain, ChainError, CondenseQuestionGeneratorChain, StuffDocumentBuilder, DEFAULT_OUTPUT_KEY,
+        Chain, ChainError, CondenseQuestionGeneratorChain, StuffDocumentBuilder,
+        CITATION_QA_TEMPLATE, DEFAULT_OUTPUT_KEY,
     },
     language_models::llm::LLM,
     memory::SimpleMemory,
     prompt::FormatPrompter,
     schemas::{BaseMemory, Retriever},
+    template_jinja2,
 };
 
 use super::ConversationalRetrieverChain;
@@ -53,6 +55,8 @@ pub struct ConversationalRetrieverChainBuilder {
     prompt: Option<Box<dyn FormatPrompter>>,
     rephrase_question: bool,
     return_source_documents: bool,
+    return_citations: bool,
+    citation_markers: bool,
     input_key: String,
     output_key: String,
 }
@@ -67,6 +71,8 @@ impl ConversationalRetrieverChainBuilder {
             prompt: None,
             rephrase_question: true,
             return_source_documents: true,
+            return_citations: false,
+            citation_markers: false,
             input_key: CONVERSATIONAL_RETRIEVAL_QA_DEFAULT_INPUT_KEY.to_string(),
             output_key: DEFAULT_OUTPUT_KEY.to_string(),
         }
@@ -126,12 +132,31 @@ impl ConversationalRetrieverChainBuilder {
         self
     }
 
+    ///Returns the documents the answer cites under the `citations` key, see `resolve_citations`
+    pub fn return_citations(mut self, return_citations: bool) -> Self {
+        self.return_citations = return_citations;
+        self
+    }
+
+    ///Numbers the documents in the context and, unless a custom prompt is set, asks the model
+    ///to cite them with markers such as `[1]`
+    pub fn citation_markers(mut self, citation_markers: bool) -> Self {
+        self.citation_markers = citation_markers;
+        self
+    }
+
     pub fn build(mut self) -> Result<ConversationalRetrieverChain, ChainError> {
         if let Some(llm) = self.llm {
             let combine_documents_chain = {
                 let mut builder = StuffDocumentBuilder::new().llm(llm.clone_box());
                 if let Some(prompt) = self.prompt {
                     builder = builder.prompt(prompt);
+                } else if self.citation_markers {
+                    builder = builder.prompt(template_jinja2!(
+                        CITATION_QA_TEMPLATE,
+                        "context",
+                        "question"
+                    ));
                 }
                 builder.build()?
             };
@@ -165,6 +190,8 @@ impl ConversationalRetrieverChainBuilder {
             condense_question_chain,
             rephrase_question: self.rephrase_question,
             return_source_documents: self.return_source_documents,
+            return_citations: self.return_citations,
+            citation_markers: self.citation_markers,
             input_key: self.input_key,
             output_key: self.output_key,
         })
@@ -8,12 +8,14 @@ use serde_json::{json, Value};
 use tokio::sync::Mutex;
 
 use crate::{
+    callbacks::{trace_chain, trace_retriever},
     chain::{
-        Chain, ChainError, CondenseQuestionPromptBuilder, StuffQAPromptBuilder, DEFAULT_RESULT_KEY,
+        number_documents, resolve_citations, Chain, ChainError, CondenseQuestionPromptBuilder,
+        StuffQAPromptBuilder, DEFAULT_RESULT_KEY,
     },
     language_models::{GenerateResult, TokenUsage},
     prompt::PromptArgs,
-    schemas::{BaseMemory, Message, Retriever, StreamData},
+    schemas::{BaseMemory, Document, Message, Retriever, StreamData},
 };
 // _conversationalRetrievalQADefaultInputKey             = "question"
 // _conversationalRetrievalQADefaultSourceDocumentKey    = "source_documents"
@@ -22,6 +24,7 @@ use crate::{
 
 const CONVERSATIONAL_RETRIEVAL_QA_DEFAULT_SOURCE_DOCUMENT_KEY: &str = "source_documents";
 const CONVERSATIONAL_RETRIEVAL_QA_DEFAULT_GENERATED_QUESTION_KEY: &str = "generated_question";
+const CONVERSATIONAL_RETRIEVAL_QA_DEFAULT_CITATIONS_KEY: &str = "citations";
 
 pub struct ConversationalRetrieverChain {
     pub(crate) retriever: Box<dyn Retriever>,
@@ -30,6 +33,8 @@ pub struct ConversationalRetrieverChain {
     pub(crate) condense_question_chain: Box<dyn Chain>,
     pub(crate) rephrase_question: bool,
     pub(crate) return_source_documents: bool,
+    pub(crate) return_citations: bool,
+    pub(crate) citation_markers: bool,
     pub(crate) input_key: String,  //Default is `question`
     pub(crate) output_key: String, //default is output
 }
@@ -64,14 +69,31 @@ impl ConversationalRetrieverChain {
 
         Ok((question, token_usage))
     }
+
+    /// The documents as given to the combine documents chain.
+    fn context_documents(&self, documents: &[Document]) -> Vec<Document> {
+        if self.citation_markers {
+            number_documents(documents)
+        } else {
+            documents.to_vec()
+        }
+    }
 }
 
 #[async_trait]
 impl Chain for ConversationalRetrieverChain {
     async fn call(&self, input_variables: PromptArgs) -> Result<GenerateResult, ChainError> {
-        let output = self.execute(input_variables).await?;
-        let result: GenerateResult = serde_json::from_value(output[DEFAULT_RESULT_KEY].clone())?;
-        Ok(result)
+        trace_chain(
+            "ConversationalRetrieverChain",
+            input_variables,
+            |input_variables| async move {
+                let output = self.execute(input_variables).await?;
+                let result: GenerateResult =
+                    serde_json::from_value(output[DEFAULT_RESULT_KEY].clone())?;
+                Ok(result)
+            },
+        )
+        .await
     }
 
     async fn execute(
@@ -94,17 +116,20 @@ impl Chain for ConversationalRetrieverChain {
             token_usage = Some(token);
         }
 
-        let documents = self
-            .retriever
-            .get_relevant_documents(&question)
-            .await
-            .map_err(|e| ChainError::RetrieverError(e.to_string()))?;
+        let documents = trace_retriever(
+            "Retriever",
+            &question,
+            self.retriever.get_relevant_documents(&question),
+        )
+        .await
+        .map_err(|e| ChainError::RetrieverError(e.to_string()))?;
 
+        let context_documents = self.context_documents(&documents);
         let mut output = self
             .combine_documents_chain
             .call(
                 StuffQAPromptBuilder::new()
-                    .documents(&documents)
+                    .documents(&context_documents)
                     .question(question.clone())
                     .build(),
             )
@@ -131,6 +156,13 @@ impl Chain for ConversationalRetrieverChain {
 
         result.insert(DEFAULT_RESULT_KEY.to_string(), json!(output));
 
+        if self.return_citations {
+            result.insert(
+                CONVERSATIONAL_RETRIEVAL_QA_DEFAULT_CITATIONS_KEY.to_string(),
+                json!(resolve_citations(&output.generation, &documents)),
+            );
+        }
+
         if self.return_source_documents {
             result.insert(
                 CONVERSATIONAL_RETRIEVAL_QA_DEFAULT_SOURCE_DOCUMENT_KEY.to_string(),
@@ -165,17 +197,20 @@ impl Chain for ConversationalRetrieverChain {
 
         let (question, _) = self.get_question(&history, &human_message.content).await?;
 
-        let documents = self
-            .retriever
-            .get_relevant_documents(&question)
-            .await
-            .map_err(|e| ChainError::RetrieverError(e.to_string()))?;
+        let documents = trace_retriever(
+            "Retriever",
+            &question,
+            self.retriever.get_relevant_documents(&question),
+        )
+        .await
+        .map_err(|e| ChainError::RetrieverError(e.to_string()))?;
 
+        let context_documents = self.context_documents(&documents);
         let stream = self
             .combine_documents_chain
             .stream(
                 StuffQAPromptBuilder::new()
-                    .documents(&documents)
+                    .documents(&context_documents)
                     .question(question.clone())
                     .build(),
             )
@@ -219,6 +254,10 @@ impl Chain for ConversationalRetrieverChain {
             keys.push(CONVERSATIONAL_RETRIEVAL_QA_DEFAULT_SOURCE_DOCUMENT_KEY.to_string());
         }
 
+        if self.return_citations {
+            keys.push(CONVERSATIONAL_RETRIEVAL_QA_DEFAULT_CITATIONS_KEY.to_string());
+        }
+
         if self.rephrase_question {
             keys.push(CONVERSATIONAL_RETRIEVAL_QA_DEFAULT_GENERATED_QUESTION_KEY.to_string());
         }
@@ -232,15 +271,17 @@ impl Chain for ConversationalRetrieverChain {
 
 #[cfg(test)]
 mod tests {
-    use std::error::Error;
-
     use crate::{
-        chain::ConversationalRetrieverChainBuilder,
-        llm::openai::{OpenAI, OpenAIModel},
-        memory::SimpleMemory,
+        chain::{Citation, ConversationalRetrieverChainBuilder},
+        error::LangChainError,
         prompt_args,
         schemas::Document,
     };
+    #[cfg(feature = "openai")]
+    use crate::{
+        llm::openai::{OpenAI, OpenAIModel},
+        memory::SimpleMemory,
+    };
 
     use super::*;
 
@@ -250,7 +291,7 @@ mod tests {
         async fn get_relevant_documents(
             &self,
             _question: &str,
-        ) -> Result<Vec<Document>, Box<dyn Error>> {
+        ) -> Result<Vec<Document>, LangChainError> {
             Ok(vec![
                 Document::new(format!(
                     "\nQuestion: {}\nAnswer: {}\n",
@@ -272,6 +313,51 @@ mod tests {
         }
     }
 
+    /// Answers from the numbered context, citing the second document.
+    struct CitingChain;
+
+    #[async_trait]
+    impl Chain for CitingChain {
+        async fn call(&self, input_variables: PromptArgs) -> Result<GenerateResult, ChainError> {
+            let documents: Vec<Document> =
+                serde_json::from_value(input_variables["input_documents"].clone())?;
+            assert!(documents[1].page_content.starts_with("[2] "));
+            Ok(GenerateResult {
+                generation: "Luis is 24 [2].".to_string(),
+                ..Default::default()
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retriever_conversational_citations() {
+        let chain = ConversationalRetrieverChainBuilder::new()
+            .combine_documents_chain(CitingChain)
+            .condense_question_chain(CitingChain)
+            .retriever(RetrieverTest {})
+            .return_citations(true)
+            .citation_markers(true)
+            .build()
+            .unwrap();
+
+        let output = chain
+            .execute(prompt_args! { "question" => "How old is Luis?" })
+            .await
+            .unwrap();
+        let citations: Vec<Citation> = serde_json::from_value(output["citations"].clone()).unwrap();
+        assert_eq!(citations.len(), 1);
+        assert_eq!(citations[0].marker, 2);
+        assert_eq!(
+            citations[0].span.as_ref().unwrap().text,
+            "Question: How old is Luis"
+        );
+
+        let source_documents: Vec<Document> =
+            serde_json::from_value(output["source_documents"].clone()).unwrap();
+        assert!(source_documents[1].page_content.starts_with("\nQuestion"));
+    }
+
+    #[cfg(feature = "openai")]
     #[tokio::test]
     #[ignore]
     async fn test_invoke_retriever_conversational() {
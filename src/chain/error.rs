@@ -1,6 +1,10 @@
 use thiserror::Error;
+use tokio::time::error::Elapsed;
 
-use crate::{language_models::LLMError, output_parsers::OutputParserError, prompt::PromptError};
+use crate::{
+    language_models::LLMError, moderation::ModerationError, output_parsers::OutputParserError,
+    prompt::PromptError,
+};
 
 #[derive(Error, Debug)]
 pub enum ChainError {
@@ -39,4 +43,26 @@ pub enum ChainError {
 
     #[error("Agent error: {0}")]
     AgentError(String),
+
+    #[error("Chain cancelled")]
+    Cancelled,
+
+    #[error("Chain timed out")]
+    Timeout(#[from] Elapsed),
+
+    #[error("Moderation error: {0}")]
+    ModerationError(#[from] ModerationError),
+}
+
+impl ChainError {
+    /// Whether the failure is transient, such as a rate limited or timed out LLM call, so the
+    /// chain may succeed if called again.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ChainError::LLMError(e) => e.is_retryable(),
+            ChainError::OutputParser(OutputParserError::LLMError(e)) => e.is_retryable(),
+            ChainError::Timeout(_) => true,
+            _ => false,
+        }
+    }
 }
@@ -5,6 +5,7 @@ use futures::Stream;
 use futures_util::TryStreamExt;
 
 use crate::{
+    callbacks::trace_chain,
     language_models::{llm::LLM, GenerateResult},
     output_parsers::{OutputParser, SimpleParser},
     prompt::{FormatPrompter, PromptArgs},
@@ -101,17 +102,20 @@ impl Chain for LLMChain {
     }
 
     async fn call(&self, input_variables: PromptArgs) -> Result<GenerateResult, ChainError> {
-        let prompt = self.prompt.format_prompt(input_variables.clone())?;
-        log::debug!("Prompt: {:?}", prompt);
-        let mut output = self.llm.generate(&prompt.to_chat_messages()).await?;
-        output.generation = self.output_parser.parse(&output.generation).await?;
-
-        Ok(output)
+        trace_chain("LLMChain", input_variables, |input_variables| async move {
+            let prompt = self.prompt.format_prompt(input_variables.clone())?;
+            tracing::debug!("Prompt: {:?}", prompt);
+            let mut output = self.llm.generate(&prompt.to_chat_messages()).await?;
+            output.generation = self.output_parser.parse(&output.generation).await?;
+
+            Ok(output)
+        })
+        .await
     }
 
     async fn invoke(&self, input_variables: PromptArgs) -> Result<String, ChainError> {
         let prompt = self.prompt.format_prompt(input_variables.clone())?;
-        log::debug!("Prompt: {:?}", prompt);
+        tracing::debug!("Prompt: {:?}", prompt);
         let output = self
             .llm
             .generate(&prompt.to_chat_messages())
@@ -126,7 +130,7 @@ impl Chain for LLMChain {
     ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamData, ChainError>> + Send>>, ChainError>
     {
         let prompt = self.prompt.format_prompt(input_variables.clone())?;
-        log::debug!("Prompt: {:?}", prompt);
+        tracing::debug!("Prompt: {:?}", prompt);
         let llm_stream = self.llm.stream(&prompt.to_chat_messages()).await?;
 
         // Map the errors from LLMError to ChainError
@@ -136,7 +140,7 @@ impl Chain for LLMChain {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "openai"))]
 mod tests {
     use crate::{
         chain::options::ChainCallOptions,
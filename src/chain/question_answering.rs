@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use futures::Stream;
 
 use crate::{
+    callbacks::trace_chain,
     language_models::{llm::LLM, GenerateResult},
     prompt::PromptArgs,
     prompt_args,
@@ -78,7 +79,12 @@ impl CondenseQuestionGeneratorChain {
 #[async_trait]
 impl Chain for CondenseQuestionGeneratorChain {
     async fn call(&self, input_variables: PromptArgs) -> Result<GenerateResult, ChainError> {
-        self.chain.call(input_variables).await
+        trace_chain(
+            "CondenseQuestionGeneratorChain",
+            input_variables,
+            |input_variables| async move { self.chain.call(input_variables).await },
+        )
+        .await
     }
 
     async fn stream(
@@ -98,6 +104,17 @@ Question:{{question}}
 Helpful Answer:
 "#;
 
+/// The question answering prompt for documents numbered by `number_documents`, asking the model
+/// to cite them with markers that `resolve_citations` reads back.
+pub(crate) const CITATION_QA_TEMPLATE: &str = r#"Use the following numbered pieces of context to answer the question at the end. If you don't know the answer, just say that you don't know, don't try to make up an answer.
+After each sentence that uses the context, cite the pieces it relies on by their numbers in square brackets, such as [1] or [1, 3].
+
+{{context}}
+
+Question:{{question}}
+Helpful Answer:
+"#;
+
 pub struct StuffQAPromptBuilder<'a> {
     input_documents: Vec<&'a Document>,
     question: String,
@@ -148,7 +165,7 @@ pub(crate) fn load_stuff_qa<L: Into<Box<dyn LLM>>>(
     StuffDocument::new(llm_chain)
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "openai"))]
 mod tests {
     use crate::{
         chain::{Chain, StuffDocument},
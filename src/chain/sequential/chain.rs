@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use serde_json::{json, Value};
 
 use crate::{
+    callbacks::trace_chain,
     chain::{Chain, ChainError, DEFAULT_OUTPUT_KEY, DEFAULT_RESULT_KEY},
     language_models::{GenerateResult, TokenUsage},
     prompt::PromptArgs,
@@ -19,13 +20,22 @@ pub struct SequentialChain {
 #[async_trait]
 impl Chain for SequentialChain {
     async fn call(&self, input_variables: PromptArgs) -> Result<GenerateResult, ChainError> {
-        let output = self.execute(input_variables).await?;
-        let result = output
-            .get(DEFAULT_RESULT_KEY)
-            .ok_or_else(|| ChainError::MissingInputVariable(DEFAULT_RESULT_KEY.to_string()))?
-            .clone();
-        let result: GenerateResult = serde_json::from_value(result)?;
-        Ok(result)
+        trace_chain(
+            "SequentialChain",
+            input_variables,
+            |input_variables| async move {
+                let output = self.execute(input_variables).await?;
+                let result = output
+                    .get(DEFAULT_RESULT_KEY)
+                    .ok_or_else(|| {
+                        ChainError::MissingInputVariable(DEFAULT_RESULT_KEY.to_string())
+                    })?
+                    .clone();
+                let result: GenerateResult = serde_json::from_value(result)?;
+                Ok(result)
+            },
+        )
+        .await
     }
     async fn invoke(&self, input_variables: PromptArgs) -> Result<String, ChainError> {
         self.call(input_variables.clone())
@@ -58,7 +68,7 @@ impl Chain for SequentialChain {
                 .unwrap_or(&json!(GenerateResult::default()))
                 .clone();
             let result: GenerateResult = serde_json::from_value(result)?;
-            log::debug!("{}", result.generation);
+            tracing::debug!("{}", result.generation);
             //Insert the output chain to the final output
             output_result.insert(output_key.clone(), json!(result.generation.clone()));
             input_variables.insert(output_key, json!(result.generation.clone()));
@@ -85,7 +95,7 @@ impl Chain for SequentialChain {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "openai"))]
 mod tests {
     use crate::{
         chain::{Chain, LLMChainBuilder},
@@ -5,6 +5,7 @@ use futures::Stream;
 use serde_json::Value;
 
 use crate::{
+    callbacks::trace_chain,
     chain::{chain_trait::Chain, llm_chain::LLMChain, ChainError},
     language_models::{GenerateResult, TokenUsage},
     prompt::PromptArgs,
@@ -131,7 +132,7 @@ impl SQLDatabaseChain {
         }
 
         let sql_query = output.generation.trim();
-        log::debug!("output: {:?}", sql_query);
+        tracing::debug!("output: {:?}", sql_query);
         let query_result = self
             .database
             .query(sql_query)
@@ -156,31 +157,40 @@ impl Chain for SQLDatabaseChain {
     }
 
     async fn call(&self, input_variables: PromptArgs) -> Result<GenerateResult, ChainError> {
-        let (llm_inputs, mut token_usage) = self.call_builder_chains(&input_variables).await?;
-        let output = self.llmchain.call(llm_inputs).await?;
-        if let Some(tokens) = output.tokens {
-            if let Some(general_result) = token_usage.as_mut() {
-                general_result.completion_tokens += tokens.completion_tokens;
-                general_result.total_tokens += tokens.total_tokens;
-            }
-        }
+        trace_chain(
+            "SQLDatabaseChain",
+            input_variables,
+            |input_variables| async move {
+                let (llm_inputs, mut token_usage) =
+                    self.call_builder_chains(&input_variables).await?;
+                let output = self.llmchain.call(llm_inputs).await?;
+                if let Some(tokens) = output.tokens {
+                    if let Some(general_result) = token_usage.as_mut() {
+                        general_result.completion_tokens += tokens.completion_tokens;
+                        general_result.total_tokens += tokens.total_tokens;
+                    }
+                }
 
-        let strs: Vec<&str> = output
-            .generation
-            .split("\n\n")
-            .next()
-            .unwrap_or("")
-            .split("Answer:")
-            .collect();
-        let mut output = strs[0];
-        if strs.len() > 1 {
-            output = strs[1];
-        }
-        output = output.trim();
-        Ok(GenerateResult {
-            generation: output.to_string(),
-            tokens: token_usage,
-        })
+                let strs: Vec<&str> = output
+                    .generation
+                    .split("\n\n")
+                    .next()
+                    .unwrap_or("")
+                    .split("Answer:")
+                    .collect();
+                let mut output = strs[0];
+                if strs.len() > 1 {
+                    output = strs[1];
+                }
+                output = output.trim();
+                Ok(GenerateResult {
+                    generation: output.to_string(),
+                    tokens: token_usage,
+                    ..Default::default()
+                })
+            },
+        )
+        .await
     }
 
     async fn invoke(&self, input_variables: PromptArgs) -> Result<String, ChainError> {
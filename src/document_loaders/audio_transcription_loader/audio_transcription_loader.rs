@@ -0,0 +1,274 @@
+use std::{collections::HashMap, path::Path, pin::Pin};
+
+pub use async_openai::config::{Config, OpenAIConfig};
+use async_openai::{
+    types::{
+        AudioInput, AudioResponseFormat, CreateTranscriptionRequestArgs, TimestampGranularity,
+    },
+    Client,
+};
+use async_stream::stream;
+use async_trait::async_trait;
+use futures::Stream;
+use serde_json::Value;
+
+use crate::{
+    document_loaders::{process_doc_stream, Loader, LoaderError},
+    schemas::Document,
+    text_splitter::TextSplitter,
+};
+
+/// Transcribes audio files with an OpenAI compatible `/audio/transcriptions` endpoint
+/// (OpenAI Whisper, Groq, a local whisper server, ...).
+///
+/// By default every transcript segment becomes a `Document` with `start` and `end` metadata
+/// in seconds, so answers can point to the right moment of a recording. With
+/// `with_split_segments(false)` each file becomes a single document whose lines are
+/// prefixed with their timestamp.
+///
+/// # Usage
+/// ```rust,ignore
+/// let loader = AudioTranscriptionLoader::from_path("./meeting.mp3").with_language("en");
+/// let documents = loader.load().await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct AudioTranscriptionLoader<C: Config> {
+    config: C,
+    inputs: Vec<(String, AudioInput)>,
+    model: String,
+    language: Option<String>,
+    prompt: Option<String>,
+    split_segments: bool,
+}
+
+impl<C: Config> AudioTranscriptionLoader<C> {
+    pub fn new(config: C) -> Self {
+        Self {
+            config,
+            inputs: Vec::new(),
+            model: "whisper-1".to_string(),
+            language: None,
+            prompt: None,
+            split_segments: true,
+        }
+    }
+
+    /// Adds an audio file to transcribe.
+    pub fn with_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        let source = path.as_ref().to_string_lossy().to_string();
+        self.inputs.push((source, AudioInput::from(path)));
+        self
+    }
+
+    /// Adds in-memory audio; the file name extension tells the API the audio format.
+    pub fn with_bytes<S: Into<String>>(mut self, filename: S, bytes: Vec<u8>) -> Self {
+        let filename = filename.into();
+        self.inputs
+            .push((filename.clone(), AudioInput::from_vec_u8(filename, bytes)));
+        self
+    }
+
+    pub fn with_config(mut self, config: C) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn with_model<S: Into<String>>(mut self, model: S) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Sets the ISO-639-1 language of the audio, which improves accuracy and latency.
+    pub fn with_language<S: Into<String>>(mut self, language: S) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Sets a prompt guiding the transcription style or spelling of uncommon words.
+    pub fn with_prompt<S: Into<String>>(mut self, prompt: S) -> Self {
+        self.prompt = Some(prompt.into());
+        self
+    }
+
+    pub fn with_split_segments(mut self, split_segments: bool) -> Self {
+        self.split_segments = split_segments;
+        self
+    }
+}
+
+impl AudioTranscriptionLoader<OpenAIConfig> {
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        Self::default().with_path(path)
+    }
+}
+
+impl Default for AudioTranscriptionLoader<OpenAIConfig> {
+    fn default() -> Self {
+        Self::new(OpenAIConfig::default())
+    }
+}
+
+fn format_timestamp(seconds: f32) -> String {
+    let total = seconds.max(0.0) as u64;
+    format!(
+        "{:02}:{:02}:{:02}",
+        total / 3600,
+        (total % 3600) / 60,
+        total % 60
+    )
+}
+
+#[async_trait]
+impl<C: Config + Send + Sync + 'static> Loader for AudioTranscriptionLoader<C> {
+    async fn load(
+        mut self,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
+        LoaderError,
+    > {
+        let client = Client::with_config(self.config);
+
+        let stream = stream! {
+            for (source, input) in self.inputs {
+                let mut request = CreateTranscriptionRequestArgs::default();
+                request
+                    .file(input)
+                    .model(self.model.as_str())
+                    .response_format(AudioResponseFormat::VerboseJson)
+                    .timestamp_granularities(vec![TimestampGranularity::Segment]);
+                if let Some(language) = &self.language {
+                    request.language(language.as_str());
+                }
+                if let Some(prompt) = &self.prompt {
+                    request.prompt(prompt.as_str());
+                }
+
+                let transcription = request.build()?;
+                let response = client.audio().transcribe_verbose_json(transcription).await?;
+
+                let metadata = HashMap::from([
+                    ("source".to_string(), Value::from(source)),
+                    ("language".to_string(), Value::from(response.language)),
+                    ("duration".to_string(), Value::from(response.duration)),
+                ]);
+                let segments = response.segments.unwrap_or_default();
+
+                if !self.split_segments || segments.is_empty() {
+                    let content = if segments.is_empty() {
+                        response.text
+                    } else {
+                        segments
+                            .iter()
+                            .map(|segment| {
+                                format!("[{}] {}", format_timestamp(segment.start), segment.text.trim())
+                            })
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    };
+                    yield Ok(Document::new(content).with_metadata(metadata));
+                    continue;
+                }
+
+                for segment in segments {
+                    let mut metadata = metadata.clone();
+                    metadata.insert("start".to_string(), Value::from(segment.start));
+                    metadata.insert("end".to_string(), Value::from(segment.end));
+                    yield Ok(Document::new(segment.text.trim()).with_metadata(metadata));
+                }
+            }
+        };
+
+        Ok(Box::pin(stream))
+    }
+
+    async fn load_and_split<TS: TextSplitter + 'static>(
+        mut self,
+        splitter: TS,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
+        LoaderError,
+    > {
+        let doc_stream = self.load().await?;
+        let stream = process_doc_stream(doc_stream, splitter).await;
+        Ok(Box::pin(stream))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+    use serde_json::json;
+
+    use super::*;
+
+    async fn mock_server() -> mockito::ServerGuard {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/audio/transcriptions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "language": "english",
+                    "duration": 65.5,
+                    "text": "Hello everyone. Let's start.",
+                    "segments": [
+                        { "id": 0, "seek": 0, "start": 0.0, "end": 2.5, "text": " Hello everyone.",
+                          "tokens": [], "temperature": 0.0, "avg_logprob": 0.0,
+                          "compression_ratio": 1.0, "no_speech_prob": 0.0 },
+                        { "id": 1, "seek": 0, "start": 62.0, "end": 65.5, "text": " Let's start.",
+                          "tokens": [], "temperature": 0.0, "avg_logprob": 0.0,
+                          "compression_ratio": 1.0, "no_speech_prob": 0.0 }
+                    ]
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn test_audio_transcription_loader() {
+        let server = mock_server().await;
+        let config = OpenAIConfig::default()
+            .with_api_base(server.url())
+            .with_api_key("test");
+
+        let documents = AudioTranscriptionLoader::new(config.clone())
+            .with_bytes("meeting.mp3", vec![0u8; 16])
+            .load()
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[1].page_content, "Let's start.");
+        assert_eq!(
+            documents[1].metadata.get("start").unwrap(),
+            &Value::from(62.0)
+        );
+        assert_eq!(
+            documents[1].metadata.get("source").unwrap(),
+            &Value::from("meeting.mp3")
+        );
+
+        let documents = AudioTranscriptionLoader::new(config)
+            .with_bytes("meeting.mp3", vec![0u8; 16])
+            .with_split_segments(false)
+            .load()
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(documents.len(), 1);
+        assert_eq!(
+            documents[0].page_content,
+            "[00:00:00] Hello everyone.\n[00:01:02] Let's start."
+        );
+    }
+}
@@ -0,0 +1,2 @@
+mod audio_transcription_loader;
+pub use audio_transcription_loader::*;
@@ -83,42 +83,47 @@ pub async fn find_files_with_extension(folder_path: &str, opts: &DirLoaderOption
         .unwrap();
 
     for file_name in all_files {
-        let path_str = file_name.clone();
-
-        // check if the file has the required extension
-        if let Some(suffixes) = &opts.suffixes {
-            let mut has_suffix = false;
-            for suffix in suffixes {
-                if path_str.ends_with(suffix) {
-                    has_suffix = true;
-                    break;
-                }
-            }
-            if !has_suffix {
-                continue;
-            }
+        if matches_dir_loader_options(&file_name, opts) {
+            matching_files.push(file_name);
         }
+    }
 
-        if opts
-            .path_filter
-            .as_ref()
-            .map_or(false, |f| f.0(&Path::new(&file_name)))
-        {
-            continue; // Skip this path if the filter returns true
-        }
+    matching_files
+}
 
-        // check if the file matches the glob pattern
-        if let Some(glob_pattern) = &opts.glob {
-            let glob = glob::Pattern::new(glob_pattern).unwrap();
-            if !glob.matches(&path_str) {
-                continue;
+/// Checks a file path against the suffixes, path filter and glob of the given options
+pub(crate) fn matches_dir_loader_options(path_str: &str, opts: &DirLoaderOptions) -> bool {
+    // check if the file has the required extension
+    if let Some(suffixes) = &opts.suffixes {
+        let mut has_suffix = false;
+        for suffix in suffixes {
+            if path_str.ends_with(suffix) {
+                has_suffix = true;
+                break;
             }
         }
+        if !has_suffix {
+            return false;
+        }
+    }
 
-        matching_files.push(path_str);
+    if opts
+        .path_filter
+        .as_ref()
+        .is_some_and(|f| f.0(Path::new(path_str)))
+    {
+        return false; // Skip this path if the filter returns true
     }
 
-    matching_files
+    // check if the file matches the glob pattern
+    if let Some(glob_pattern) = &opts.glob {
+        let glob = glob::Pattern::new(glob_pattern).unwrap();
+        if !glob.matches(path_str) {
+            return false;
+        }
+    }
+
+    true
 }
 
 #[cfg(test)]
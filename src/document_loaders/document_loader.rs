@@ -5,18 +5,26 @@ use async_trait::async_trait;
 use futures::Stream;
 use futures_util::{pin_mut, StreamExt};
 
-use crate::{schemas::Document, text_splitter::TextSplitter};
+use crate::{
+    schemas::Document,
+    text_splitter::{RecursiveCharacterTextSplitter, TextSplitter},
+};
 
 use super::LoaderError;
 
 #[async_trait]
 pub trait Loader: Send + Sync {
+    /// Starts loading and returns a stream of documents. Errors that prevent the source
+    /// from being opened are returned directly, errors on individual documents are
+    /// yielded by the stream.
     async fn load(
         self,
     ) -> Result<
         Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
         LoaderError,
     >;
+
+    /// Loads the documents and splits each one with `splitter` as it arrives.
     async fn load_and_split<TS: TextSplitter + 'static>(
         self,
         splitter: TS,
@@ -24,6 +32,50 @@ pub trait Loader: Send + Sync {
         Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
         LoaderError,
     >;
+
+    /// Loads the documents and splits them with the default `RecursiveCharacterTextSplitter`.
+    async fn load_and_split_default(
+        self,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
+        LoaderError,
+    >
+    where
+        Self: Sized + 'static,
+    {
+        self.load_and_split(RecursiveCharacterTextSplitter::default())
+            .await
+    }
+
+    /// Returns a single stream of documents, with a failure to start loading yielded as
+    /// its first item. Documents are produced lazily so large sources can be split,
+    /// embedded and stored incrementally without holding everything in memory.
+    ///
+    /// # Usage
+    /// ```rust,ignore
+    /// let mut documents = loader.load_and_stream();
+    /// while let Some(document) = documents.next().await {
+    ///     store.add_documents(&[document?], &VecStoreOptions::default()).await?;
+    /// }
+    /// ```
+    fn load_and_stream(
+        self,
+    ) -> Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>
+    where
+        Self: Sized + 'static,
+    {
+        Box::pin(stream! {
+            match self.load().await {
+                Ok(doc_stream) => {
+                    pin_mut!(doc_stream);
+                    while let Some(doc_result) = doc_stream.next().await {
+                        yield doc_result;
+                    }
+                }
+                Err(e) => yield Err(e),
+            }
+        })
+    }
 }
 
 pub(crate) async fn process_doc_stream<TS: TextSplitter + 'static>(
@@ -49,3 +101,24 @@ pub(crate) async fn process_doc_stream<TS: TextSplitter + 'static>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::document_loaders::{JsonLoader, TextLoader};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_load_and_stream() {
+        let documents = TextLoader::new("Hello world!")
+            .load_and_stream()
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(documents.len(), 1);
+        assert_eq!(documents[0].as_ref().unwrap().page_content, "Hello world!");
+
+        let mut documents = JsonLoader::from_string("not json").load_and_stream();
+        assert!(documents.next().await.unwrap().is_err());
+        assert!(documents.next().await.is_none());
+    }
+}
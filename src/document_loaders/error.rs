@@ -33,6 +33,14 @@ pub enum LoaderError {
     #[error(transparent)]
     PdfExtractOutputError(#[from] pdf_extract::OutputError),
 
+    #[error(transparent)]
+    ReqwestError(#[from] reqwest::Error),
+
+    #[cfg(feature = "openai")]
+    #[error(transparent)]
+    OpenAIError(#[from] async_openai::error::OpenAIError),
+
+    #[cfg(feature = "html")]
     #[error(transparent)]
     ReadabilityError(#[from] readability::error::Error),
 
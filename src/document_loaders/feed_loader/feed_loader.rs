@@ -0,0 +1,346 @@
+use std::{
+    collections::{HashMap, HashSet},
+    pin::Pin,
+};
+
+use async_stream::stream;
+use async_trait::async_trait;
+use feed_rs::model::{Entry, Text};
+use futures::Stream;
+use scraper::Html;
+use serde_json::Value;
+use url::Url;
+
+use crate::{
+    document_loaders::{fetch_document, process_doc_stream, Loader, LoaderError, WebLoaderOptions},
+    schemas::Document,
+    text_splitter::TextSplitter,
+};
+
+/// Loads the entries of RSS and Atom feeds, one `Document` per entry.
+///
+/// Entries are deduplicated by their GUID (or Atom id) across all the feeds. The document
+/// content is the entry title followed by its content or summary with HTML stripped; when
+/// `with_fetch_full_article(true)` is set the linked article page is fetched instead and
+/// cleaned like `WebLoader` does. Metadata includes `source` (the entry link), `title`,
+/// `guid`, `feed`, `published` and `updated` (RFC 3339) and `authors` when available.
+///
+/// # Usage
+/// ```rust,ignore
+/// let loader = FeedLoader::from_urls(["https://blog.rust-lang.org/feed.xml"])?
+///     .with_fetch_full_article(true);
+/// let documents = loader.load().await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct FeedLoader {
+    urls: Vec<Url>,
+    fetch_full_article: bool,
+    options: WebLoaderOptions,
+}
+
+impl FeedLoader {
+    pub fn new(urls: Vec<Url>) -> Self {
+        Self {
+            urls,
+            fetch_full_article: false,
+            options: WebLoaderOptions::default(),
+        }
+    }
+
+    pub fn from_urls<I, S>(urls: I) -> Result<Self, LoaderError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let urls = urls
+            .into_iter()
+            .map(|url| {
+                Url::parse(url.as_ref()).map_err(|e| {
+                    LoaderError::OtherError(format!("Invalid url {}: {e}", url.as_ref()))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(urls))
+    }
+
+    /// Fetches the page linked by each entry and uses it as the document content.
+    pub fn with_fetch_full_article(mut self, fetch_full_article: bool) -> Self {
+        self.fetch_full_article = fetch_full_article;
+        self
+    }
+
+    /// Sets the options used to fetch the feeds and the articles.
+    pub fn with_options(mut self, options: WebLoaderOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+fn text_to_plain(text: &Text) -> String {
+    if text.content_type.subty() == "html" {
+        html_to_plain(&text.content)
+    } else {
+        text.content.trim().to_string()
+    }
+}
+
+fn html_to_plain(html: &str) -> String {
+    Html::parse_fragment(html)
+        .root_element()
+        .text()
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+fn entry_metadata(
+    entry: &Entry,
+    feed_url: &Url,
+    feed_title: Option<&str>,
+) -> HashMap<String, Value> {
+    let mut metadata = HashMap::from([
+        ("guid".to_string(), Value::from(entry.id.as_str())),
+        ("feed".to_string(), Value::from(feed_url.as_str())),
+    ]);
+    if let Some(link) = entry.links.first() {
+        metadata.insert("source".to_string(), Value::from(link.href.as_str()));
+    }
+    if let Some(title) = &entry.title {
+        metadata.insert("title".to_string(), Value::from(text_to_plain(title)));
+    }
+    if let Some(feed_title) = feed_title {
+        metadata.insert("feed_title".to_string(), Value::from(feed_title));
+    }
+    if let Some(published) = entry.published {
+        metadata.insert("published".to_string(), Value::from(published.to_rfc3339()));
+    }
+    if let Some(updated) = entry.updated {
+        metadata.insert("updated".to_string(), Value::from(updated.to_rfc3339()));
+    }
+    if entry.authors.iter().any(|author| author.name.is_some()) {
+        let authors = entry
+            .authors
+            .iter()
+            .filter_map(|author| author.name.as_deref().map(Value::from))
+            .collect::<Vec<_>>();
+        metadata.insert("authors".to_string(), Value::from(authors));
+    }
+    metadata
+}
+
+fn entry_content(entry: &Entry) -> String {
+    let title = entry.title.as_ref().map(text_to_plain).unwrap_or_default();
+    let body = entry
+        .content
+        .as_ref()
+        .and_then(|content| content.body.as_deref())
+        .map(html_to_plain)
+        .or_else(|| entry.summary.as_ref().map(text_to_plain))
+        .unwrap_or_default();
+    format!("{}\n{}", title, body).trim().to_string()
+}
+
+#[async_trait]
+impl Loader for FeedLoader {
+    async fn load(
+        mut self,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
+        LoaderError,
+    > {
+        let client = self.options.build_client()?;
+        let keep_html = self.options.keep_html();
+
+        let stream = stream! {
+            let mut seen = HashSet::new();
+            for feed_url in self.urls {
+                let bytes = match client.get(feed_url.clone()).send().await {
+                    Ok(response) => match response.error_for_status() {
+                        Ok(response) => response.bytes().await,
+                        Err(e) => Err(e),
+                    },
+                    Err(e) => Err(e),
+                };
+                let feed = match bytes {
+                    Ok(bytes) => feed_rs::parser::parse(bytes.as_ref()).map_err(|e| {
+                        LoaderError::OtherError(format!("Invalid feed {}: {}", feed_url, e))
+                    }),
+                    Err(e) => Err(e.into()),
+                };
+                let feed = match feed {
+                    Ok(feed) => feed,
+                    Err(e) => {
+                        yield Err(e);
+                        continue;
+                    }
+                };
+                let feed_title = feed.title.as_ref().map(text_to_plain);
+
+                for entry in feed.entries {
+                    if !seen.insert(entry.id.clone()) {
+                        continue;
+                    }
+
+                    let mut metadata = entry_metadata(&entry, &feed_url, feed_title.as_deref());
+                    let link = entry.links.first().and_then(|link| Url::parse(&link.href).ok());
+
+                    let content = match (self.fetch_full_article, link) {
+                        (true, Some(link)) => match fetch_document(&client, link, keep_html).await {
+                            Ok(article) => article.page_content,
+                            Err(e) => {
+                                tracing::warn!("Failed to fetch article {}: {}", entry.id, e);
+                                entry_content(&entry)
+                            }
+                        },
+                        _ => entry_content(&entry),
+                    };
+
+                    if !metadata.contains_key("source") {
+                        metadata.insert("source".to_string(), Value::from(feed_url.as_str()));
+                    }
+                    yield Ok(Document::new(content).with_metadata(metadata));
+                }
+            }
+        };
+
+        Ok(Box::pin(stream))
+    }
+
+    async fn load_and_split<TS: TextSplitter + 'static>(
+        mut self,
+        splitter: TS,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
+        LoaderError,
+    > {
+        let doc_stream = self.load().await?;
+        let stream = process_doc_stream(doc_stream, splitter).await;
+        Ok(Box::pin(stream))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_feed_loader() {
+        let mut server = mockito::Server::new_async().await;
+        let base = server.url();
+
+        server
+            .mock("GET", "/rss.xml")
+            .with_status(200)
+            .with_header("content-type", "application/rss+xml")
+            .with_body(format!(
+                r#"<?xml version="1.0"?>
+<rss version="2.0"><channel>
+  <title>News</title>
+  <item>
+    <title>First</title>
+    <link>{base}/first</link>
+    <guid>item-1</guid>
+    <pubDate>Mon, 01 Jan 2024 10:00:00 GMT</pubDate>
+    <description>&lt;p&gt;First &lt;b&gt;summary&lt;/b&gt;&lt;/p&gt;</description>
+  </item>
+  <item>
+    <title>Second</title>
+    <link>{base}/second</link>
+    <guid>item-2</guid>
+    <description>Second summary</description>
+  </item>
+</channel></rss>"#
+            ))
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/atom.xml")
+            .with_status(200)
+            .with_header("content-type", "application/atom+xml")
+            .with_body(format!(
+                r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Mirror</title>
+  <id>urn:mirror</id>
+  <updated>2024-01-02T00:00:00Z</updated>
+  <entry>
+    <title>First again</title>
+    <id>item-1</id>
+    <link href="{base}/first"/>
+    <updated>2024-01-02T00:00:00Z</updated>
+  </entry>
+  <entry>
+    <title>Third</title>
+    <id>item-3</id>
+    <link href="{base}/third"/>
+    <updated>2024-01-03T00:00:00Z</updated>
+    <summary>Third summary</summary>
+  </entry>
+</feed>"#
+            ))
+            .create_async()
+            .await;
+
+        let loader =
+            FeedLoader::from_urls([format!("{base}/rss.xml"), format!("{base}/atom.xml")]).unwrap();
+        let documents = loader
+            .load()
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(documents.len(), 3);
+        assert_eq!(documents[0].page_content, "First\nFirst summary");
+        assert_eq!(
+            documents[0].metadata.get("published").unwrap(),
+            &Value::from("2024-01-01T10:00:00+00:00")
+        );
+        assert_eq!(
+            documents[0].metadata.get("source").unwrap(),
+            &Value::from(format!("{base}/first"))
+        );
+        assert_eq!(documents[2].page_content, "Third\nThird summary");
+        assert_eq!(documents[2].metadata.get("guid").unwrap(), "item-3");
+    }
+
+    #[tokio::test]
+    async fn test_feed_loader_fetch_full_article() {
+        let mut server = mockito::Server::new_async().await;
+        let base = server.url();
+
+        server
+            .mock("GET", "/rss.xml")
+            .with_status(200)
+            .with_body(format!(
+                r#"<rss version="2.0"><channel><title>News</title>
+<item><title>First</title><link>{base}/first</link><guid>item-1</guid></item>
+</channel></rss>"#
+            ))
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/first")
+            .with_status(200)
+            .with_header("content-type", "text/plain")
+            .with_body("The full article")
+            .create_async()
+            .await;
+
+        let documents = FeedLoader::from_urls([format!("{base}/rss.xml")])
+            .unwrap()
+            .with_fetch_full_article(true)
+            .load()
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(documents.len(), 1);
+        assert_eq!(documents[0].page_content, "The full article");
+    }
+}
@@ -0,0 +1,2 @@
+mod feed_loader;
+pub use feed_loader::*;
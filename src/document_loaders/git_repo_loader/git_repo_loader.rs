@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::pin::Pin;
+
+use crate::document_loaders::{
+    matches_dir_loader_options, process_doc_stream, DirLoaderOptions, LoaderError,
+};
+use crate::{document_loaders::Loader, schemas::Document, text_splitter::TextSplitter};
+use async_trait::async_trait;
+use futures::Stream;
+use gix::{traverse::tree::Recorder, ThreadSafeRepository};
+use serde_json::Value;
+use tokio::process::Command;
+
+/// Loads the files of a git repository as committed at `HEAD` (or at the configured revision),
+/// emitting one document per file with `source` (the path inside the repository) and
+/// `commit` metadata. Files are filtered with the same `DirLoaderOptions` used by the
+/// directory based loaders; files that are not valid utf-8 are skipped.
+#[derive(Clone)]
+pub struct GitRepoLoader {
+    repo: ThreadSafeRepository,
+    revision: Option<String>,
+    options: DirLoaderOptions,
+}
+
+impl GitRepoLoader {
+    pub fn new(repo: ThreadSafeRepository) -> Self {
+        Self {
+            repo,
+            revision: None,
+            options: DirLoaderOptions::default(),
+        }
+    }
+
+    pub fn from_path<P: AsRef<Path>>(directory: P) -> Result<Self, LoaderError> {
+        let repo = ThreadSafeRepository::discover(directory)?;
+        Ok(Self::new(repo))
+    }
+
+    /// Clones `url` into `directory` with the `git` executable and opens it.
+    pub async fn from_remote<S: AsRef<str>, P: AsRef<Path>>(
+        url: S,
+        directory: P,
+    ) -> Result<Self, LoaderError> {
+        let output = Command::new("git")
+            .arg("clone")
+            .arg("--depth")
+            .arg("1")
+            .arg(url.as_ref())
+            .arg(directory.as_ref())
+            .output()
+            .await?;
+        if !output.status.success() {
+            return Err(LoaderError::OtherError(format!(
+                "Failed to clone {}: {}",
+                url.as_ref(),
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+        Self::from_path(directory)
+    }
+
+    /// Loads the files at the given revision (branch, tag or commit) instead of `HEAD`.
+    pub fn with_revision<S: Into<String>>(mut self, revision: S) -> Self {
+        self.revision = Some(revision.into());
+        self
+    }
+
+    pub fn with_options(mut self, options: DirLoaderOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+fn other_error<E: std::fmt::Display>(e: E) -> LoaderError {
+    LoaderError::OtherError(e.to_string())
+}
+
+#[async_trait]
+impl Loader for GitRepoLoader {
+    async fn load(
+        mut self,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
+        LoaderError,
+    > {
+        let repo = self.repo.to_thread_local();
+        let (commit_id, entries) = {
+            let commit = match &self.revision {
+                Some(revision) => repo
+                    .rev_parse_single(revision.as_str())
+                    .map_err(other_error)?
+                    .object()
+                    .map_err(other_error)?
+                    .peel_to_commit()
+                    .map_err(other_error)?,
+                None => repo.head_commit().map_err(other_error)?,
+            };
+            let commit_id = commit.id.to_string();
+
+            let mut recorder = Recorder::default();
+            commit
+                .tree()
+                .map_err(other_error)?
+                .traverse()
+                .breadthfirst(&mut recorder)
+                .map_err(other_error)?;
+
+            let entries = recorder
+                .records
+                .into_iter()
+                .filter(|entry| entry.mode.is_blob())
+                .map(|entry| (entry.filepath.to_string(), entry.oid))
+                .filter(|(path, _)| matches_dir_loader_options(path, &self.options))
+                .collect::<Vec<_>>();
+            (commit_id, entries)
+        };
+
+        // Since gix objects can't be shared across thread safely, use channels as a workaround.
+        let (tx, rx) = flume::bounded(1);
+
+        tokio::spawn(async move {
+            for (path, oid) in entries {
+                let data = match repo.find_object(oid) {
+                    Ok(object) => object.detach().data,
+                    Err(e) => {
+                        if tx.send(Err(other_error(e))).is_err() {
+                            break;
+                        }
+                        continue;
+                    }
+                };
+                let Ok(content) = String::from_utf8(data) else {
+                    tracing::debug!("Skipping non utf-8 file {}", path);
+                    continue;
+                };
+
+                let document = Document::new(content).with_metadata(HashMap::from([
+                    ("source".to_string(), Value::from(path)),
+                    ("commit".to_string(), Value::from(commit_id.as_str())),
+                ]));
+                if tx.send(Ok(document)).is_err() {
+                    // stream might have been dropped
+                    break;
+                }
+            }
+        });
+
+        Ok(Box::pin(rx.into_stream()))
+    }
+
+    async fn load_and_split<TS: TextSplitter + 'static>(
+        mut self,
+        splitter: TS,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
+        LoaderError,
+    > {
+        let doc_stream = self.load().await?;
+        let stream = process_doc_stream(doc_stream, splitter).await;
+        Ok(Box::pin(stream))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process::Command};
+
+    use futures_util::StreamExt;
+
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .expect("Failed to run git");
+        assert!(status.success());
+    }
+
+    #[tokio::test]
+    async fn test_git_repo_loader() {
+        let dir = env::temp_dir().join("git_repo_loader_test_dir");
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir).unwrap();
+        }
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("src/lib.rs"), "pub fn answer() -> u8 { 42 }").unwrap();
+        std::fs::write(dir.join("README.md"), "# Test").unwrap();
+        git(&dir, &["init", "-q"]);
+        git(&dir, &["add", "."]);
+        git(&dir, &["commit", "-q", "-m", "initial"]);
+        std::fs::write(dir.join("untracked.rs"), "fn main() {}").unwrap();
+
+        let loader = GitRepoLoader::from_path(&dir)
+            .unwrap()
+            .with_options(DirLoaderOptions {
+                suffixes: Some(vec![".rs".to_string()]),
+                ..Default::default()
+            });
+        let head = loader.repo.to_thread_local().head_id().unwrap().to_string();
+
+        let documents = loader
+            .load()
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(documents.len(), 1);
+        assert_eq!(documents[0].page_content, "pub fn answer() -> u8 { 42 }");
+        assert_eq!(
+            documents[0].metadata.get("source").unwrap(),
+            &Value::from("src/lib.rs")
+        );
+        assert_eq!(
+            documents[0].metadata.get("commit").unwrap(),
+            &Value::from(head)
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
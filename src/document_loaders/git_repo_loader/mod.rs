@@ -0,0 +1,2 @@
+mod git_repo_loader;
+pub use git_repo_loader::*;
@@ -21,6 +21,7 @@ use crate::{
 pub struct HtmlLoader<R> {
     html: R,
     url: Url,
+    keep_html: bool,
 }
 
 impl HtmlLoader<Cursor<Vec<u8>>> {
@@ -33,7 +34,18 @@ impl HtmlLoader<Cursor<Vec<u8>>> {
 
 impl<R: Read> HtmlLoader<R> {
     pub fn new(html: R, url: Url) -> Self {
-        Self { html, url }
+        Self {
+            html,
+            url,
+            keep_html: false,
+        }
+    }
+
+    /// Keeps the page as raw HTML instead of extracting its readable text,
+    /// e.g. to split it with `HtmlSectionSplitter`.
+    pub fn with_keep_html(mut self, keep_html: bool) -> Self {
+        self.keep_html = keep_html;
+        self
     }
 }
 
@@ -53,11 +65,16 @@ impl<R: Read + Send + Sync + 'static> Loader for HtmlLoader<R> {
         Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
         LoaderError,
     > {
-        let cleaned_html = readability::extractor::extract(&mut self.html, &self.url)?;
-        let doc =
-            Document::new(format!("{}\n{}", cleaned_html.title, cleaned_html.text)).with_metadata(
-                HashMap::from([("source".to_string(), Value::from(self.url.as_str()))]),
-            );
+        let doc = if self.keep_html {
+            let mut html = String::new();
+            self.html.read_to_string(&mut html)?;
+            Document::new(html).with_metadata(HashMap::from([(
+                "source".to_string(),
+                Value::from(self.url.as_str()),
+            )]))
+        } else {
+            html_to_document(&mut self.html, &self.url)?
+        };
 
         let stream = stream::iter(vec![Ok(doc)]);
         Ok(Box::pin(stream))
@@ -76,11 +93,23 @@ impl<R: Read + Send + Sync + 'static> Loader for HtmlLoader<R> {
     }
 }
 
+/// Extracts the readable title and text of an HTML page into a `Document`
+/// with the page url as its `source` metadata.
+pub(crate) fn html_to_document<R: Read>(html: &mut R, url: &Url) -> Result<Document, LoaderError> {
+    let cleaned_html = readability::extractor::extract(html, url)?;
+    Ok(
+        Document::new(format!("{}\n{}", cleaned_html.title, cleaned_html.text)).with_metadata(
+            HashMap::from([("source".to_string(), Value::from(url.as_str()))]),
+        ),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use futures_util::StreamExt;
 
     use super::*;
+    use crate::text_splitter::HtmlSectionSplitter;
 
     #[tokio::test]
     async fn test_html_loader() {
@@ -132,4 +161,31 @@ mod tests {
         );
         assert_eq!(documents[0].page_content, expected);
     }
+
+    #[tokio::test]
+    async fn test_html_loader_keep_html_with_section_splitter() {
+        let input = "<h1>Title</h1><p>Hello</p><section id=\"more\"><p>World</p></section>";
+        let html_loader =
+            HtmlLoader::from_string(input, Url::parse("https://example.com/").unwrap())
+                .with_keep_html(true);
+
+        let documents = html_loader
+            .load_and_split(HtmlSectionSplitter::default())
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[0].page_content, "Hello");
+        assert_eq!(
+            documents[1].metadata.get("element_path").unwrap(),
+            &Value::from("section#more > h1")
+        );
+        assert_eq!(
+            documents[1].metadata.get("source").unwrap(),
+            &Value::from("https://example.com/")
+        );
+    }
 }
@@ -0,0 +1,278 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufRead, BufReader, Cursor, Read},
+    path::Path,
+    pin::Pin,
+};
+
+use async_stream::stream;
+use async_trait::async_trait;
+use futures::Stream;
+use serde_json::Value;
+
+use crate::{
+    document_loaders::{process_doc_stream, JsonSelector, Loader, LoaderError},
+    schemas::Document,
+    text_splitter::TextSplitter,
+};
+
+/// Loads JSON or JSON Lines data, turning each selected record into a `Document`.
+///
+/// - `jq_schema` selects the records of a JSON document (defaults to `.`, the whole document).
+///   It is ignored for JSON Lines, where every line is a record.
+/// - `content_key` selects the content inside each record; strings are used as-is and any
+///   other value is serialized. Without it the whole record is the content.
+/// - `metadata_keys` select the values stored as metadata, keyed by their last path segment.
+///   Without it every other top level field of an object record becomes metadata.
+///
+/// Each document also gets a `seq_num` metadata entry, and `source` when loaded from a path.
+///
+/// # Usage
+/// ```rust,ignore
+/// let loader = JsonLoader::from_path("./messages.jsonl")?
+///     .with_content_key(".text")?
+///     .with_metadata_keys(&[".author", ".channel.name"])?;
+/// let documents = loader.load().await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsonLoader<R> {
+    reader: R,
+    json_lines: bool,
+    jq_schema: JsonSelector,
+    content_key: Option<JsonSelector>,
+    metadata_keys: Option<Vec<JsonSelector>>,
+    source: Option<String>,
+}
+
+impl<R: Read> JsonLoader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            json_lines: false,
+            jq_schema: JsonSelector::parse(".").expect("identity selector is valid"),
+            content_key: None,
+            metadata_keys: None,
+            source: None,
+        }
+    }
+
+    /// Reads the input as JSON Lines, one record per non empty line.
+    pub fn with_json_lines(mut self, json_lines: bool) -> Self {
+        self.json_lines = json_lines;
+        self
+    }
+
+    pub fn with_jq_schema(mut self, jq_schema: &str) -> Result<Self, LoaderError> {
+        self.jq_schema = JsonSelector::parse(jq_schema)?;
+        Ok(self)
+    }
+
+    pub fn with_content_key(mut self, content_key: &str) -> Result<Self, LoaderError> {
+        self.content_key = Some(JsonSelector::parse(content_key)?);
+        Ok(self)
+    }
+
+    pub fn with_metadata_keys(mut self, metadata_keys: &[&str]) -> Result<Self, LoaderError> {
+        self.metadata_keys = Some(
+            metadata_keys
+                .iter()
+                .map(|key| JsonSelector::parse(*key))
+                .collect::<Result<_, _>>()?,
+        );
+        Ok(self)
+    }
+}
+
+impl JsonLoader<Cursor<Vec<u8>>> {
+    pub fn from_string<S: Into<String>>(input: S) -> Self {
+        let input = input.into();
+        let reader = Cursor::new(input.into_bytes());
+        Self::new(reader)
+    }
+}
+
+impl JsonLoader<BufReader<File>> {
+    /// Opens a JSON file. Files ending in `.jsonl` or `.ndjson` are read as JSON Lines.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, LoaderError> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        let reader = BufReader::new(file);
+        let json_lines = matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("jsonl") | Some("ndjson")
+        );
+
+        let mut loader = Self::new(reader).with_json_lines(json_lines);
+        loader.source = Some(path.to_string_lossy().to_string());
+        Ok(loader)
+    }
+}
+
+struct RecordMapper {
+    content_key: Option<JsonSelector>,
+    metadata_keys: Option<Vec<JsonSelector>>,
+    source: Option<String>,
+}
+
+impl RecordMapper {
+    fn to_document(&self, record: &Value, seq_num: usize) -> Document {
+        let content = match &self.content_key {
+            Some(selector) => selector.select_first(record).unwrap_or(&Value::Null),
+            None => record,
+        };
+        let page_content = match content {
+            Value::String(text) => text.clone(),
+            Value::Null => String::new(),
+            other => other.to_string(),
+        };
+
+        let mut metadata = HashMap::new();
+        match &self.metadata_keys {
+            Some(selectors) => {
+                for selector in selectors {
+                    if let (Some(key), Some(value)) =
+                        (selector.last_key(), selector.select_first(record))
+                    {
+                        metadata.insert(key.to_string(), value.clone());
+                    }
+                }
+            }
+            None => {
+                if let (Some(content_key), Value::Object(fields)) = (&self.content_key, record) {
+                    let skip = content_key.top_level_key();
+                    for (key, value) in fields {
+                        if Some(key.as_str()) != skip {
+                            metadata.insert(key.clone(), value.clone());
+                        }
+                    }
+                }
+            }
+        }
+        metadata.insert("seq_num".to_string(), Value::from(seq_num));
+        if let Some(source) = &self.source {
+            metadata.insert("source".to_string(), Value::from(source.as_str()));
+        }
+
+        Document::new(page_content).with_metadata(metadata)
+    }
+}
+
+#[async_trait]
+impl<R: Read + Send + Sync + 'static> Loader for JsonLoader<R> {
+    async fn load(
+        mut self,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
+        LoaderError,
+    > {
+        let mapper = RecordMapper {
+            content_key: self.content_key,
+            metadata_keys: self.metadata_keys,
+            source: self.source,
+        };
+
+        if self.json_lines {
+            let reader = BufReader::new(self.reader);
+            let stream = stream! {
+                let mut seq_num = 0;
+                for line in reader.lines() {
+                    let line = line?;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let record: Value = serde_json::from_str(&line).map_err(|e| {
+                        LoaderError::OtherError(format!("Invalid json line {}: {}", seq_num + 1, e))
+                    })?;
+                    seq_num += 1;
+                    yield Ok(mapper.to_document(&record, seq_num));
+                }
+            };
+            return Ok(Box::pin(stream));
+        }
+
+        let value: Value = serde_json::from_reader(self.reader)
+            .map_err(|e| LoaderError::OtherError(format!("Invalid json: {e}")))?;
+        let documents = self
+            .jq_schema
+            .select(&value)
+            .into_iter()
+            .enumerate()
+            .map(|(i, record)| Ok(mapper.to_document(record, i + 1)))
+            .collect::<Vec<_>>();
+
+        Ok(Box::pin(futures::stream::iter(documents)))
+    }
+
+    async fn load_and_split<TS: TextSplitter + 'static>(
+        mut self,
+        splitter: TS,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
+        LoaderError,
+    > {
+        let doc_stream = self.load().await?;
+        let stream = process_doc_stream(doc_stream, splitter).await;
+        Ok(Box::pin(stream))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_json_loader() {
+        let input = r#"{
+            "messages": [
+                { "text": "Hello", "author": "alice", "channel": { "name": "general" } },
+                { "text": "World", "author": "bob", "channel": { "name": "random" } }
+            ]
+        }"#;
+
+        let documents = JsonLoader::from_string(input)
+            .with_jq_schema(".messages[]")
+            .unwrap()
+            .with_content_key(".text")
+            .unwrap()
+            .with_metadata_keys(&[".author", ".channel.name"])
+            .unwrap()
+            .load()
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[0].page_content, "Hello");
+        assert_eq!(documents[1].page_content, "World");
+        assert_eq!(documents[1].metadata.get("author").unwrap(), "bob");
+        assert_eq!(documents[1].metadata.get("name").unwrap(), "random");
+        assert_eq!(documents[1].metadata.get("seq_num").unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_json_lines_loader() {
+        let input = "{\"text\": \"first\", \"id\": 1}\n\n{\"text\": \"second\", \"id\": 2}\n";
+
+        let documents = JsonLoader::from_string(input)
+            .with_json_lines(true)
+            .with_content_key(".text")
+            .unwrap()
+            .load()
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[0].page_content, "first");
+        assert_eq!(documents[0].metadata.get("id").unwrap(), 1);
+        assert!(!documents[0].metadata.contains_key("text"));
+        assert_eq!(documents[1].metadata.get("seq_num").unwrap(), 2);
+    }
+}
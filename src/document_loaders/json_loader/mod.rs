@@ -0,0 +1,5 @@
+mod json_loader;
+pub use json_loader::*;
+
+mod selector;
+pub use selector::*;
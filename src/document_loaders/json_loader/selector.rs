@@ -0,0 +1,183 @@
+use serde_json::Value;
+
+use crate::document_loaders::LoaderError;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+    Iterate,
+}
+
+/// A small subset of jq paths used to pick values out of JSON documents.
+///
+/// Supported syntax: `.` (identity), `.key`, `.["quoted key"]`, `[0]` (index) and
+/// `[]` (iterate over an array or the values of an object), e.g. `.items[].body`.
+/// JSON pointers such as `/items/0/body` are accepted as well.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonSelector {
+    expression: String,
+    segments: Vec<Segment>,
+}
+
+impl JsonSelector {
+    pub fn parse<S: Into<String>>(expression: S) -> Result<Self, LoaderError> {
+        let expression = expression.into();
+        let segments = if expression.starts_with('/') {
+            parse_pointer(&expression)
+        } else {
+            parse_jq(&expression)?
+        };
+        Ok(Self {
+            expression,
+            segments,
+        })
+    }
+
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// The name of the last key of the path, used as metadata key.
+    pub fn last_key(&self) -> Option<&str> {
+        self.segments
+            .iter()
+            .rev()
+            .find_map(|segment| match segment {
+                Segment::Key(key) => Some(key.as_str()),
+                _ => None,
+            })
+    }
+
+    /// Returns every value matched by the selector, in document order.
+    pub fn select<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        let mut current = vec![value];
+        for segment in &self.segments {
+            current = current
+                .into_iter()
+                .flat_map(|value| -> Vec<&Value> {
+                    match segment {
+                        Segment::Key(key) => value.get(key).into_iter().collect(),
+                        Segment::Index(index) => value.get(index).into_iter().collect(),
+                        Segment::Iterate => match value {
+                            Value::Array(values) => values.iter().collect(),
+                            Value::Object(map) => map.values().collect(),
+                            _ => Vec::new(),
+                        },
+                    }
+                })
+                .collect();
+        }
+        current
+    }
+
+    /// Returns the first value matched by the selector.
+    pub fn select_first<'a>(&self, value: &'a Value) -> Option<&'a Value> {
+        self.select(value).into_iter().next()
+    }
+
+    /// Whether the selector is a single top level key, e.g. `.text`.
+    pub(crate) fn top_level_key(&self) -> Option<&str> {
+        match self.segments.as_slice() {
+            [Segment::Key(key)] => Some(key.as_str()),
+            _ => None,
+        }
+    }
+}
+
+fn parse_pointer(pointer: &str) -> Vec<Segment> {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|token| {
+            let token = token.replace("~1", "/").replace("~0", "~");
+            match token.parse::<usize>() {
+                Ok(index) => Segment::Index(index),
+                Err(_) => Segment::Key(token),
+            }
+        })
+        .collect()
+}
+
+fn parse_jq(expression: &str) -> Result<Vec<Segment>, LoaderError> {
+    let invalid = || LoaderError::OtherError(format!("Invalid json selector: {expression}"));
+    let chars: Vec<char> = expression.trim().chars().collect();
+    let mut segments = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '.' => {
+                i += 1;
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '-')
+                {
+                    i += 1;
+                }
+                if i > start {
+                    segments.push(Segment::Key(chars[start..i].iter().collect()));
+                }
+            }
+            '[' => {
+                let end = chars[i..]
+                    .iter()
+                    .position(|c| *c == ']')
+                    .ok_or_else(invalid)?
+                    + i;
+                let inner: String = chars[i + 1..end].iter().collect();
+                let inner = inner.trim();
+                if inner.is_empty() {
+                    segments.push(Segment::Iterate);
+                } else if let Some(key) = inner
+                    .strip_prefix('"')
+                    .and_then(|inner| inner.strip_suffix('"'))
+                {
+                    segments.push(Segment::Key(key.to_string()));
+                } else {
+                    segments.push(Segment::Index(inner.parse().map_err(|_| invalid())?));
+                }
+                i = end + 1;
+            }
+            _ => return Err(invalid()),
+        }
+    }
+
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_json_selector() {
+        let value = json!({
+            "items": [
+                { "body": "first", "meta": { "lang": "en" } },
+                { "body": "second", "meta": { "lang": "de" } }
+            ],
+            "weird key": 1
+        });
+
+        let selector = JsonSelector::parse(".items[].body").unwrap();
+        assert_eq!(selector.select(&value), vec!["first", "second"]);
+        assert_eq!(selector.last_key(), Some("body"));
+
+        let selector = JsonSelector::parse(".items[1].meta.lang").unwrap();
+        assert_eq!(selector.select_first(&value).unwrap(), "de");
+
+        let selector = JsonSelector::parse(r#".["weird key"]"#).unwrap();
+        assert_eq!(selector.select_first(&value).unwrap(), 1);
+
+        let selector = JsonSelector::parse("/items/0/body").unwrap();
+        assert_eq!(selector.select_first(&value).unwrap(), "first");
+
+        let selector = JsonSelector::parse(".").unwrap();
+        assert_eq!(selector.select_first(&value).unwrap(), &value);
+
+        assert!(JsonSelector::parse(".items[abc]").is_err());
+    }
+}
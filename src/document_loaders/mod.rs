@@ -7,12 +7,22 @@ pub use text_loader::*;
 mod csv_loader;
 pub use csv_loader::*;
 
+mod json_loader;
+pub use json_loader::*;
+
 #[cfg(feature = "git")]
 mod git_commit_loader;
 #[cfg(feature = "git")]
 pub use git_commit_loader::*;
 
+#[cfg(feature = "git")]
+mod git_repo_loader;
+#[cfg(feature = "git")]
+pub use git_repo_loader::*;
+
+#[cfg(feature = "pandoc")]
 mod pandoc_loader;
+#[cfg(feature = "pandoc")]
 pub use pandoc_loader::*;
 
 #[cfg(any(feature = "lopdf", feature = "pdf-extract"))]
@@ -20,9 +30,41 @@ mod pdf_loader;
 #[cfg(any(feature = "lopdf", feature = "pdf-extract"))]
 pub use pdf_loader::*;
 
+#[cfg(feature = "html")]
 mod html_loader;
+#[cfg(feature = "html")]
 pub use html_loader::*;
 
+#[cfg(feature = "web")]
+mod web_loader;
+#[cfg(feature = "web")]
+pub use web_loader::*;
+
+#[cfg(feature = "web")]
+mod sitemap_loader;
+#[cfg(feature = "web")]
+pub use sitemap_loader::*;
+
+#[cfg(feature = "notion")]
+mod notion_loader;
+#[cfg(feature = "notion")]
+pub use notion_loader::*;
+
+#[cfg(feature = "openai")]
+mod audio_transcription_loader;
+#[cfg(feature = "openai")]
+pub use audio_transcription_loader::*;
+
+#[cfg(feature = "rss")]
+mod feed_loader;
+#[cfg(feature = "rss")]
+pub use feed_loader::*;
+
+#[cfg(feature = "s3")]
+mod s3_loader;
+#[cfg(feature = "s3")]
+pub use s3_loader::*;
+
 #[cfg(feature = "html-to-markdown")]
 mod html_to_markdown_loader;
 #[cfg(feature = "html-to-markdown")]
@@ -31,7 +73,9 @@ pub use html_to_markdown_loader::*;
 mod error;
 pub use error::*;
 
+#[cfg(not(target_arch = "wasm32"))]
 mod dir_loader;
+#[cfg(not(target_arch = "wasm32"))]
 pub use dir_loader::*;
 
 #[cfg(feature = "tree-sitter")]
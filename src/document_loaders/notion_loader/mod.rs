@@ -0,0 +1,2 @@
+mod notion_loader;
+pub use notion_loader::*;
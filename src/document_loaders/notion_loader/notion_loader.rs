@@ -0,0 +1,488 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    pin::Pin,
+};
+
+use async_recursion::async_recursion;
+use async_stream::stream;
+use async_trait::async_trait;
+use futures::Stream;
+use reqwest::Client;
+use serde_json::{json, Value};
+
+use crate::{
+    document_loaders::{process_doc_stream, Loader, LoaderError},
+    platform::send,
+    schemas::Document,
+    text_splitter::TextSplitter,
+};
+
+const NOTION_API_BASE: &str = "https://api.notion.com/v1";
+const NOTION_VERSION: &str = "2022-06-28";
+
+/// Where the `NotionLoader` starts walking the workspace.
+#[derive(Debug, Clone)]
+pub enum NotionSource {
+    Page(String),
+    Database(String),
+}
+
+/// Loads Notion pages through the Notion API, converting their blocks to markdown.
+/// Each page becomes one `Document` with `source` (the page url), `page_id` and `title` metadata.
+/// Child pages and child databases are followed when `recursive` is enabled (the default).
+///
+/// # Usage
+/// ```rust,ignore
+/// let loader = NotionLoader::from_database(std::env::var("NOTION_API_KEY")?, "database-id");
+/// let documents = loader.load().await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct NotionLoader {
+    api_key: String,
+    source: NotionSource,
+    base_url: String,
+    recursive: bool,
+}
+
+impl NotionLoader {
+    pub fn new<S: Into<String>>(api_key: S, source: NotionSource) -> Self {
+        Self {
+            api_key: api_key.into(),
+            source,
+            base_url: NOTION_API_BASE.to_string(),
+            recursive: true,
+        }
+    }
+
+    pub fn from_page<S: Into<String>, P: Into<String>>(api_key: S, page_id: P) -> Self {
+        Self::new(api_key, NotionSource::Page(page_id.into()))
+    }
+
+    pub fn from_database<S: Into<String>, D: Into<String>>(api_key: S, database_id: D) -> Self {
+        Self::new(api_key, NotionSource::Database(database_id.into()))
+    }
+
+    pub fn with_api_key<S: Into<String>>(mut self, api_key: S) -> Self {
+        self.api_key = api_key.into();
+        self
+    }
+
+    pub fn with_base_url<S: Into<String>>(mut self, base_url: S) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn with_recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+}
+
+struct NotionClient {
+    client: Client,
+    api_key: String,
+    base_url: String,
+}
+
+#[derive(Default)]
+struct RenderedBlocks {
+    markdown: Vec<String>,
+    child_pages: Vec<String>,
+    child_databases: Vec<String>,
+}
+
+impl NotionClient {
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value, LoaderError> {
+        let (status, body) = send(async {
+            let response = request
+                .bearer_auth(&self.api_key)
+                .header("Notion-Version", NOTION_VERSION)
+                .send()
+                .await?;
+            let status = response.status();
+            Ok::<_, reqwest::Error>((status, response.json::<Value>().await?))
+        })
+        .await?;
+        if !status.is_success() {
+            return Err(LoaderError::OtherError(format!(
+                "Notion API error {}: {}",
+                status,
+                body["message"].as_str().unwrap_or_default()
+            )));
+        }
+        Ok(body)
+    }
+
+    async fn retrieve_page(&self, page_id: &str) -> Result<Value, LoaderError> {
+        let url = format!("{}/pages/{}", self.base_url, page_id);
+        self.send(self.client.get(url)).await
+    }
+
+    async fn query_database(&self, database_id: &str) -> Result<Vec<Value>, LoaderError> {
+        let url = format!("{}/databases/{}/query", self.base_url, database_id);
+        let mut results = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut body = json!({ "page_size": 100 });
+            if let Some(cursor) = &cursor {
+                body["start_cursor"] = Value::from(cursor.as_str());
+            }
+            let response = self.send(self.client.post(&url).json(&body)).await?;
+            results.extend(response["results"].as_array().cloned().unwrap_or_default());
+            match next_cursor(&response) {
+                Some(next) => cursor = Some(next),
+                None => return Ok(results),
+            }
+        }
+    }
+
+    async fn block_children(&self, block_id: &str) -> Result<Vec<Value>, LoaderError> {
+        let url = format!("{}/blocks/{}/children", self.base_url, block_id);
+        let mut results = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut query = vec![("page_size", "100".to_string())];
+            if let Some(cursor) = &cursor {
+                query.push(("start_cursor", cursor.clone()));
+            }
+            let response = self.send(self.client.get(&url).query(&query)).await?;
+            results.extend(response["results"].as_array().cloned().unwrap_or_default());
+            match next_cursor(&response) {
+                Some(next) => cursor = Some(next),
+                None => return Ok(results),
+            }
+        }
+    }
+
+    #[async_recursion]
+    async fn render_blocks(
+        &self,
+        block_id: &str,
+        depth: usize,
+        rendered: &mut RenderedBlocks,
+    ) -> Result<(), LoaderError> {
+        for block in self.block_children(block_id).await? {
+            let block_type = block["type"].as_str().unwrap_or_default();
+            let id = block["id"].as_str().unwrap_or_default().to_string();
+            match block_type {
+                "child_page" => {
+                    rendered.child_pages.push(id);
+                    continue;
+                }
+                "child_database" => {
+                    rendered.child_databases.push(id);
+                    continue;
+                }
+                _ => {}
+            }
+
+            if let Some(line) = block_to_markdown(&block) {
+                let indent = "  ".repeat(depth);
+                let line = line
+                    .lines()
+                    .map(|l| format!("{indent}{l}"))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                rendered.markdown.push(line);
+            }
+
+            if block["has_children"].as_bool().unwrap_or(false) {
+                self.render_blocks(&id, depth + 1, rendered).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn page_to_document(
+        &self,
+        page: &Value,
+    ) -> Result<(Document, RenderedBlocks), LoaderError> {
+        let page_id = page["id"].as_str().unwrap_or_default().to_string();
+        let mut rendered = RenderedBlocks::default();
+        self.render_blocks(&page_id, 0, &mut rendered).await?;
+
+        let title = page_title(page);
+        let mut content = rendered.markdown.join("\n\n");
+        if !title.is_empty() {
+            content = format!("# {title}\n\n{content}");
+        }
+
+        let mut metadata = HashMap::from([
+            ("page_id".to_string(), Value::from(page_id)),
+            ("title".to_string(), Value::from(title)),
+        ]);
+        if let Some(url) = page["url"].as_str() {
+            metadata.insert("source".to_string(), Value::from(url));
+        }
+        if let Some(edited) = page["last_edited_time"].as_str() {
+            metadata.insert("last_edited_time".to_string(), Value::from(edited));
+        }
+
+        Ok((Document::new(content).with_metadata(metadata), rendered))
+    }
+}
+
+fn next_cursor(response: &Value) -> Option<String> {
+    if response["has_more"].as_bool().unwrap_or(false) {
+        response["next_cursor"].as_str().map(String::from)
+    } else {
+        None
+    }
+}
+
+fn rich_text(value: &Value) -> String {
+    value
+        .as_array()
+        .map(|parts| {
+            parts
+                .iter()
+                .filter_map(|part| part["plain_text"].as_str())
+                .collect::<String>()
+        })
+        .unwrap_or_default()
+}
+
+fn page_title(page: &Value) -> String {
+    page["properties"]
+        .as_object()
+        .and_then(|properties| {
+            properties
+                .values()
+                .find(|property| property["type"] == "title")
+                .map(|property| rich_text(&property["title"]))
+        })
+        .unwrap_or_default()
+}
+
+fn block_to_markdown(block: &Value) -> Option<String> {
+    let block_type = block["type"].as_str()?;
+    let data = &block[block_type];
+    let text = rich_text(&data["rich_text"]);
+
+    let markdown = match block_type {
+        "paragraph" => text,
+        "heading_1" => format!("# {text}"),
+        "heading_2" => format!("## {text}"),
+        "heading_3" => format!("### {text}"),
+        "bulleted_list_item" | "toggle" => format!("- {text}"),
+        "numbered_list_item" => format!("1. {text}"),
+        "to_do" => {
+            let checked = if data["checked"].as_bool().unwrap_or(false) {
+                "x"
+            } else {
+                " "
+            };
+            format!("- [{checked}] {text}")
+        }
+        "quote" | "callout" => format!("> {text}"),
+        "code" => format!(
+            "```{}\n{}\n```",
+            data["language"].as_str().unwrap_or_default(),
+            text
+        ),
+        "equation" => format!("$${}$$", data["expression"].as_str().unwrap_or_default()),
+        "divider" => "---".to_string(),
+        "table_row" => {
+            let cells = data["cells"]
+                .as_array()
+                .map(|cells| cells.iter().map(rich_text).collect::<Vec<_>>())
+                .unwrap_or_default();
+            format!("| {} |", cells.join(" | "))
+        }
+        "bookmark" | "embed" | "link_preview" => data["url"].as_str()?.to_string(),
+        _ => return None,
+    };
+
+    if markdown.trim().is_empty() {
+        None
+    } else {
+        Some(markdown)
+    }
+}
+
+#[async_trait]
+impl Loader for NotionLoader {
+    async fn load(
+        mut self,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
+        LoaderError,
+    > {
+        let notion = NotionClient {
+            client: Client::new(),
+            api_key: self.api_key,
+            base_url: self.base_url,
+        };
+        let recursive = self.recursive;
+
+        let (mut pages, mut databases) = (VecDeque::new(), VecDeque::new());
+        match self.source {
+            NotionSource::Page(id) => pages.push_back(id),
+            NotionSource::Database(id) => databases.push_back(id),
+        }
+
+        let stream = stream! {
+            let mut visited = HashSet::new();
+            loop {
+                if let Some(database_id) = databases.pop_front() {
+                    match notion.query_database(&database_id).await {
+                        Ok(rows) => pages.extend(
+                            rows.iter()
+                                .filter_map(|row| row["id"].as_str().map(String::from)),
+                        ),
+                        Err(e) => yield Err(e),
+                    }
+                    continue;
+                }
+
+                let Some(page_id) = pages.pop_front() else {
+                    break;
+                };
+                if !visited.insert(page_id.clone()) {
+                    continue;
+                }
+
+                let page = match notion.retrieve_page(&page_id).await {
+                    Ok(page) => page,
+                    Err(e) => {
+                        yield Err(e);
+                        continue;
+                    }
+                };
+                match notion.page_to_document(&page).await {
+                    Ok((document, rendered)) => {
+                        if recursive {
+                            pages.extend(rendered.child_pages);
+                            databases.extend(rendered.child_databases);
+                        }
+                        yield Ok(document);
+                    }
+                    Err(e) => yield Err(e),
+                }
+            }
+        };
+
+        Ok(Box::pin(stream))
+    }
+
+    async fn load_and_split<TS: TextSplitter + 'static>(
+        mut self,
+        splitter: TS,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
+        LoaderError,
+    > {
+        let doc_stream = self.load().await?;
+        let stream = process_doc_stream(doc_stream, splitter).await;
+        Ok(Box::pin(stream))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+    use mockito::Matcher;
+
+    use super::*;
+
+    fn page(id: &str, title: &str) -> String {
+        json!({
+            "id": id,
+            "url": format!("https://www.notion.so/{id}"),
+            "properties": {
+                "Name": { "type": "title", "title": [{ "plain_text": title }] }
+            }
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_notion_loader() {
+        let mut server = mockito::Server::new_async().await;
+
+        server
+            .mock("GET", "/pages/root")
+            .match_header("authorization", "Bearer secret")
+            .with_body(page("root", "Handbook"))
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/blocks/root/children")
+            .match_query(Matcher::Any)
+            .with_body(
+                json!({
+                    "has_more": false,
+                    "results": [
+                        { "id": "b1", "type": "heading_1", "has_children": false,
+                          "heading_1": { "rich_text": [{ "plain_text": "Welcome" }] } },
+                        { "id": "b2", "type": "bulleted_list_item", "has_children": true,
+                          "bulleted_list_item": { "rich_text": [{ "plain_text": "Parent" }] } },
+                        { "id": "child", "type": "child_page", "has_children": true,
+                          "child_page": { "title": "Child" } }
+                    ]
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/blocks/b2/children")
+            .match_query(Matcher::Any)
+            .with_body(
+                json!({
+                    "has_more": false,
+                    "results": [
+                        { "id": "b3", "type": "to_do", "has_children": false,
+                          "to_do": { "checked": true, "rich_text": [{ "plain_text": "Nested" }] } }
+                    ]
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/pages/child")
+            .with_body(page("child", "Child"))
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/blocks/child/children")
+            .match_query(Matcher::Any)
+            .with_body(
+                json!({
+                    "has_more": false,
+                    "results": [
+                        { "id": "b4", "type": "paragraph", "has_children": false,
+                          "paragraph": { "rich_text": [{ "plain_text": "Child content" }] } }
+                    ]
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let loader = NotionLoader::from_page("secret", "root").with_base_url(server.url());
+        let documents = loader
+            .load()
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(documents.len(), 2);
+        assert_eq!(
+            documents[0].page_content,
+            "# Handbook\n\n# Welcome\n\n- Parent\n\n  - [x] Nested"
+        );
+        assert_eq!(
+            documents[0].metadata.get("source").unwrap(),
+            &Value::from("https://www.notion.so/root")
+        );
+        assert_eq!(documents[1].page_content, "# Child\n\nChild content");
+        assert_eq!(
+            documents[1].metadata.get("page_id").unwrap(),
+            &Value::from("child")
+        );
+    }
+}
@@ -0,0 +1,2 @@
+mod s3_loader;
+pub use s3_loader::*;
@@ -0,0 +1,269 @@
+use std::{collections::HashMap, io::Cursor, path::Path, pin::Pin};
+
+use async_stream::stream;
+use async_trait::async_trait;
+use aws_sdk_s3::{error::DisplayErrorContext, Client};
+use futures::Stream;
+use futures_util::StreamExt;
+use serde_json::Value;
+use url::Url;
+
+use crate::{
+    document_loaders::{
+        process_doc_stream, CsvLoader, HtmlLoader, Loader, LoaderError, TextLoader,
+    },
+    schemas::Document,
+    text_splitter::TextSplitter,
+};
+
+/// Lists the objects of a bucket (optionally under a prefix), downloads them and dispatches
+/// each one to the loader matching its extension: csv, html, pdf (with the `lopdf` or
+/// `pdf-extract` feature) and plain text for everything else.
+/// Every document gets `source` (`s3://bucket/key`), `bucket` and `key` metadata.
+///
+/// # Usage
+/// ```rust,ignore
+/// // Credentials are resolved through the standard AWS provider chain.
+/// let loader = S3Loader::from_env("my-bucket")
+///     .await
+///     .with_prefix("exports/2024/")
+///     .with_suffixes(vec![".md".to_string(), ".pdf".to_string()]);
+/// let documents = loader.load().await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct S3Loader {
+    client: Client,
+    bucket: String,
+    prefix: Option<String>,
+    suffixes: Option<Vec<String>>,
+}
+
+impl S3Loader {
+    pub fn new<S: Into<String>>(client: Client, bucket: S) -> Self {
+        Self {
+            client,
+            bucket: bucket.into(),
+            prefix: None,
+            suffixes: None,
+        }
+    }
+
+    /// Creates a loader whose client is configured from the standard AWS provider chain
+    /// (environment, shared config and credentials files, SSO, instance metadata, ...).
+    pub async fn from_env<S: Into<String>>(bucket: S) -> Self {
+        let config = aws_config::load_from_env().await;
+        Self::new(Client::new(&config), bucket)
+    }
+
+    pub fn with_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Only objects whose key ends with one of the suffixes are loaded.
+    pub fn with_suffixes(mut self, suffixes: Vec<String>) -> Self {
+        self.suffixes = Some(suffixes);
+        self
+    }
+
+    fn matches_suffix(&self, key: &str) -> bool {
+        self.suffixes
+            .as_ref()
+            .is_none_or(|suffixes| suffixes.iter().any(|suffix| key.ends_with(suffix)))
+    }
+
+    async fn download(&self, key: &str) -> Result<Vec<u8>, LoaderError> {
+        let object = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| LoaderError::OtherError(DisplayErrorContext(e).to_string()))?;
+        let bytes = object
+            .body
+            .collect()
+            .await
+            .map_err(|e| LoaderError::OtherError(e.to_string()))?;
+        Ok(bytes.into_bytes().to_vec())
+    }
+}
+
+/// Turns the raw bytes of a file into documents, picking the loader from the file extension.
+pub(crate) async fn load_bytes_by_extension(
+    name: &str,
+    bytes: Vec<u8>,
+    source: &str,
+) -> Result<Vec<Document>, LoaderError> {
+    let extension = Path::new(name)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let stream = match extension.as_str() {
+        "csv" => {
+            let headers = csv::Reader::from_reader(bytes.as_slice())
+                .headers()?
+                .iter()
+                .map(String::from)
+                .collect();
+            CsvLoader::new(Cursor::new(bytes), headers).load().await?
+        }
+        "html" | "htm" => {
+            let url = Url::parse(source).map_err(|e| LoaderError::OtherError(e.to_string()))?;
+            HtmlLoader::new(Cursor::new(bytes), url).load().await?
+        }
+        #[cfg(feature = "pdf-extract")]
+        "pdf" => {
+            crate::document_loaders::pdf_extract_loader::PdfExtractLoader::new(Cursor::new(bytes))?
+                .load()
+                .await?
+        }
+        #[cfg(all(feature = "lopdf", not(feature = "pdf-extract")))]
+        "pdf" => {
+            crate::document_loaders::lo_loader::LoPdfLoader::new(Cursor::new(bytes))?
+                .load()
+                .await?
+        }
+        _ => TextLoader::new(String::from_utf8(bytes)?).load().await?,
+    };
+
+    stream
+        .map(|doc| {
+            doc.map(|mut doc| {
+                doc.metadata
+                    .insert("source".to_string(), Value::from(source));
+                doc
+            })
+        })
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect()
+}
+
+#[async_trait]
+impl Loader for S3Loader {
+    async fn load(
+        mut self,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
+        LoaderError,
+    > {
+        let stream = stream! {
+            let mut pages = self
+                .client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .set_prefix(self.prefix.clone())
+                .into_paginator()
+                .send();
+
+            while let Some(page) = pages.next().await {
+                let page = match page {
+                    Ok(page) => page,
+                    Err(e) => {
+                        yield Err(LoaderError::OtherError(DisplayErrorContext(e).to_string()));
+                        break;
+                    }
+                };
+
+                for object in page.contents() {
+                    let Some(key) = object.key() else {
+                        continue;
+                    };
+                    if key.ends_with('/') || !self.matches_suffix(key) {
+                        continue;
+                    }
+
+                    let source = format!("s3://{}/{}", self.bucket, key);
+                    let documents = match self.download(key).await {
+                        Ok(bytes) => load_bytes_by_extension(key, bytes, &source).await,
+                        Err(e) => Err(e),
+                    };
+
+                    match documents {
+                        Ok(documents) => {
+                            for mut doc in documents {
+                                let metadata = HashMap::from([
+                                    ("bucket".to_string(), Value::from(self.bucket.as_str())),
+                                    ("key".to_string(), Value::from(key)),
+                                ]);
+                                doc.metadata.extend(metadata);
+                                if let Some(e_tag) = object.e_tag() {
+                                    doc.metadata
+                                        .insert("e_tag".to_string(), Value::from(e_tag));
+                                }
+                                if let Some(last_modified) = object.last_modified() {
+                                    doc.metadata.insert(
+                                        "last_modified".to_string(),
+                                        Value::from(last_modified.to_string()),
+                                    );
+                                }
+                                yield Ok(doc);
+                            }
+                        }
+                        Err(e) => yield Err(e),
+                    }
+                }
+            }
+        };
+
+        Ok(Box::pin(stream))
+    }
+
+    async fn load_and_split<TS: TextSplitter + 'static>(
+        mut self,
+        splitter: TS,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
+        LoaderError,
+    > {
+        let doc_stream = self.load().await?;
+        let stream = process_doc_stream(doc_stream, splitter).await;
+        Ok(Box::pin(stream))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_load_bytes_by_extension() {
+        let csv = "name,age\nJohn Doe,25\n".as_bytes().to_vec();
+        let documents = load_bytes_by_extension("people.csv", csv, "s3://bucket/people.csv")
+            .await
+            .unwrap();
+        assert_eq!(documents.len(), 1);
+        assert_eq!(documents[0].page_content, "name: John Doe\nage: 25\n");
+        assert_eq!(
+            documents[0].metadata.get("source").unwrap(),
+            &Value::from("s3://bucket/people.csv")
+        );
+
+        let text = "# Notes".as_bytes().to_vec();
+        let documents = load_bytes_by_extension("notes.md", text, "s3://bucket/notes.md")
+            .await
+            .unwrap();
+        assert_eq!(documents.len(), 1);
+        assert_eq!(documents[0].page_content, "# Notes");
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn s3_loader() {
+        let loader = S3Loader::from_env("my-bucket").await.with_prefix("docs/");
+
+        let documents = loader
+            .load()
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert!(!documents.is_empty());
+    }
+}
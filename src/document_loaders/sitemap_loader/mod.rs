@@ -0,0 +1,2 @@
+mod sitemap_loader;
+pub use sitemap_loader::*;
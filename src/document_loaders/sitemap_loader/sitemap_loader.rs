@@ -0,0 +1,293 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    pin::Pin,
+};
+
+use async_trait::async_trait;
+use futures::Stream;
+use regex::Regex;
+use reqwest::Client;
+use url::Url;
+
+use crate::{
+    document_loaders::{load_urls, process_doc_stream, Loader, LoaderError, WebLoaderOptions},
+    schemas::Document,
+    text_splitter::TextSplitter,
+};
+
+#[derive(Debug, Clone)]
+pub struct SitemapLoaderOptions {
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+    max_depth: usize,
+    web_options: WebLoaderOptions,
+}
+
+impl Default for SitemapLoaderOptions {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            max_depth: 3,
+            web_options: WebLoaderOptions::default(),
+        }
+    }
+}
+
+impl SitemapLoaderOptions {
+    /// Only pages whose url matches at least one include pattern are loaded.
+    /// When no include pattern is set every page is a candidate.
+    pub fn with_include(mut self, pattern: &str) -> Result<Self, LoaderError> {
+        self.include.push(parse_pattern(pattern)?);
+        Ok(self)
+    }
+
+    /// Pages whose url matches any exclude pattern are skipped.
+    pub fn with_exclude(mut self, pattern: &str) -> Result<Self, LoaderError> {
+        self.exclude.push(parse_pattern(pattern)?);
+        Ok(self)
+    }
+
+    /// Sets how many levels of nested sitemap indexes are followed.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the options used to fetch the sitemaps and the pages.
+    pub fn with_web_options(mut self, web_options: WebLoaderOptions) -> Self {
+        self.web_options = web_options;
+        self
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn web_options(&self) -> &WebLoaderOptions {
+        &self.web_options
+    }
+
+    fn is_url_allowed(&self, url: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|re| re.is_match(url)))
+            && !self.exclude.iter().any(|re| re.is_match(url))
+    }
+}
+
+fn parse_pattern(pattern: &str) -> Result<Regex, LoaderError> {
+    Regex::new(pattern)
+        .map_err(|e| LoaderError::OtherError(format!("Invalid url pattern {pattern}: {e}")))
+}
+
+/// Loads every page listed in a sitemap.xml, following sitemap indexes recursively.
+///
+/// # Usage
+/// ```rust,ignore
+/// let options = SitemapLoaderOptions::default().with_include(r"/docs/")?;
+/// let loader = SitemapLoader::new(Url::parse("https://example.com/sitemap.xml")?)
+///     .with_options(options);
+/// let documents = loader.load().await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct SitemapLoader {
+    sitemap_url: Url,
+    options: SitemapLoaderOptions,
+}
+
+impl SitemapLoader {
+    pub fn new(sitemap_url: Url) -> Self {
+        Self {
+            sitemap_url,
+            options: SitemapLoaderOptions::default(),
+        }
+    }
+
+    pub fn from_url<S: AsRef<str>>(sitemap_url: S) -> Result<Self, LoaderError> {
+        let url = Url::parse(sitemap_url.as_ref()).map_err(|e| {
+            LoaderError::OtherError(format!("Invalid url {}: {e}", sitemap_url.as_ref()))
+        })?;
+        Ok(Self::new(url))
+    }
+
+    pub fn with_options(mut self, options: SitemapLoaderOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Returns the page urls listed by the sitemap after applying the include and exclude filters.
+    pub async fn urls(&self) -> Result<Vec<Url>, LoaderError> {
+        let client = self.options.web_options.build_client()?;
+        collect_sitemap_urls(&client, self.sitemap_url.clone(), &self.options).await
+    }
+}
+
+enum Sitemap {
+    Index(Vec<String>),
+    UrlSet(Vec<String>),
+}
+
+fn parse_sitemap(content: &str) -> Sitemap {
+    let loc_re = Regex::new(r"(?s)<loc>\s*(?:<!\[CDATA\[)?(.*?)(?:\]\]>)?\s*</loc>").unwrap();
+    let locations = loc_re
+        .captures_iter(content)
+        .map(|cap| html_escape::decode_html_entities(cap[1].trim()).to_string())
+        .collect();
+
+    if content.contains("<sitemapindex") {
+        Sitemap::Index(locations)
+    } else {
+        Sitemap::UrlSet(locations)
+    }
+}
+
+async fn collect_sitemap_urls(
+    client: &Client,
+    sitemap_url: Url,
+    options: &SitemapLoaderOptions,
+) -> Result<Vec<Url>, LoaderError> {
+    let mut pending = VecDeque::from([(sitemap_url, 0)]);
+    let mut visited_sitemaps = HashSet::new();
+    let mut seen_pages = HashSet::new();
+    let mut pages = Vec::new();
+
+    while let Some((sitemap_url, depth)) = pending.pop_front() {
+        if !visited_sitemaps.insert(sitemap_url.clone()) {
+            continue;
+        }
+
+        let content = client
+            .get(sitemap_url.clone())
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        match parse_sitemap(&content) {
+            Sitemap::Index(sitemaps) => {
+                if depth >= options.max_depth {
+                    tracing::warn!("Max sitemap depth reached, skipping {}", sitemap_url);
+                    continue;
+                }
+                for location in sitemaps {
+                    match sitemap_url.join(&location) {
+                        Ok(url) => pending.push_back((url, depth + 1)),
+                        Err(e) => tracing::warn!("Invalid sitemap url {}: {}", location, e),
+                    }
+                }
+            }
+            Sitemap::UrlSet(locations) => {
+                for location in locations {
+                    if !options.is_url_allowed(&location) {
+                        continue;
+                    }
+                    match sitemap_url.join(&location) {
+                        Ok(url) => {
+                            if seen_pages.insert(url.clone()) {
+                                pages.push(url);
+                            }
+                        }
+                        Err(e) => tracing::warn!("Invalid page url {}: {}", location, e),
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(pages)
+}
+
+#[async_trait]
+impl Loader for SitemapLoader {
+    async fn load(
+        mut self,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
+        LoaderError,
+    > {
+        let urls = self.urls().await?;
+        load_urls(urls, &self.options.web_options).await
+    }
+
+    async fn load_and_split<TS: TextSplitter + 'static>(
+        mut self,
+        splitter: TS,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
+        LoaderError,
+    > {
+        let doc_stream = self.load().await?;
+        let stream = process_doc_stream(doc_stream, splitter).await;
+        Ok(Box::pin(stream))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+    use serde_json::Value;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sitemap_loader() {
+        let mut server = mockito::Server::new_async().await;
+        let base = server.url();
+
+        server
+            .mock("GET", "/sitemap.xml")
+            .with_status(200)
+            .with_body(format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <sitemap><loc>{base}/sitemap-docs.xml</loc></sitemap>
+</sitemapindex>"#
+            ))
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/sitemap-docs.xml")
+            .with_status(200)
+            .with_body(format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url><loc>{base}/docs/intro</loc><lastmod>2024-01-01</lastmod></url>
+  <url><loc>{base}/docs/changelog</loc></url>
+  <url><loc>{base}/blog/post</loc></url>
+</urlset>"#
+            ))
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/docs/intro")
+            .with_status(200)
+            .with_header("content-type", "text/plain")
+            .with_body("intro")
+            .create_async()
+            .await;
+
+        let options = SitemapLoaderOptions::default()
+            .with_include("/docs/")
+            .unwrap()
+            .with_exclude("changelog")
+            .unwrap();
+        let loader = SitemapLoader::from_url(format!("{base}/sitemap.xml"))
+            .unwrap()
+            .with_options(options);
+
+        let documents = loader
+            .load()
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(documents.len(), 1);
+        assert_eq!(documents[0].page_content, "intro");
+        assert_eq!(
+            documents[0].metadata.get("source").unwrap(),
+            &Value::from(format!("{base}/docs/intro"))
+        );
+    }
+}
@@ -0,0 +1,5 @@
+mod robots;
+pub(crate) use robots::*;
+
+mod web_loader;
+pub use web_loader::*;
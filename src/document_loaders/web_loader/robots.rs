@@ -0,0 +1,108 @@
+/// A minimal robots.txt matcher supporting `User-agent`, `Allow` and `Disallow`
+/// directives with `*` wildcards and `$` end anchors.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RobotsTxt {
+    rules: Vec<(bool, String)>,
+}
+
+impl RobotsTxt {
+    /// Parses the robots.txt `content`, keeping only the rules that apply to `user_agent`.
+    /// Rules from a group naming the user agent take precedence over the `*` group.
+    pub(crate) fn parse(content: &str, user_agent: &str) -> Self {
+        let user_agent = user_agent.to_lowercase();
+        let mut specific: Vec<(bool, String)> = Vec::new();
+        let mut wildcard: Vec<(bool, String)> = Vec::new();
+        let mut found_specific = false;
+
+        let mut group_agents: Vec<String> = Vec::new();
+        let mut in_rules = false;
+
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim();
+
+            match key.as_str() {
+                "user-agent" => {
+                    if in_rules {
+                        group_agents.clear();
+                        in_rules = false;
+                    }
+                    group_agents.push(value.to_lowercase());
+                }
+                "allow" | "disallow" => {
+                    in_rules = true;
+                    if value.is_empty() {
+                        continue;
+                    }
+                    let rule = (key == "allow", value.to_string());
+                    let matches_agent = group_agents
+                        .iter()
+                        .any(|agent| agent != "*" && user_agent.contains(agent.as_str()));
+                    if matches_agent {
+                        found_specific = true;
+                        specific.push(rule);
+                    } else if group_agents.iter().any(|agent| agent == "*") {
+                        wildcard.push(rule);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            rules: if found_specific { specific } else { wildcard },
+        }
+    }
+
+    /// Returns whether `path` may be fetched. The longest matching rule wins and
+    /// `Allow` wins ties, following the robots exclusion protocol (RFC 9309).
+    pub(crate) fn is_allowed(&self, path: &str) -> bool {
+        let mut best: Option<(usize, bool)> = None;
+        for (allow, pattern) in &self.rules {
+            if !pattern_matches(pattern, path) {
+                continue;
+            }
+            let len = pattern.len();
+            best = match best {
+                Some((best_len, best_allow))
+                    if best_len > len || (best_len == len && best_allow) =>
+                {
+                    Some((best_len, best_allow))
+                }
+                _ => Some((len, *allow)),
+            };
+        }
+        best.is_none_or(|(_, allow)| allow)
+    }
+}
+
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    for (i, part) in parts.iter().enumerate() {
+        let is_last = i == parts.len() - 1;
+        if is_last && anchored {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+
+    !anchored || rest.is_empty()
+}
@@ -0,0 +1,396 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::Cursor,
+    pin::Pin,
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use futures::{stream, Stream, StreamExt};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE},
+    Client,
+};
+use serde_json::Value;
+use url::Url;
+
+use crate::{
+    document_loaders::{html_to_document, process_doc_stream, Loader, LoaderError},
+    schemas::Document,
+    text_splitter::TextSplitter,
+};
+
+use super::RobotsTxt;
+
+#[derive(Debug, Clone)]
+pub struct WebLoaderOptions {
+    concurrency: usize,
+    timeout: Option<Duration>,
+    headers: HashMap<String, String>,
+    user_agent: String,
+    respect_robots_txt: bool,
+    keep_html: bool,
+}
+
+impl Default for WebLoaderOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            timeout: Some(Duration::from_secs(30)),
+            headers: HashMap::new(),
+            user_agent: format!("langchain-rust/{}", env!("CARGO_PKG_VERSION")),
+            respect_robots_txt: false,
+            keep_html: false,
+        }
+    }
+}
+
+impl WebLoaderOptions {
+    /// Sets how many urls are fetched at the same time.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets the timeout of each request, `None` disables it.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Adds a header sent with every request.
+    pub fn with_header<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.headers.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn with_headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    pub fn with_user_agent<S: Into<String>>(mut self, user_agent: S) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// When enabled, urls disallowed by the site's robots.txt for the configured
+    /// user agent are skipped.
+    pub fn with_respect_robots_txt(mut self, respect_robots_txt: bool) -> Self {
+        self.respect_robots_txt = respect_robots_txt;
+        self
+    }
+
+    /// Keeps HTML pages as raw HTML instead of extracting their readable text,
+    /// e.g. to split them with `HtmlSectionSplitter`.
+    pub fn with_keep_html(mut self, keep_html: bool) -> Self {
+        self.keep_html = keep_html;
+        self
+    }
+
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub fn headers(&self) -> &HashMap<String, String> {
+        &self.headers
+    }
+
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    pub fn respect_robots_txt(&self) -> bool {
+        self.respect_robots_txt
+    }
+
+    pub fn keep_html(&self) -> bool {
+        self.keep_html
+    }
+
+    pub(crate) fn build_client(&self) -> Result<Client, LoaderError> {
+        let mut headers = HeaderMap::new();
+        for (key, value) in &self.headers {
+            let name = HeaderName::from_bytes(key.as_bytes())
+                .map_err(|e| LoaderError::OtherError(format!("Invalid header {key}: {e}")))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|e| LoaderError::OtherError(format!("Invalid header {key}: {e}")))?;
+            headers.insert(name, value);
+        }
+
+        let mut builder = Client::builder()
+            .user_agent(&self.user_agent)
+            .default_headers(headers);
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        Ok(builder.build()?)
+    }
+}
+
+/// Fetches one or more urls and turns each response into a `Document`.
+/// HTML pages are cleaned with the same readability extractor as `HtmlLoader`,
+/// other content types are kept as plain text.
+///
+/// # Usage
+/// ```rust,ignore
+/// let loader = WebLoader::new(vec![Url::parse("https://example.com")?])
+///     .with_options(WebLoaderOptions::default().with_respect_robots_txt(true));
+/// let documents = loader.load().await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct WebLoader {
+    urls: Vec<Url>,
+    options: WebLoaderOptions,
+}
+
+impl WebLoader {
+    pub fn new(urls: Vec<Url>) -> Self {
+        Self {
+            urls,
+            options: WebLoaderOptions::default(),
+        }
+    }
+
+    pub fn from_urls<I, S>(urls: I) -> Result<Self, LoaderError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let urls = urls
+            .into_iter()
+            .map(|url| {
+                Url::parse(url.as_ref()).map_err(|e| {
+                    LoaderError::OtherError(format!("Invalid url {}: {e}", url.as_ref()))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(urls))
+    }
+
+    pub fn with_options(mut self, options: WebLoaderOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+/// Fetches the robots.txt of every origin in `urls` and keeps only the allowed urls.
+/// Origins whose robots.txt cannot be fetched are treated as allowing everything.
+pub(crate) async fn filter_by_robots_txt(
+    client: &Client,
+    urls: Vec<Url>,
+    user_agent: &str,
+) -> Vec<Url> {
+    let origins: HashSet<String> = urls
+        .iter()
+        .map(|url| url.origin().ascii_serialization())
+        .collect();
+
+    let mut robots = HashMap::new();
+    for origin in origins {
+        let rules = match client.get(format!("{origin}/robots.txt")).send().await {
+            Ok(response) if response.status().is_success() => response
+                .text()
+                .await
+                .map(|content| RobotsTxt::parse(&content, user_agent))
+                .unwrap_or_default(),
+            _ => RobotsTxt::default(),
+        };
+        robots.insert(origin, rules);
+    }
+
+    urls.into_iter()
+        .filter(|url| {
+            let allowed = robots
+                .get(&url.origin().ascii_serialization())
+                .is_none_or(|rules| rules.is_allowed(url.path()));
+            if !allowed {
+                tracing::info!("Skipping {} disallowed by robots.txt", url);
+            }
+            allowed
+        })
+        .collect()
+}
+
+pub(crate) async fn fetch_document(
+    client: &Client,
+    url: Url,
+    keep_html: bool,
+) -> Result<Document, LoaderError> {
+    let response = client.get(url.clone()).send().await?.error_for_status()?;
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let body = response.text().await?;
+
+    let is_html = content_type.is_empty() || content_type.contains("html");
+    let mut document = if is_html && !keep_html {
+        html_to_document(&mut Cursor::new(body.into_bytes()), &url)?
+    } else {
+        Document::new(body).with_metadata(HashMap::from([(
+            "source".to_string(),
+            Value::from(url.as_str()),
+        )]))
+    };
+    if !content_type.is_empty() {
+        document
+            .metadata
+            .insert("content_type".to_string(), Value::from(content_type));
+    }
+
+    Ok(document)
+}
+
+/// Streams the documents of `urls`, fetching up to `options.concurrency()` at a time
+/// while preserving the input order.
+pub(crate) async fn load_urls(
+    urls: Vec<Url>,
+    options: &WebLoaderOptions,
+) -> Result<Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>, LoaderError>
+{
+    let client = Arc::new(options.build_client()?);
+    let urls = if options.respect_robots_txt {
+        filter_by_robots_txt(&client, urls, &options.user_agent).await
+    } else {
+        urls
+    };
+
+    let keep_html = options.keep_html;
+    let stream = stream::iter(urls)
+        .map(move |url| {
+            let client = client.clone();
+            async move { fetch_document(&client, url, keep_html).await }
+        })
+        .buffered(options.concurrency);
+
+    Ok(Box::pin(stream))
+}
+
+#[async_trait]
+impl Loader for WebLoader {
+    async fn load(
+        mut self,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
+        LoaderError,
+    > {
+        load_urls(self.urls, &self.options).await
+    }
+
+    async fn load_and_split<TS: TextSplitter + 'static>(
+        mut self,
+        splitter: TS,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<Document, LoaderError>> + Send + 'static>>,
+        LoaderError,
+    > {
+        let doc_stream = self.load().await?;
+        let stream = process_doc_stream(doc_stream, splitter).await;
+        Ok(Box::pin(stream))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_web_loader() {
+        let mut server = mockito::Server::new_async().await;
+        let html_mock = server
+            .mock("GET", "/page")
+            .match_header("x-api-key", "secret")
+            .with_status(200)
+            .with_header("content-type", "text/html")
+            .with_body("<html><body><p>Hello world!</p></body></html>")
+            .create_async()
+            .await;
+        let text_mock = server
+            .mock("GET", "/notes.txt")
+            .with_status(200)
+            .with_header("content-type", "text/plain")
+            .with_body("plain notes")
+            .create_async()
+            .await;
+
+        let loader = WebLoader::from_urls([
+            format!("{}/page", server.url()),
+            format!("{}/notes.txt", server.url()),
+        ])
+        .unwrap()
+        .with_options(WebLoaderOptions::default().with_header("x-api-key", "secret"));
+
+        let documents = loader
+            .load()
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[0].page_content, "\nHello world!");
+        assert_eq!(
+            documents[0].metadata.get("source").unwrap(),
+            &Value::from(format!("{}/page", server.url()))
+        );
+        assert_eq!(documents[1].page_content, "plain notes");
+        assert_eq!(
+            documents[1].metadata.get("content_type").unwrap(),
+            &Value::from("text/plain")
+        );
+
+        html_mock.assert_async().await;
+        text_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_web_loader_respects_robots_txt() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/robots.txt")
+            .with_status(200)
+            .with_body("User-agent: *\nDisallow: /private\nAllow: /private/open\n")
+            .create_async()
+            .await;
+        let private_mock = server
+            .mock("GET", "/private/secret")
+            .expect(0)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/private/open")
+            .with_status(200)
+            .with_header("content-type", "text/plain")
+            .with_body("open")
+            .create_async()
+            .await;
+
+        let loader = WebLoader::from_urls([
+            format!("{}/private/secret", server.url()),
+            format!("{}/private/open", server.url()),
+        ])
+        .unwrap()
+        .with_options(WebLoaderOptions::default().with_respect_robots_txt(true));
+
+        let documents = loader
+            .load()
+            .await
+            .unwrap()
+            .map(|x| x.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(documents.len(), 1);
+        assert_eq!(documents[0].page_content, "open");
+        private_mock.assert_async().await;
+    }
+}
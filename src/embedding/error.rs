@@ -1,3 +1,4 @@
+#[cfg(feature = "openai")]
 use async_openai::error::OpenAIError;
 #[cfg(feature = "mistralai")]
 use mistralai_client::v1::error::{ApiError, ClientError};
@@ -11,6 +12,7 @@ pub enum EmbedderError {
     #[error("Network request failed: {0}")]
     RequestError(#[from] ReqwestError),
 
+    #[cfg(feature = "openai")]
     #[error("OpenAI error: {0}")]
     OpenAIError(#[from] OpenAIError),
 
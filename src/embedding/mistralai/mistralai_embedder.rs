@@ -23,7 +23,7 @@ impl MistralAIEmbedder {
 #[async_trait]
 impl Embedder for MistralAIEmbedder {
     async fn embed_documents(&self, documents: &[String]) -> Result<Vec<Vec<f64>>, EmbedderError> {
-        log::debug!("Embedding documents: {:?}", documents);
+        tracing::debug!("Embedding documents: {:?}", documents);
 
         let response = self
             .client
@@ -39,7 +39,7 @@ impl Embedder for MistralAIEmbedder {
     }
 
     async fn embed_query(&self, text: &str) -> Result<Vec<f64>, EmbedderError> {
-        log::debug!("Embedding query: {:?}", text);
+        tracing::debug!("Embedding query: {:?}", text);
 
         let response = self
             .client
@@ -8,6 +8,7 @@ pub mod ollama;
 #[cfg(feature = "ollama")]
 pub use ollama::*;
 
+#[cfg(feature = "openai")]
 pub mod openai;
 pub use error::*;
 
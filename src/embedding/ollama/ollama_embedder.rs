@@ -54,7 +54,7 @@ impl Default for OllamaEmbedder {
 #[async_trait]
 impl Embedder for OllamaEmbedder {
     async fn embed_documents(&self, documents: &[String]) -> Result<Vec<Vec<f64>>, EmbedderError> {
-        log::debug!("Embedding documents: {:?}", documents);
+        tracing::debug!("Embedding documents: {:?}", documents);
 
         let response = self
             .client
@@ -74,7 +74,7 @@ impl Embedder for OllamaEmbedder {
     }
 
     async fn embed_query(&self, text: &str) -> Result<Vec<f64>, EmbedderError> {
-        log::debug!("Embedding query: {:?}", text);
+        tracing::debug!("Embedding query: {:?}", text);
 
         let response = self
             .client
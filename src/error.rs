@@ -0,0 +1,242 @@
+use std::io;
+
+#[cfg(feature = "openai")]
+use async_openai::error::OpenAIError;
+use reqwest::{Error as ReqwestError, StatusCode};
+use serde_json::Error as SerdeJsonError;
+use thiserror::Error;
+use tokio::time::error::Elapsed;
+
+#[cfg(feature = "claude")]
+use crate::llm::AnthropicError;
+use crate::{
+    embedding::EmbedderError, language_models::LLMError, output_parsers::OutputParserError,
+    prompt::PromptError,
+};
+
+/// The error of the crate wide traits, such as `VectorStore`, `Retriever` and `Tool`.
+///
+/// Use `is_retryable` to tell transient failures, such as timeouts, rate limits or server
+/// errors, from fatal ones.
+#[derive(Error, Debug)]
+pub enum LangChainError {
+    #[error("LLM error: {0}")]
+    LLMError(#[from] LLMError),
+
+    #[error("Embedding error: {0}")]
+    EmbedderError(#[from] EmbedderError),
+
+    #[error("Vector store error: {0}")]
+    VectorStoreError(String),
+
+    #[cfg(feature = "sqlx")]
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+
+    #[error("Parsing error: {0}")]
+    OutputParserError(#[from] OutputParserError),
+
+    #[error("Prompt error: {0}")]
+    PromptError(#[from] PromptError),
+
+    #[error("Tool error: {0}")]
+    ToolError(String),
+
+    #[error("Network request failed: {0}")]
+    RequestError(#[from] ReqwestError),
+
+    #[error("URL parsing error: {0}")]
+    UrlParseError(#[from] url::ParseError),
+
+    #[error("JSON serialization/deserialization error: {0}")]
+    SerdeJsonError(#[from] SerdeJsonError),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] io::Error),
+
+    #[error("Operation timed out")]
+    Timeout(#[from] Elapsed),
+
+    #[error("Error: {0}")]
+    OtherError(String),
+}
+
+impl LangChainError {
+    /// Whether the failure is transient, so the operation may succeed if retried.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            LangChainError::LLMError(e) => e.is_retryable(),
+            LangChainError::EmbedderError(e) => match e {
+                EmbedderError::RequestError(e) => request_error_is_retryable(e),
+                #[cfg(feature = "openai")]
+                EmbedderError::OpenAIError(e) => openai_error_is_retryable(e),
+                EmbedderError::HttpError { status_code, .. } => status_is_retryable(*status_code),
+                _ => false,
+            },
+            #[cfg(feature = "sqlx")]
+            LangChainError::DatabaseError(e) => matches!(
+                e,
+                sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut | sqlx::Error::WorkerCrashed
+            ),
+            LangChainError::OutputParserError(OutputParserError::LLMError(e)) => e.is_retryable(),
+            LangChainError::RequestError(e) => request_error_is_retryable(e),
+            LangChainError::IoError(e) => io_error_is_retryable(e),
+            LangChainError::Timeout(_) => true,
+            _ => false,
+        }
+    }
+}
+
+impl From<String> for LangChainError {
+    fn from(message: String) -> Self {
+        LangChainError::OtherError(message)
+    }
+}
+
+impl From<&str> for LangChainError {
+    fn from(message: &str) -> Self {
+        LangChainError::OtherError(message.to_string())
+    }
+}
+
+#[cfg(feature = "openai")]
+impl From<OpenAIError> for LangChainError {
+    fn from(e: OpenAIError) -> Self {
+        LangChainError::LLMError(LLMError::OpenAIError(e))
+    }
+}
+
+#[cfg(feature = "qdrant")]
+impl From<qdrant_client::QdrantError> for LangChainError {
+    fn from(e: qdrant_client::QdrantError) -> Self {
+        LangChainError::VectorStoreError(e.to_string())
+    }
+}
+
+#[cfg(feature = "opensearch")]
+impl From<opensearch::Error> for LangChainError {
+    fn from(e: opensearch::Error) -> Self {
+        LangChainError::VectorStoreError(e.to_string())
+    }
+}
+
+#[cfg(feature = "surrealdb")]
+impl From<surrealdb::Error> for LangChainError {
+    fn from(e: surrealdb::Error) -> Self {
+        LangChainError::VectorStoreError(e.to_string())
+    }
+}
+
+impl LLMError {
+    /// Whether the failure is transient, so the call may succeed if retried.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            #[cfg(feature = "openai")]
+            LLMError::OpenAIError(e) => openai_error_is_retryable(e),
+            #[cfg(feature = "claude")]
+            LLMError::AnthropicError(e) => matches!(
+                e,
+                AnthropicError::RateLimitError(_)
+                    | AnthropicError::ApiError(_)
+                    | AnthropicError::OverloadedError(_)
+            ),
+            LLMError::RequestError(e) => request_error_is_retryable(e),
+            LLMError::IoError(e) => io_error_is_retryable(e),
+            LLMError::Timeout(_) => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "openai")]
+fn openai_error_is_retryable(e: &OpenAIError) -> bool {
+    match e {
+        OpenAIError::Reqwest(e) => request_error_is_retryable(e),
+        OpenAIError::ApiError(e) => [e.r#type.as_deref(), e.code.as_deref()]
+            .into_iter()
+            .flatten()
+            .any(|kind| {
+                matches!(
+                    kind,
+                    "rate_limit_exceeded" | "server_error" | "service_unavailable"
+                )
+            }),
+        _ => false,
+    }
+}
+
+fn request_error_is_retryable(e: &ReqwestError) -> bool {
+    // Connection errors are not reported apart from other errors by the wasm32 client.
+    #[cfg(not(target_arch = "wasm32"))]
+    if e.is_connect() {
+        return true;
+    }
+    e.is_timeout() || e.status().is_some_and(status_is_retryable)
+}
+
+fn status_is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+fn io_error_is_retryable(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::TimedOut
+            | io::ErrorKind::Interrupted
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::ConnectionRefused
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_retryable() {
+        let server_error = LangChainError::from(EmbedderError::HttpError {
+            status_code: StatusCode::BAD_GATEWAY,
+            error_message: String::new(),
+        });
+        assert!(server_error.is_retryable());
+
+        assert!(LangChainError::from(io::Error::from(io::ErrorKind::TimedOut)).is_retryable());
+        assert!(!LangChainError::from("Input should be a string").is_retryable());
+    }
+
+    #[cfg(feature = "openai")]
+    #[test]
+    fn test_openai_error_is_retryable() {
+        use async_openai::error::ApiError;
+
+        let rate_limited =
+            LangChainError::from(LLMError::OpenAIError(OpenAIError::ApiError(ApiError {
+                message: "Rate limit reached".to_string(),
+                r#type: Some("requests".to_string()),
+                param: None,
+                code: Some("rate_limit_exceeded".to_string()),
+            })));
+        assert!(rate_limited.is_retryable());
+    }
+
+    #[cfg(feature = "claude")]
+    #[test]
+    fn test_anthropic_error_is_retryable() {
+        let overloaded = LangChainError::from(LLMError::AnthropicError(
+            AnthropicError::OverloadedError("busy".to_string()),
+        ));
+        assert!(overloaded.is_retryable());
+
+        let unauthorized = LangChainError::from(LLMError::AnthropicError(
+            AnthropicError::AuthenticationError("bad key".to_string()),
+        ));
+        assert!(!unauthorized.is_retryable());
+    }
+
+    #[test]
+    fn test_error_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync + 'static>() {}
+        assert_send_sync::<LangChainError>();
+    }
+}
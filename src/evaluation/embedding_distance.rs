@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{embedding::embedder_trait::Embedder, semantic_router::utils::cosine_similarity};
+
+use super::{reference, EvaluationError, EvaluationRun, Evaluator, Score};
+
+/// Scores the cosine similarity of the embeddings of the prediction and the reference, so
+/// paraphrases of the reference score close to 1.
+pub struct EmbeddingDistance {
+    embedder: Arc<dyn Embedder>,
+}
+
+impl EmbeddingDistance {
+    pub fn new<E: Embedder + 'static>(embedder: E) -> Self {
+        Self {
+            embedder: Arc::new(embedder),
+        }
+    }
+}
+
+#[async_trait]
+impl Evaluator for EmbeddingDistance {
+    fn name(&self) -> String {
+        "embedding_similarity".to_string()
+    }
+
+    async fn evaluate(&self, run: &EvaluationRun) -> Result<Score, EvaluationError> {
+        let texts = [run.prediction.clone(), reference(run, self)?.to_string()];
+        let embeddings = self.embedder.embed_documents(&texts).await?;
+        Ok(Score::new(cosine_similarity(
+            &embeddings[0],
+            &embeddings[1],
+        )))
+    }
+}
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+use crate::{chain::ChainError, embedding::EmbedderError, language_models::LLMError};
+
+#[derive(Error, Debug)]
+pub enum EvaluationError {
+    #[error("Chain error: {0}")]
+    ChainError(#[from] ChainError),
+
+    #[error("LLM error: {0}")]
+    LLMError(#[from] LLMError),
+
+    #[error("Embedder error: {0}")]
+    EmbedderError(#[from] EmbedderError),
+
+    #[error("The {0} evaluator needs a reference")]
+    MissingReference(String),
+
+    #[error("Could not parse the judge response: {0}")]
+    ParsingError(String),
+}
@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::schemas::Document;
+
+use super::EvaluationError;
+
+/// One run of a chain on one example: what the evaluators score.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EvaluationRun {
+    /// The input of the chain, such as the question.
+    pub input: String,
+    /// The output of the chain.
+    pub prediction: String,
+    /// The expected output.
+    pub reference: Option<String>,
+    /// The documents the chain retrieved, in rank order.
+    pub contexts: Vec<Document>,
+    /// The IDs or contents of the documents the chain should retrieve.
+    pub relevant: Vec<String>,
+}
+
+/// A score between 0 and 1, higher being better.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Score {
+    pub value: f64,
+    /// Why the evaluator gave the score, when it explains itself.
+    pub reasoning: Option<String>,
+}
+
+impl Score {
+    pub fn new(value: f64) -> Self {
+        Self {
+            value,
+            reasoning: None,
+        }
+    }
+
+    pub fn with_reasoning<S: Into<String>>(mut self, reasoning: S) -> Self {
+        self.reasoning = Some(reasoning.into());
+        self
+    }
+}
+
+#[async_trait]
+pub trait Evaluator: Send + Sync {
+    /// The name the scores are reported under.
+    fn name(&self) -> String;
+
+    async fn evaluate(&self, run: &EvaluationRun) -> Result<Score, EvaluationError>;
+}
+
+impl<E> From<E> for Box<dyn Evaluator>
+where
+    E: Evaluator + 'static,
+{
+    fn from(evaluator: E) -> Self {
+        Box::new(evaluator)
+    }
+}
+
+/// The reference of `run`, or a `MissingReference` error naming `evaluator`.
+pub(crate) fn reference<'a>(
+    run: &'a EvaluationRun,
+    evaluator: &dyn Evaluator,
+) -> Result<&'a str, EvaluationError> {
+    run.reference
+        .as_deref()
+        .ok_or_else(|| EvaluationError::MissingReference(evaluator.name()))
+}
@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+
+use super::{reference, EvaluationError, EvaluationRun, Evaluator, Score};
+
+/// Scores 1 when the prediction equals the reference, ignoring surrounding whitespace, and 0
+/// otherwise.
+#[derive(Debug, Clone, Default)]
+pub struct ExactMatch {
+    ignore_case: bool,
+}
+
+impl ExactMatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+}
+
+#[async_trait]
+impl Evaluator for ExactMatch {
+    fn name(&self) -> String {
+        "exact_match".to_string()
+    }
+
+    async fn evaluate(&self, run: &EvaluationRun) -> Result<Score, EvaluationError> {
+        let prediction = run.prediction.trim();
+        let reference = reference(run, self)?.trim();
+        let matches = if self.ignore_case {
+            prediction.to_lowercase() == reference.to_lowercase()
+        } else {
+            prediction == reference
+        };
+        Ok(Score::new(if matches { 1.0 } else { 0.0 }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_exact_match() {
+        let run = EvaluationRun {
+            prediction: " Paris\n".to_string(),
+            reference: Some("paris".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(ExactMatch::new().evaluate(&run).await.unwrap().value, 0.0);
+        assert_eq!(
+            ExactMatch::new()
+                .with_ignore_case(true)
+                .evaluate(&run)
+                .await
+                .unwrap()
+                .value,
+            1.0
+        );
+        assert!(matches!(
+            ExactMatch::new().evaluate(&EvaluationRun::default()).await,
+            Err(EvaluationError::MissingReference(_))
+        ));
+    }
+}
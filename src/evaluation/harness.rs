@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+
+use futures::{stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    chain::{output_generation, Chain},
+    prompt::{value_to_string, PromptArgs},
+    schemas::Document,
+};
+
+use super::{EvaluationError, EvaluationRun, Evaluator, Score};
+
+/// An example of a dataset: the inputs of the chain and what it is expected to produce.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Example {
+    pub inputs: PromptArgs,
+    /// The expected output.
+    #[serde(default)]
+    pub reference: Option<String>,
+    /// The IDs or contents of the documents the chain should retrieve.
+    #[serde(default)]
+    pub relevant: Vec<String>,
+}
+
+impl Example {
+    pub fn new(inputs: PromptArgs) -> Self {
+        Self {
+            inputs,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_reference<S: Into<String>>(mut self, reference: S) -> Self {
+        self.reference = Some(reference.into());
+        self
+    }
+
+    pub fn with_relevant(mut self, relevant: Vec<String>) -> Self {
+        self.relevant = relevant;
+        self
+    }
+}
+
+/// The scores of one example.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExampleResult {
+    pub run: EvaluationRun,
+    /// The scores by evaluator name.
+    pub scores: HashMap<String, Score>,
+    /// The errors of the evaluators that could not score the example, by evaluator name.
+    pub errors: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvaluationReport {
+    /// The results of the examples, in dataset order.
+    pub results: Vec<ExampleResult>,
+    /// The mean score of each evaluator, over the examples it could score.
+    pub scores: HashMap<String, f64>,
+}
+
+/// Runs each example of a dataset through a chain and scores the output with evaluators,
+/// reporting the mean score of each evaluator, so a change to a prompt or a retriever can be
+/// compared with the previous scores.
+///
+/// The prediction is the generation of the chain, or the value of the output key when one is
+/// set. The retrieved contexts are read from the `source_documents` output, as returned by
+/// the conversational retriever chain.
+///
+/// # Usage
+/// ```rust,ignore
+/// let harness = EvaluationHarness::new(vec![
+///     ExactMatch::new().with_ignore_case(true).into(),
+///     LLMJudge::correctness(OpenAI::default()).into(),
+///     HitRate.into(),
+/// ]);
+/// let report = harness.run(&chain, &dataset).await?;
+/// assert!(report.scores["correctness"] >= 0.8);
+/// ```
+pub struct EvaluationHarness {
+    evaluators: Vec<Box<dyn Evaluator>>,
+    input_key: String,
+    output_key: Option<String>,
+    concurrency: usize,
+}
+
+impl EvaluationHarness {
+    pub fn new(evaluators: Vec<Box<dyn Evaluator>>) -> Self {
+        Self {
+            evaluators,
+            input_key: "input".to_string(),
+            output_key: None,
+            concurrency: 4,
+        }
+    }
+
+    /// Sets the input the evaluators see as the question, `input` by default.
+    pub fn with_input_key<S: Into<String>>(mut self, input_key: S) -> Self {
+        self.input_key = input_key.into();
+        self
+    }
+
+    /// Reads the prediction from this output of the chain instead of its generation.
+    pub fn with_output_key<S: Into<String>>(mut self, output_key: S) -> Self {
+        self.output_key = Some(output_key.into());
+        self
+    }
+
+    /// Sets how many examples run at once, 4 by default.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Runs the dataset, failing on the first chain error. Evaluator errors are recorded in
+    /// the example results instead, and left out of the mean scores.
+    pub async fn run(
+        &self,
+        chain: &dyn Chain,
+        dataset: &[Example],
+    ) -> Result<EvaluationReport, EvaluationError> {
+        let results: Vec<ExampleResult> = stream::iter(dataset)
+            .map(|example| self.run_example(chain, example))
+            .buffered(self.concurrency)
+            .try_collect()
+            .await?;
+
+        let mut totals: HashMap<String, (f64, usize)> = HashMap::new();
+        for result in &results {
+            for (name, score) in &result.scores {
+                let total = totals.entry(name.clone()).or_default();
+                total.0 += score.value;
+                total.1 += 1;
+            }
+        }
+        let scores = totals
+            .into_iter()
+            .map(|(name, (sum, count))| (name, sum / count as f64))
+            .collect();
+
+        Ok(EvaluationReport { results, scores })
+    }
+
+    async fn run_example(
+        &self,
+        chain: &dyn Chain,
+        example: &Example,
+    ) -> Result<ExampleResult, EvaluationError> {
+        let output = chain.execute(example.inputs.clone()).await?;
+        let run = EvaluationRun {
+            input: example
+                .inputs
+                .get(&self.input_key)
+                .map(value_to_string)
+                .unwrap_or_default(),
+            prediction: self.prediction(&output),
+            reference: example.reference.clone(),
+            contexts: output
+                .get("source_documents")
+                .and_then(|documents| {
+                    serde_json::from_value::<Vec<Document>>(documents.clone()).ok()
+                })
+                .unwrap_or_default(),
+            relevant: example.relevant.clone(),
+        };
+
+        let mut scores = HashMap::new();
+        let mut errors = HashMap::new();
+        for evaluator in &self.evaluators {
+            match evaluator.evaluate(&run).await {
+                Ok(score) => {
+                    scores.insert(evaluator.name(), score);
+                }
+                Err(e) => {
+                    tracing::warn!("Evaluator {} failed: {}", evaluator.name(), e);
+                    errors.insert(evaluator.name(), e.to_string());
+                }
+            }
+        }
+
+        Ok(ExampleResult {
+            run,
+            scores,
+            errors,
+        })
+    }
+
+    fn prediction(&self, output: &HashMap<String, Value>) -> String {
+        if let Some(output_key) = &self.output_key {
+            return output
+                .get(output_key)
+                .map(value_to_string)
+                .unwrap_or_default();
+        }
+        output_generation(output).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    use crate::{
+        chain::{ChainError, DEFAULT_OUTPUT_KEY, DEFAULT_RESULT_KEY},
+        evaluation::{ExactMatch, HitRate},
+        language_models::GenerateResult,
+        prompt_args,
+    };
+
+    use super::*;
+
+    struct CapitalChain;
+
+    #[async_trait]
+    impl Chain for CapitalChain {
+        async fn call(&self, input_variables: PromptArgs) -> Result<GenerateResult, ChainError> {
+            let country = value_to_string(&input_variables["input"]);
+            let generation = match country.as_str() {
+                "France" => "Paris",
+                "Germany" => "Berlin",
+                "Spain" => return Err(ChainError::OtherError("no answer".to_string())),
+                _ => "Unknown",
+            };
+            Ok(GenerateResult {
+                generation: generation.to_string(),
+                ..Default::default()
+            })
+        }
+
+        async fn execute(
+            &self,
+            input_variables: PromptArgs,
+        ) -> Result<HashMap<String, Value>, ChainError> {
+            let result = self.call(input_variables).await?;
+            Ok(HashMap::from([
+                (DEFAULT_OUTPUT_KEY.to_string(), json!(result.generation)),
+                (DEFAULT_RESULT_KEY.to_string(), json!(result)),
+                (
+                    "source_documents".to_string(),
+                    json!([Document::new(format!(
+                        "The capital is {}.",
+                        result.generation
+                    ))]),
+                ),
+            ]))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_evaluation_harness() {
+        let dataset = vec![
+            Example::new(prompt_args! { "input" => "France" })
+                .with_reference("Paris")
+                .with_relevant(vec!["The capital is Paris.".to_string()]),
+            Example::new(prompt_args! { "input" => "Germany" }).with_reference("Bonn"),
+            Example::new(prompt_args! { "input" => "Italy" }).with_reference("Rome"),
+        ];
+        let harness = EvaluationHarness::new(vec![ExactMatch::new().into(), HitRate.into()])
+            .with_concurrency(2);
+
+        let report = harness.run(&CapitalChain, &dataset).await.unwrap();
+        assert_eq!(report.results.len(), 3);
+        assert_eq!(report.results[1].run.prediction, "Berlin");
+        assert_eq!(report.results[0].run.input, "France");
+        assert!((report.scores["exact_match"] - 1.0 / 3.0).abs() < 1e-9);
+        // Only the first example has relevant documents to score the retrieval against.
+        assert_eq!(report.scores["hit_rate"], 1.0);
+        assert!(report.results[2].errors.contains_key("hit_rate"));
+
+        let dataset = vec![Example::new(prompt_args! { "input" => "Spain" })];
+        assert!(matches!(
+            harness.run(&CapitalChain, &dataset).await,
+            Err(EvaluationError::ChainError(_))
+        ));
+    }
+}
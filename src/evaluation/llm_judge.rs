@@ -0,0 +1,247 @@
+use async_trait::async_trait;
+use regex::Regex;
+
+use crate::language_models::llm::LLM;
+
+use super::{reference, EvaluationError, EvaluationRun, Evaluator, Score};
+
+const CORRECTNESS_TEMPLATE: &str = "You are grading the answer to a question against a reference answer.
+Question:
+--------------
+{input}
+--------------
+Reference answer:
+--------------
+{reference}
+--------------
+Answer:
+--------------
+{prediction}
+--------------
+
+Grade whether the Answer is correct: it must agree with the Reference answer, though it may be worded differently or add details that do not contradict it.
+Explain your reasoning step by step, then end with a line of the form \"Score: N\", where N is from 1 (wrong) to 5 (fully correct).";
+
+const FAITHFULNESS_TEMPLATE: &str = "You are grading whether an answer is supported by the context it was given.
+Context:
+--------------
+{context}
+--------------
+Question:
+--------------
+{input}
+--------------
+Answer:
+--------------
+{prediction}
+--------------
+
+Grade whether every claim of the Answer is supported by the Context, regardless of whether it is true otherwise.
+Explain your reasoning step by step, then end with a line of the form \"Score: N\", where N is from 1 (mostly unsupported) to 5 (fully supported).";
+
+const CRITERION_TEMPLATE: &str = "You are grading an answer to an input on the following criterion: {criterion}
+Input:
+--------------
+{input}
+--------------
+Answer:
+--------------
+{prediction}
+--------------
+
+Explain your reasoning step by step, then end with a line of the form \"Score: N\", where N is from 1 (does not meet the criterion) to 5 (fully meets it).";
+
+#[derive(Clone)]
+enum Criterion {
+    Correctness,
+    Faithfulness,
+    Custom { name: String, description: String },
+}
+
+/// Asks an LLM to grade the prediction from 1 to 5, reported as a score from 0 to 1 with the
+/// reasoning of the LLM.
+///
+/// `correctness` compares the prediction with the reference, `faithfulness` checks that the
+/// prediction is supported by the retrieved contexts, and `criterion` grades it on a criterion
+/// of your own.
+///
+/// # Usage
+/// ```rust,ignore
+/// let judge = LLMJudge::correctness(OpenAI::default());
+/// let score = judge.evaluate(&run).await?;
+/// ```
+pub struct LLMJudge {
+    llm: Box<dyn LLM>,
+    criterion: Criterion,
+}
+
+impl LLMJudge {
+    pub fn correctness<L: Into<Box<dyn LLM>>>(llm: L) -> Self {
+        Self {
+            llm: llm.into(),
+            criterion: Criterion::Correctness,
+        }
+    }
+
+    pub fn faithfulness<L: Into<Box<dyn LLM>>>(llm: L) -> Self {
+        Self {
+            llm: llm.into(),
+            criterion: Criterion::Faithfulness,
+        }
+    }
+
+    /// Grades the prediction on `description`, reporting the scores under `name`.
+    pub fn criterion<L, N, D>(llm: L, name: N, description: D) -> Self
+    where
+        L: Into<Box<dyn LLM>>,
+        N: Into<String>,
+        D: Into<String>,
+    {
+        Self {
+            llm: llm.into(),
+            criterion: Criterion::Custom {
+                name: name.into(),
+                description: description.into(),
+            },
+        }
+    }
+
+    fn prompt(&self, run: &EvaluationRun) -> Result<String, EvaluationError> {
+        let prompt = match &self.criterion {
+            Criterion::Correctness => {
+                CORRECTNESS_TEMPLATE.replace("{reference}", reference(run, self)?)
+            }
+            Criterion::Faithfulness => {
+                let context = run
+                    .contexts
+                    .iter()
+                    .map(|document| document.page_content.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+                FAITHFULNESS_TEMPLATE.replace("{context}", &context)
+            }
+            Criterion::Custom { description, .. } => {
+                CRITERION_TEMPLATE.replace("{criterion}", description)
+            }
+        };
+        Ok(prompt
+            .replace("{input}", &run.input)
+            .replace("{prediction}", &run.prediction))
+    }
+}
+
+/// Reads the last `Score: N` line of a judge response, normalized from 1-5 to 0-1.
+fn parse_score(response: &str) -> Result<f64, EvaluationError> {
+    let re = Regex::new(r"(?i)score:\s*\**\s*([1-5])").unwrap();
+    let grade: f64 = re
+        .captures_iter(response)
+        .last()
+        .and_then(|captures| captures[1].parse().ok())
+        .ok_or_else(|| EvaluationError::ParsingError(response.to_string()))?;
+    Ok((grade - 1.0) / 4.0)
+}
+
+#[async_trait]
+impl Evaluator for LLMJudge {
+    fn name(&self) -> String {
+        match &self.criterion {
+            Criterion::Correctness => "correctness".to_string(),
+            Criterion::Faithfulness => "faithfulness".to_string(),
+            Criterion::Custom { name, .. } => name.clone(),
+        }
+    }
+
+    async fn evaluate(&self, run: &EvaluationRun) -> Result<Score, EvaluationError> {
+        let response = self.llm.invoke(&self.prompt(run)?).await?;
+        Ok(Score::new(parse_score(&response)?).with_reasoning(response.trim()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        pin::Pin,
+        sync::{Arc, Mutex},
+    };
+
+    use futures::Stream;
+
+    use crate::{
+        language_models::{GenerateResult, LLMError},
+        schemas::{Document, Message, StreamData},
+    };
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct ScriptedLLM {
+        response: String,
+        prompts: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl LLM for ScriptedLLM {
+        async fn generate(&self, messages: &[Message]) -> Result<GenerateResult, LLMError> {
+            self.prompts
+                .lock()
+                .unwrap()
+                .push(messages[0].content.clone());
+            Ok(GenerateResult {
+                tokens: None,
+                generation: self.response.clone(),
+                ..Default::default()
+            })
+        }
+
+        async fn stream(
+            &self,
+            _messages: &[Message],
+        ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamData, LLMError>> + Send>>, LLMError>
+        {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_parse_score() {
+        assert_eq!(parse_score("Looks right.\nScore: 5").unwrap(), 1.0);
+        assert_eq!(
+            parse_score("score: 2 at first, but\n**Score:** 3").unwrap(),
+            0.5
+        );
+        assert!(matches!(
+            parse_score("It is correct."),
+            Err(EvaluationError::ParsingError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_llm_judge_faithfulness() {
+        let llm = ScriptedLLM {
+            response: "The context says the sky is blue.\nScore: 4".to_string(),
+            prompts: Arc::new(Mutex::new(Vec::new())),
+        };
+        let prompts = llm.prompts.clone();
+        let judge = LLMJudge::faithfulness(llm);
+        let run = EvaluationRun {
+            input: "What color is the sky?".to_string(),
+            prediction: "Blue".to_string(),
+            contexts: vec![Document::new("The sky is blue.")],
+            ..Default::default()
+        };
+
+        let score = judge.evaluate(&run).await.unwrap();
+        assert_eq!(judge.name(), "faithfulness");
+        assert_eq!(score.value, 0.75);
+        assert!(score.reasoning.unwrap().starts_with("The context says"));
+        let prompt = prompts.lock().unwrap()[0].clone();
+        assert!(prompt.contains("The sky is blue.") && prompt.contains("What color is the sky?"));
+
+        assert!(matches!(
+            LLMJudge::correctness(judge.llm.clone())
+                .evaluate(&run)
+                .await,
+            Err(EvaluationError::MissingReference(_))
+        ));
+    }
+}
@@ -0,0 +1,23 @@
+//! Evaluators scoring chain outputs and retrievals, and a harness running a dataset through a
+//! chain to aggregate their scores, so prompt and retrieval changes can be regression-tested.
+
+mod error;
+pub use error::*;
+
+mod evaluator;
+pub use evaluator::*;
+
+mod exact_match;
+pub use exact_match::*;
+
+mod embedding_distance;
+pub use embedding_distance::*;
+
+mod llm_judge;
+pub use llm_judge::*;
+
+mod retrieval;
+pub use retrieval::*;
+
+mod harness;
+pub use harness::*;
@@ -0,0 +1,92 @@
+use async_trait::async_trait;
+
+use crate::schemas::Document;
+
+use super::{EvaluationError, EvaluationRun, Evaluator, Score};
+
+/// The rank, from 1, of the first retrieved document that is relevant to the run.
+fn first_relevant_rank(run: &EvaluationRun) -> Result<Option<usize>, EvaluationError> {
+    if run.relevant.is_empty() {
+        return Err(EvaluationError::MissingReference("retrieval".to_string()));
+    }
+    let is_relevant = |document: &Document| {
+        run.relevant.iter().any(|relevant| {
+            document.id.as_ref() == Some(relevant) || &document.page_content == relevant
+        })
+    };
+    Ok(run
+        .contexts
+        .iter()
+        .position(is_relevant)
+        .map(|position| position + 1))
+}
+
+/// Scores 1 when any retrieved document is relevant, and 0 otherwise.
+///
+/// A document is relevant when its ID or its content is one of the `relevant` entries of the
+/// run. Averaged over a dataset this is the hit rate of the retriever.
+#[derive(Debug, Clone, Default)]
+pub struct HitRate;
+
+#[async_trait]
+impl Evaluator for HitRate {
+    fn name(&self) -> String {
+        "hit_rate".to_string()
+    }
+
+    async fn evaluate(&self, run: &EvaluationRun) -> Result<Score, EvaluationError> {
+        let rank = first_relevant_rank(run)?;
+        Ok(Score::new(if rank.is_some() { 1.0 } else { 0.0 }))
+    }
+}
+
+/// Scores the reciprocal of the rank of the first relevant retrieved document, or 0 when none
+/// is relevant. Averaged over a dataset this is the mean reciprocal rank (MRR) of the
+/// retriever.
+#[derive(Debug, Clone, Default)]
+pub struct MeanReciprocalRank;
+
+#[async_trait]
+impl Evaluator for MeanReciprocalRank {
+    fn name(&self) -> String {
+        "mrr".to_string()
+    }
+
+    async fn evaluate(&self, run: &EvaluationRun) -> Result<Score, EvaluationError> {
+        let rank = first_relevant_rank(run)?;
+        Ok(Score::new(rank.map_or(0.0, |rank| 1.0 / rank as f64)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_retrieval_metrics() {
+        let mut run = EvaluationRun {
+            contexts: vec![
+                Document::new("Paris is in France."),
+                Document::new("Berlin is in Germany."),
+            ],
+            relevant: vec!["Berlin is in Germany.".to_string()],
+            ..Default::default()
+        };
+        run.contexts[0].id = Some("paris".to_string());
+        assert_eq!(HitRate.evaluate(&run).await.unwrap().value, 1.0);
+        assert_eq!(MeanReciprocalRank.evaluate(&run).await.unwrap().value, 0.5);
+
+        run.relevant = vec!["paris".to_string()];
+        assert_eq!(MeanReciprocalRank.evaluate(&run).await.unwrap().value, 1.0);
+
+        run.relevant = vec!["rome".to_string()];
+        assert_eq!(HitRate.evaluate(&run).await.unwrap().value, 0.0);
+        assert_eq!(MeanReciprocalRank.evaluate(&run).await.unwrap().value, 0.0);
+
+        run.relevant.clear();
+        assert!(matches!(
+            HitRate.evaluate(&run).await,
+            Err(EvaluationError::MissingReference(_))
+        ));
+    }
+}
@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+use crate::{document_loaders::LoaderError, error::LangChainError};
+
+#[derive(Error, Debug)]
+pub enum IndexingError {
+    #[error("Loader error: {0}")]
+    LoaderError(#[from] LoaderError),
+
+    #[error("Vector store error: {0}")]
+    VectorStoreError(#[from] LangChainError),
+
+    #[cfg(feature = "sqlx")]
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+
+    #[error("Error: {0}")]
+    OtherError(String),
+}
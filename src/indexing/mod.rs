@@ -0,0 +1,16 @@
+mod error;
+pub use error::*;
+
+mod pipeline;
+pub use pipeline::*;
+
+mod reader;
+pub use reader::*;
+
+mod record_manager;
+pub use record_manager::*;
+
+#[cfg(feature = "sqlx")]
+mod sql_record_manager;
+#[cfg(feature = "sqlx")]
+pub use sql_record_manager::*;
@@ -0,0 +1,581 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use futures::{pin_mut, Stream, TryStreamExt};
+use serde_json::Value;
+use tokio::io::AsyncRead;
+
+use crate::{
+    document_loaders::{Loader, LoaderError},
+    embedding::embedder_trait::Embedder,
+    prompt::value_to_string,
+    schemas::Document,
+    text_splitter::TextSplitter,
+    vectorstore::{VecStoreOptions, VectorStore},
+};
+
+use super::{record_key, split_reader, IndexingError, Record, RecordManager, DEFAULT_READ_WINDOW};
+
+/// The counts of an indexing run, reported after each batch and returned at the end.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexingStats {
+    /// The batches added to the store.
+    pub num_batches: usize,
+    /// The documents added to the store.
+    pub num_added: usize,
+    /// The documents not added because the record manager already had them.
+    pub num_skipped: usize,
+    /// The stale documents deleted from the store by the cleanup.
+    pub num_deleted: usize,
+}
+
+/// Which documents indexed by earlier runs are deleted once a run with a record manager
+/// completes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CleanupMode {
+    /// Keeps every document.
+    #[default]
+    None,
+    /// Deletes the documents of the sources indexed by the run that the run did not produce,
+    /// such as the old chunks of an edited file. Requires a source id key.
+    Incremental,
+    /// Deletes every document the run did not produce, so the store mirrors the source. Only
+    /// run it with the complete set of documents.
+    Full,
+}
+
+/// Called with the running counts each time a batch is added to the store.
+pub type ProgressCallback = Arc<dyn Fn(&IndexingStats) + Send + Sync>;
+
+/// Loads, splits, embeds and stores documents, adding batches to the store concurrently so
+/// embedding a batch overlaps with inserting the previous ones.
+///
+/// Documents are pulled from the loader only as batches complete, so at most `concurrency`
+/// batches are held in memory however large the source is. Batches are embedded by the store,
+/// with the embedder of the options when set.
+///
+/// With a `RecordManager`, documents are keyed by a hash of their content and metadata:
+/// documents already indexed are skipped, and the `CleanupMode` decides which documents of
+/// earlier runs are deleted from the store. Cleanup needs the store to support `delete`.
+///
+/// # Usage
+/// ```rust,ignore
+/// let pipeline = IndexingPipeline::new(store)
+///     .with_batch_size(128)
+///     .with_concurrency(8)
+///     .with_progress(|stats| println!("{} chunks indexed", stats.num_added));
+/// let stats = pipeline
+///     .index_documents(DirLoader::new("./docs"), RecursiveCharacterTextSplitter::default())
+///     .await?;
+///
+/// // Re-runs only embed the changed files, and delete the chunks of their old versions.
+/// let pipeline = IndexingPipeline::new(store)
+///     .with_record_manager(SqlRecordManager::new(pool))
+///     .with_cleanup(CleanupMode::Incremental)
+///     .with_source_id_key("source");
+/// ```
+pub struct IndexingPipeline {
+    store: Box<dyn VectorStore>,
+    options: VecStoreOptions,
+    batch_size: usize,
+    concurrency: usize,
+    progress: Option<ProgressCallback>,
+    record_manager: Option<Box<dyn RecordManager>>,
+    cleanup: CleanupMode,
+    source_id_key: Option<String>,
+    read_window: usize,
+}
+
+impl IndexingPipeline {
+    pub fn new<V: Into<Box<dyn VectorStore>>>(store: V) -> Self {
+        Self {
+            store: store.into(),
+            options: VecStoreOptions::default(),
+            batch_size: 64,
+            concurrency: 4,
+            progress: None,
+            record_manager: None,
+            cleanup: CleanupMode::None,
+            source_id_key: None,
+            read_window: DEFAULT_READ_WINDOW,
+        }
+    }
+
+    pub fn with_options(mut self, options: VecStoreOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn with_embedder<E: Embedder + 'static>(mut self, embedder: E) -> Self {
+        self.options = self.options.with_embedder(embedder);
+        self
+    }
+
+    /// Sets how many documents are embedded and added to the store at once.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Sets how many batches are added to the store at the same time.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    pub fn with_progress<F>(mut self, progress: F) -> Self
+    where
+        F: Fn(&IndexingStats) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    pub fn with_record_manager<R: RecordManager + 'static>(mut self, record_manager: R) -> Self {
+        self.record_manager = Some(Box::new(record_manager));
+        self
+    }
+
+    pub fn with_cleanup(mut self, cleanup: CleanupMode) -> Self {
+        self.cleanup = cleanup;
+        self
+    }
+
+    /// Sets the metadata key holding the source of each document, such as a file path.
+    pub fn with_source_id_key<S: Into<String>>(mut self, source_id_key: S) -> Self {
+        self.source_id_key = Some(source_id_key.into());
+        self
+    }
+
+    /// Sets the bytes of text `index_reader` splits at once, 64 KiB by default.
+    pub fn with_read_window(mut self, read_window: usize) -> Self {
+        self.read_window = read_window;
+        self
+    }
+
+    /// Loads the documents of `loader`, splits them with `splitter` and adds the chunks to
+    /// the store.
+    pub async fn index_documents<L, TS>(
+        &self,
+        loader: L,
+        splitter: TS,
+    ) -> Result<IndexingStats, IndexingError>
+    where
+        L: Loader,
+        TS: TextSplitter + 'static,
+    {
+        let documents = loader.load_and_split(splitter).await?;
+        self.index_stream(documents).await
+    }
+
+    /// Splits the text of `reader` with `splitter` and adds the chunks to the store as they are
+    /// read, so a multi-gigabyte file is indexed without being loaded in memory. See
+    /// `split_reader` for the metadata of the chunks.
+    pub async fn index_reader<R, TS>(
+        &self,
+        reader: R,
+        splitter: TS,
+        metadata: HashMap<String, Value>,
+    ) -> Result<IndexingStats, IndexingError>
+    where
+        R: AsyncRead + Unpin + Send,
+        TS: TextSplitter,
+    {
+        self.index_stream(split_reader(reader, splitter, metadata, self.read_window))
+            .await
+    }
+
+    /// Adds the documents of `documents` to the store. Stops at the first error of the
+    /// stream or the store, leaving the batches already added in the store and skipping the
+    /// cleanup.
+    pub async fn index_stream<S>(&self, documents: S) -> Result<IndexingStats, IndexingError>
+    where
+        S: Stream<Item = Result<Document, LoaderError>> + Send,
+    {
+        if self.cleanup != CleanupMode::None && self.record_manager.is_none() {
+            return Err(IndexingError::OtherError(
+                "cleanup requires a record manager".to_string(),
+            ));
+        }
+        if self.cleanup == CleanupMode::Incremental && self.source_id_key.is_none() {
+            return Err(IndexingError::OtherError(
+                "incremental cleanup requires a source id key".to_string(),
+            ));
+        }
+
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_micros() as i64)
+            .unwrap_or_default();
+        let group_ids = Mutex::new(HashSet::new());
+
+        let batches = documents
+            .map_err(IndexingError::from)
+            .try_chunks(self.batch_size)
+            .map_err(|e| e.1);
+
+        let mut stats = IndexingStats::default();
+        {
+            let added = batches
+                .map_ok(|batch| self.add_batch(batch, started_at, &group_ids))
+                .try_buffer_unordered(self.concurrency);
+            pin_mut!(added);
+
+            while let Some(batch_stats) = added.try_next().await? {
+                stats.num_batches += 1;
+                stats.num_added += batch_stats.num_added;
+                stats.num_skipped += batch_stats.num_skipped;
+                if let Some(progress) = &self.progress {
+                    progress(&stats);
+                }
+            }
+        }
+
+        let group_ids: Vec<String> = group_ids.into_inner().unwrap().into_iter().collect();
+        stats.num_deleted = match self.cleanup {
+            CleanupMode::None => 0,
+            CleanupMode::Incremental => self.clean_up(started_at, Some(&group_ids)).await?,
+            CleanupMode::Full => self.clean_up(started_at, None).await?,
+        };
+        Ok(stats)
+    }
+
+    /// Adds the documents of a batch the record manager does not have, recording the sources
+    /// of the batch in `group_ids`.
+    async fn add_batch(
+        &self,
+        batch: Vec<Document>,
+        started_at: i64,
+        group_ids: &Mutex<HashSet<String>>,
+    ) -> Result<IndexingStats, IndexingError> {
+        let Some(record_manager) = &self.record_manager else {
+            self.store.add_documents(&batch, &self.options).await?;
+            return Ok(IndexingStats {
+                num_added: batch.len(),
+                ..Default::default()
+            });
+        };
+
+        let batch_len = batch.len();
+        let mut seen = HashSet::new();
+        let mut keys = Vec::with_capacity(batch_len);
+        let mut documents = Vec::with_capacity(batch_len);
+        let mut batch_group_ids = Vec::with_capacity(batch_len);
+        for document in batch {
+            let key = record_key(&document);
+            if !seen.insert(key.clone()) {
+                continue;
+            }
+            batch_group_ids.push(self.group_id(&document)?);
+            keys.push(key);
+            documents.push(document);
+        }
+        group_ids
+            .lock()
+            .unwrap()
+            .extend(batch_group_ids.iter().flatten().cloned());
+        let num_duplicates = batch_len - keys.len();
+
+        let exists = record_manager.exists(&keys).await?;
+        let mut existing_keys = Vec::new();
+        let mut new_records = Vec::new();
+        let mut new_documents = Vec::new();
+        for (((key, group_id), document), exists) in keys
+            .into_iter()
+            .zip(batch_group_ids)
+            .zip(documents)
+            .zip(exists)
+        {
+            if exists {
+                existing_keys.push(key);
+            } else {
+                new_records.push(Record {
+                    key,
+                    id: String::new(),
+                    group_id,
+                });
+                new_documents.push(document);
+            }
+        }
+
+        if !new_documents.is_empty() {
+            let ids = self
+                .store
+                .add_documents(&new_documents, &self.options)
+                .await?;
+            for (record, id) in new_records.iter_mut().zip(ids) {
+                record.id = id;
+            }
+            record_manager.update(&new_records, started_at).await?;
+        }
+        record_manager.touch(&existing_keys, started_at).await?;
+
+        Ok(IndexingStats {
+            num_added: new_documents.len(),
+            num_skipped: existing_keys.len() + num_duplicates,
+            ..Default::default()
+        })
+    }
+
+    fn group_id(&self, document: &Document) -> Result<Option<String>, IndexingError> {
+        let Some(source_id_key) = &self.source_id_key else {
+            return Ok(None);
+        };
+        match document.metadata.get(source_id_key) {
+            Some(source_id) => Ok(Some(value_to_string(source_id))),
+            None if self.cleanup == CleanupMode::Incremental => Err(IndexingError::OtherError(
+                format!("document has no `{}` metadata", source_id_key),
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Deletes the documents not indexed since `started_at`, in `group_ids` when given, and
+    /// returns how many were deleted.
+    async fn clean_up(
+        &self,
+        started_at: i64,
+        group_ids: Option<&[String]>,
+    ) -> Result<usize, IndexingError> {
+        let Some(record_manager) = &self.record_manager else {
+            return Ok(0);
+        };
+        let stale = record_manager.list(started_at, group_ids).await?;
+        if stale.is_empty() {
+            return Ok(0);
+        }
+        let ids: Vec<String> = stale.iter().map(|record| record.id.clone()).collect();
+        self.store.delete(&ids, &self.options).await?;
+        let keys: Vec<String> = stale.into_iter().map(|record| record.key).collect();
+        record_manager.delete(&keys).await?;
+        Ok(keys.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+    use futures::stream;
+
+    use crate::{
+        error::LangChainError,
+        indexing::InMemoryRecordManager,
+        text_splitter::{RecursiveCharacterSplitterOptions, RecursiveCharacterTextSplitter},
+    };
+
+    use super::*;
+
+    /// Records the documents it is given, tracking how many batches are added at once.
+    #[derive(Default)]
+    struct RecordingStore {
+        documents: Arc<Mutex<Vec<Document>>>,
+        in_flight: Arc<AtomicUsize>,
+        max_in_flight: Arc<AtomicUsize>,
+        next_id: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl VectorStore for RecordingStore {
+        async fn add_documents(
+            &self,
+            docs: &[Document],
+            _opt: &VecStoreOptions,
+        ) -> Result<Vec<String>, LangChainError> {
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            let documents: Vec<Document> = docs
+                .iter()
+                .map(|doc| {
+                    let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+                    doc.clone().with_id(id.to_string())
+                })
+                .collect();
+            let ids = documents.iter().filter_map(|doc| doc.id.clone()).collect();
+            self.documents.lock().unwrap().extend(documents);
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(ids)
+        }
+
+        async fn delete(
+            &self,
+            ids: &[String],
+            _opt: &VecStoreOptions,
+        ) -> Result<(), LangChainError> {
+            self.documents
+                .lock()
+                .unwrap()
+                .retain(|doc| !doc.id.as_ref().is_some_and(|id| ids.contains(id)));
+            Ok(())
+        }
+
+        async fn similarity_search(
+            &self,
+            _query: &str,
+            _limit: usize,
+            _opt: &VecStoreOptions,
+        ) -> Result<Vec<Document>, LangChainError> {
+            Ok(vec![])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_index_stream() {
+        let store = RecordingStore::default();
+        let documents = store.documents.clone();
+        let max_in_flight = store.max_in_flight.clone();
+        let reports = Arc::new(AtomicUsize::new(0));
+        let progress_reports = reports.clone();
+
+        let pipeline = IndexingPipeline::new(store)
+            .with_batch_size(3)
+            .with_concurrency(2)
+            .with_progress(move |_| {
+                progress_reports.fetch_add(1, Ordering::SeqCst);
+            });
+        let stats = pipeline
+            .index_stream(stream::iter(
+                (0..10).map(|i| Ok(Document::new(format!("document {}", i)))),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            stats,
+            IndexingStats {
+                num_batches: 4,
+                num_added: 10,
+                ..Default::default()
+            }
+        );
+        assert_eq!(reports.load(Ordering::SeqCst), 4);
+        assert_eq!(documents.lock().unwrap().len(), 10);
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_index_stream_error() {
+        let pipeline = IndexingPipeline::new(RecordingStore::default());
+        let result = pipeline
+            .index_stream(stream::iter(vec![
+                Ok(Document::new("document")),
+                Err(LoaderError::OtherError("unreadable".to_string())),
+            ]))
+            .await;
+        assert!(matches!(result, Err(IndexingError::LoaderError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_index_reader() {
+        let store = RecordingStore::default();
+        let documents = store.documents.clone();
+        let text = "word ".repeat(2000);
+
+        let pipeline = IndexingPipeline::new(store)
+            .with_batch_size(8)
+            .with_read_window(256);
+        let stats = pipeline
+            .index_reader(
+                std::io::Cursor::new(text.clone()),
+                RecursiveCharacterTextSplitter::new(
+                    RecursiveCharacterSplitterOptions::default()
+                        .with_chunk_size(50)
+                        .with_chunk_overlap(0),
+                ),
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+
+        let documents = documents.lock().unwrap();
+        assert_eq!(stats.num_added, documents.len());
+        let words: usize = documents
+            .iter()
+            .map(|doc| doc.page_content.split_whitespace().count())
+            .sum();
+        assert_eq!(words, 2000);
+    }
+
+    fn source_documents(sources: &[(&str, &[&str])]) -> Vec<Result<Document, LoaderError>> {
+        sources
+            .iter()
+            .flat_map(|(source, contents)| {
+                contents.iter().map(move |content| {
+                    Ok(Document::new(*content).with_metadata(HashMap::from([(
+                        "source".to_string(),
+                        Value::from(*source),
+                    )])))
+                })
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_index_stream_with_record_manager() {
+        let store = RecordingStore::default();
+        let documents = store.documents.clone();
+        let contents = || {
+            let mut contents: Vec<String> = documents
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|doc| doc.page_content.clone())
+                .collect();
+            contents.sort();
+            contents
+        };
+        let mut pipeline = IndexingPipeline::new(store)
+            .with_batch_size(2)
+            .with_record_manager(InMemoryRecordManager::new())
+            .with_cleanup(CleanupMode::Incremental)
+            .with_source_id_key("source");
+
+        let stats = pipeline
+            .index_stream(stream::iter(source_documents(&[
+                ("a.txt", &["one", "two"]),
+                ("b.txt", &["three"]),
+            ])))
+            .await
+            .unwrap();
+        assert_eq!((stats.num_added, stats.num_skipped), (3, 0));
+
+        let stats = pipeline
+            .index_stream(stream::iter(source_documents(&[
+                ("a.txt", &["one", "two", "two"]),
+                ("b.txt", &["three"]),
+            ])))
+            .await
+            .unwrap();
+        assert_eq!((stats.num_added, stats.num_skipped), (0, 4));
+
+        // Editing a.txt replaces its stale chunk and leaves b.txt alone.
+        let stats = pipeline
+            .index_stream(stream::iter(source_documents(&[(
+                "a.txt",
+                &["one", "two, edited"],
+            )])))
+            .await
+            .unwrap();
+        assert_eq!(
+            (stats.num_added, stats.num_skipped, stats.num_deleted),
+            (1, 1, 1)
+        );
+        assert_eq!(contents(), vec!["one", "three", "two, edited"]);
+
+        pipeline = pipeline.with_cleanup(CleanupMode::Full);
+        let stats = pipeline
+            .index_stream(stream::iter(source_documents(&[(
+                "a.txt",
+                &["one", "two, edited"],
+            )])))
+            .await
+            .unwrap();
+        assert_eq!(stats.num_deleted, 1);
+        assert_eq!(contents(), vec!["one", "two, edited"]);
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use async_stream::try_stream;
+use futures::Stream;
+use serde_json::Value;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::{document_loaders::LoaderError, schemas::Document, text_splitter::TextSplitter};
+
+/// The bytes of text split at once by `split_reader` by default.
+pub const DEFAULT_READ_WINDOW: usize = 1 << 16;
+
+/// The bytes read at once by `split_reader`.
+const READ_BLOCK_SIZE: usize = 8 << 10;
+
+/// Splits the text read from `reader` into chunk documents as it is read, holding about
+/// `window` bytes of text at a time however large the input is.
+///
+/// The text read is split each time `window` bytes accumulate. The chunks are emitted except
+/// the last one, which may be cut short by the window and is split again with the text that
+/// follows it. The window should be several times the chunk size of the splitter.
+///
+/// Chunks carry `metadata`, with `chunk_index` and, when the chunk appears verbatim in the
+/// text, the `start_offset` and `end_offset` of the chunk in the whole input. `total_chunks`
+/// is not known while streaming and is not set.
+///
+/// # Usage
+/// ```rust,ignore
+/// let file = tokio::fs::File::open("corpus.txt").await?;
+/// let chunks = split_reader(file, splitter, metadata, DEFAULT_READ_WINDOW);
+/// ```
+pub fn split_reader<R, TS>(
+    mut reader: R,
+    splitter: TS,
+    metadata: HashMap<String, Value>,
+    window: usize,
+) -> impl Stream<Item = Result<Document, LoaderError>> + Send
+where
+    R: AsyncRead + Unpin + Send,
+    TS: TextSplitter,
+{
+    try_stream! {
+        let window = window.max(1);
+        let mut block = vec![0; READ_BLOCK_SIZE.min(window)];
+        // Bytes of a character cut by the end of the last read.
+        let mut pending: Vec<u8> = Vec::new();
+        let mut text = String::new();
+        // The byte offset of `text` in the input.
+        let mut offset = 0;
+        let mut chunk_index = 0;
+
+        loop {
+            let read = reader.read(&mut block).await?;
+            let eof = read == 0;
+            pending.extend_from_slice(&block[..read]);
+            let valid = match std::str::from_utf8(&pending) {
+                Ok(valid) => valid.len(),
+                Err(e) if e.error_len().is_none() && !eof => e.valid_up_to(),
+                Err(e) => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, e))?,
+            };
+            text.push_str(std::str::from_utf8(&pending[..valid]).unwrap_or_default());
+            pending.drain(..valid);
+
+            if !eof && text.len() < window {
+                continue;
+            }
+
+            let mut chunks = splitter.split_text(&text).await?;
+            // The last chunk is held back, to be split again with the text that follows.
+            let carried = if eof { None } else { chunks.pop() };
+            let mut search_from = 0;
+            for chunk in chunks {
+                let mut chunk_metadata = metadata.clone();
+                chunk_metadata.insert("chunk_index".to_string(), Value::from(chunk_index));
+                if let Some(start) = text[search_from..].find(&chunk) {
+                    let start = start + search_from;
+                    chunk_metadata.insert("start_offset".to_string(), Value::from(offset + start));
+                    chunk_metadata.insert(
+                        "end_offset".to_string(),
+                        Value::from(offset + start + chunk.len()),
+                    );
+                    search_from = start + text[start..].chars().next().map_or(0, char::len_utf8);
+                }
+                chunk_index += 1;
+                yield Document::new(chunk).with_metadata(chunk_metadata);
+            }
+
+            if eof {
+                break;
+            }
+            match carried.map(|carried| (text[search_from..].rfind(&carried), carried)) {
+                Some((Some(start), _)) => {
+                    let start = start + search_from;
+                    text.drain(..start);
+                    offset += start;
+                }
+                // The splitter rewrote the chunk, so its offsets are lost.
+                Some((None, carried)) => text = carried,
+                // The window held no text to split, such as only whitespace.
+                None => {
+                    offset += text.len();
+                    text.clear();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use futures::TryStreamExt;
+
+    use crate::text_splitter::{RecursiveCharacterSplitterOptions, RecursiveCharacterTextSplitter};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_split_reader() {
+        let text = (0..200)
+            .map(|i| format!("café line {}", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let splitter = RecursiveCharacterTextSplitter::new(
+            RecursiveCharacterSplitterOptions::default()
+                .with_chunk_size(40)
+                .with_chunk_overlap(0),
+        );
+        let metadata = HashMap::from([("source".to_string(), Value::from("big.txt"))]);
+
+        let documents: Vec<Document> = split_reader(
+            Cursor::new(text.clone().into_bytes()),
+            splitter,
+            metadata,
+            101,
+        )
+        .try_collect()
+        .await
+        .unwrap();
+
+        assert!(documents.len() > 10);
+        let mut end = 0;
+        for (index, document) in documents.iter().enumerate() {
+            let metadata = &document.metadata;
+            assert_eq!(metadata["source"], "big.txt");
+            assert_eq!(metadata["chunk_index"], Value::from(index));
+            let start = metadata["start_offset"].as_u64().unwrap() as usize;
+            assert!(start >= end);
+            end = metadata["end_offset"].as_u64().unwrap() as usize;
+            assert_eq!(&text[start..end], document.page_content);
+        }
+        assert_eq!(end, text.len());
+    }
+
+    #[tokio::test]
+    async fn test_split_reader_invalid_utf8() {
+        let result: Result<Vec<Document>, LoaderError> = split_reader(
+            Cursor::new(vec![b'a', 0xff, b'b']),
+            RecursiveCharacterTextSplitter::default(),
+            HashMap::new(),
+            DEFAULT_READ_WINDOW,
+        )
+        .try_collect()
+        .await;
+        assert!(matches!(result, Err(LoaderError::IOError(_))));
+    }
+}
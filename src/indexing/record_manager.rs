@@ -0,0 +1,131 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Mutex,
+};
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+
+use crate::schemas::Document;
+
+use super::IndexingError;
+
+/// A document written to a vector store by an indexing run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// The hash of the document content and metadata, see `record_key`.
+    pub key: String,
+    /// The ID the vector store returned for the document.
+    pub id: String,
+    /// The source the document came from, which incremental cleanup deletes stale records of.
+    pub group_id: Option<String>,
+}
+
+/// Tracks the documents an `IndexingPipeline` wrote to a vector store, so re-running an
+/// ingestion skips the unchanged documents and deletes the ones no longer produced.
+///
+/// Times are the microseconds since the Unix epoch at which the indexing run started.
+#[async_trait]
+pub trait RecordManager: Send + Sync {
+    /// Returns, for each key, whether a record with that key exists.
+    async fn exists(&self, keys: &[String]) -> Result<Vec<bool>, IndexingError>;
+
+    /// Inserts the records, or replaces the records with the same keys, as updated at
+    /// `updated_at`.
+    async fn update(&self, records: &[Record], updated_at: i64) -> Result<(), IndexingError>;
+
+    /// Marks the records with the given keys as updated at `updated_at`.
+    async fn touch(&self, keys: &[String], updated_at: i64) -> Result<(), IndexingError>;
+
+    /// Returns the records last updated before `before`, limited to `group_ids` when given.
+    async fn list(
+        &self,
+        before: i64,
+        group_ids: Option<&[String]>,
+    ) -> Result<Vec<Record>, IndexingError>;
+
+    /// Deletes the records with the given keys.
+    async fn delete(&self, keys: &[String]) -> Result<(), IndexingError>;
+}
+
+/// The key of a document: the SHA-256 of its content and metadata, so a document changes key
+/// whenever it changes.
+pub fn record_key(document: &Document) -> String {
+    // Sorted so the key does not depend on the order of the metadata map.
+    let metadata: BTreeMap<_, _> = document.metadata.iter().collect();
+    let mut hasher = Sha256::new();
+    hasher.update(document.page_content.as_bytes());
+    hasher.update(
+        serde_json::to_string(&metadata)
+            .unwrap_or_default()
+            .as_bytes(),
+    );
+    format!("{:x}", hasher.finalize())
+}
+
+/// Keeps the records in memory, for tests and for indexing runs within a single process.
+#[derive(Default)]
+pub struct InMemoryRecordManager {
+    records: Mutex<HashMap<String, (Record, i64)>>,
+}
+
+impl InMemoryRecordManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl RecordManager for InMemoryRecordManager {
+    async fn exists(&self, keys: &[String]) -> Result<Vec<bool>, IndexingError> {
+        let records = self.records.lock().unwrap();
+        Ok(keys.iter().map(|key| records.contains_key(key)).collect())
+    }
+
+    async fn update(&self, records: &[Record], updated_at: i64) -> Result<(), IndexingError> {
+        let mut stored = self.records.lock().unwrap();
+        for record in records {
+            stored.insert(record.key.clone(), (record.clone(), updated_at));
+        }
+        Ok(())
+    }
+
+    async fn touch(&self, keys: &[String], updated_at: i64) -> Result<(), IndexingError> {
+        let mut records = self.records.lock().unwrap();
+        for key in keys {
+            if let Some((_, record_updated_at)) = records.get_mut(key) {
+                *record_updated_at = updated_at;
+            }
+        }
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        before: i64,
+        group_ids: Option<&[String]>,
+    ) -> Result<Vec<Record>, IndexingError> {
+        let records = self.records.lock().unwrap();
+        Ok(records
+            .values()
+            .filter(|(record, updated_at)| {
+                *updated_at < before
+                    && group_ids.is_none_or(|group_ids| {
+                        record
+                            .group_id
+                            .as_ref()
+                            .is_some_and(|group_id| group_ids.contains(group_id))
+                    })
+            })
+            .map(|(record, _)| record.clone())
+            .collect())
+    }
+
+    async fn delete(&self, keys: &[String]) -> Result<(), IndexingError> {
+        let mut records = self.records.lock().unwrap();
+        for key in keys {
+            records.remove(key);
+        }
+        Ok(())
+    }
+}
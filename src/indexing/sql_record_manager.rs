@@ -0,0 +1,320 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use sqlx::{Pool, Postgres, Row, Sqlite};
+
+use super::{IndexingError, Record, RecordManager};
+
+/// The database the records are kept in.
+#[derive(Clone)]
+pub enum RecordDatabase {
+    Postgres(Pool<Postgres>),
+    Sqlite(Pool<Sqlite>),
+}
+
+impl From<Pool<Postgres>> for RecordDatabase {
+    fn from(pool: Pool<Postgres>) -> Self {
+        RecordDatabase::Postgres(pool)
+    }
+}
+
+impl From<Pool<Sqlite>> for RecordDatabase {
+    fn from(pool: Pool<Sqlite>) -> Self {
+        RecordDatabase::Sqlite(pool)
+    }
+}
+
+enum Param {
+    Text(String),
+    OptionalText(Option<String>),
+    Integer(i64),
+}
+
+/// Keeps the records in a Postgres or SQLite table, so incremental indexing works across
+/// processes and restarts.
+///
+/// Records are scoped by a namespace, so one table can track several vector stores or
+/// collections.
+///
+/// # Usage
+/// ```rust,ignore
+/// let pool = sqlx::SqlitePool::connect("sqlite://records.db").await?;
+/// let record_manager = SqlRecordManager::new(pool).with_namespace("docs");
+/// record_manager.initialize().await?;
+/// ```
+pub struct SqlRecordManager {
+    database: RecordDatabase,
+    table: String,
+    namespace: String,
+}
+
+impl SqlRecordManager {
+    pub fn new<D: Into<RecordDatabase>>(database: D) -> Self {
+        Self {
+            database: database.into(),
+            table: "langchain_record_manager".to_string(),
+            namespace: "default".to_string(),
+        }
+    }
+
+    pub fn with_table<S: Into<String>>(mut self, table: S) -> Self {
+        self.table = table.into();
+        self
+    }
+
+    pub fn with_namespace<S: Into<String>>(mut self, namespace: S) -> Self {
+        self.namespace = namespace.into();
+        self
+    }
+
+    /// Creates the records table if it does not exist.
+    pub async fn initialize(&self) -> Result<(), IndexingError> {
+        let query = format!(
+            r#"
+            CREATE TABLE IF NOT EXISTS {}
+            (
+              namespace TEXT NOT NULL,
+              key TEXT NOT NULL,
+              id TEXT NOT NULL,
+              group_id TEXT,
+              updated_at BIGINT NOT NULL,
+              PRIMARY KEY (namespace, key)
+            )
+            "#,
+            self.table
+        );
+        self.execute(&query, Vec::new()).await
+    }
+
+    /// The placeholders of `count` parameters following the first `offset` ones.
+    fn placeholders(count: usize, offset: usize) -> String {
+        (0..count)
+            .map(|i| format!("${}", i + offset + 1))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    async fn execute(&self, query: &str, params: Vec<Param>) -> Result<(), IndexingError> {
+        match &self.database {
+            RecordDatabase::Postgres(pool) => {
+                let mut query = sqlx::query(query);
+                for param in params {
+                    query = match param {
+                        Param::Text(value) => query.bind(value),
+                        Param::OptionalText(value) => query.bind(value),
+                        Param::Integer(value) => query.bind(value),
+                    };
+                }
+                query.execute(pool).await?;
+            }
+            RecordDatabase::Sqlite(pool) => {
+                let mut query = sqlx::query(query);
+                for param in params {
+                    query = match param {
+                        Param::Text(value) => query.bind(value),
+                        Param::OptionalText(value) => query.bind(value),
+                        Param::Integer(value) => query.bind(value),
+                    };
+                }
+                query.execute(pool).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs a query selecting `key, id, group_id`.
+    async fn fetch(&self, query: &str, params: Vec<Param>) -> Result<Vec<Record>, IndexingError> {
+        let records = match &self.database {
+            RecordDatabase::Postgres(pool) => {
+                let mut query = sqlx::query(query);
+                for param in params {
+                    query = match param {
+                        Param::Text(value) => query.bind(value),
+                        Param::OptionalText(value) => query.bind(value),
+                        Param::Integer(value) => query.bind(value),
+                    };
+                }
+                query
+                    .fetch_all(pool)
+                    .await?
+                    .iter()
+                    .map(|row| {
+                        Ok(Record {
+                            key: row.try_get(0)?,
+                            id: row.try_get(1)?,
+                            group_id: row.try_get(2)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, sqlx::Error>>()?
+            }
+            RecordDatabase::Sqlite(pool) => {
+                let mut query = sqlx::query(query);
+                for param in params {
+                    query = match param {
+                        Param::Text(value) => query.bind(value),
+                        Param::OptionalText(value) => query.bind(value),
+                        Param::Integer(value) => query.bind(value),
+                    };
+                }
+                query
+                    .fetch_all(pool)
+                    .await?
+                    .iter()
+                    .map(|row| {
+                        Ok(Record {
+                            key: row.try_get(0)?,
+                            id: row.try_get(1)?,
+                            group_id: row.try_get(2)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, sqlx::Error>>()?
+            }
+        };
+        Ok(records)
+    }
+
+    fn namespaced(&self, keys: &[String]) -> Vec<Param> {
+        std::iter::once(Param::Text(self.namespace.clone()))
+            .chain(keys.iter().cloned().map(Param::Text))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl RecordManager for SqlRecordManager {
+    async fn exists(&self, keys: &[String]) -> Result<Vec<bool>, IndexingError> {
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+        let query = format!(
+            "SELECT key, id, group_id FROM {} WHERE namespace = $1 AND key IN ({})",
+            self.table,
+            Self::placeholders(keys.len(), 1)
+        );
+        let found: HashSet<String> = self
+            .fetch(&query, self.namespaced(keys))
+            .await?
+            .into_iter()
+            .map(|record| record.key)
+            .collect();
+        Ok(keys.iter().map(|key| found.contains(key)).collect())
+    }
+
+    async fn update(&self, records: &[Record], updated_at: i64) -> Result<(), IndexingError> {
+        let query = format!(
+            "INSERT INTO {} (namespace, key, id, group_id, updated_at) VALUES ($1, $2, $3, $4, $5) \
+             ON CONFLICT (namespace, key) DO UPDATE SET id = excluded.id, \
+             group_id = excluded.group_id, updated_at = excluded.updated_at",
+            self.table
+        );
+        for record in records {
+            let params = vec![
+                Param::Text(self.namespace.clone()),
+                Param::Text(record.key.clone()),
+                Param::Text(record.id.clone()),
+                Param::OptionalText(record.group_id.clone()),
+                Param::Integer(updated_at),
+            ];
+            self.execute(&query, params).await?;
+        }
+        Ok(())
+    }
+
+    async fn touch(&self, keys: &[String], updated_at: i64) -> Result<(), IndexingError> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+        let query = format!(
+            "UPDATE {} SET updated_at = $1 WHERE namespace = $2 AND key IN ({})",
+            self.table,
+            Self::placeholders(keys.len(), 2)
+        );
+        let mut params = vec![Param::Integer(updated_at)];
+        params.extend(self.namespaced(keys));
+        self.execute(&query, params).await
+    }
+
+    async fn list(
+        &self,
+        before: i64,
+        group_ids: Option<&[String]>,
+    ) -> Result<Vec<Record>, IndexingError> {
+        let mut query = format!(
+            "SELECT key, id, group_id FROM {} WHERE namespace = $1 AND updated_at < $2",
+            self.table
+        );
+        let mut params = vec![Param::Text(self.namespace.clone()), Param::Integer(before)];
+        if let Some(group_ids) = group_ids {
+            if group_ids.is_empty() {
+                return Ok(Vec::new());
+            }
+            query.push_str(&format!(
+                " AND group_id IN ({})",
+                Self::placeholders(group_ids.len(), 2)
+            ));
+            params.extend(group_ids.iter().cloned().map(Param::Text));
+        }
+        self.fetch(&query, params).await
+    }
+
+    async fn delete(&self, keys: &[String]) -> Result<(), IndexingError> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+        let query = format!(
+            "DELETE FROM {} WHERE namespace = $1 AND key IN ({})",
+            self.table,
+            Self::placeholders(keys.len(), 1)
+        );
+        self.execute(&query, self.namespaced(keys)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sql_record_manager_sqlite() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let record_manager = SqlRecordManager::new(pool);
+        record_manager.initialize().await.unwrap();
+
+        let record = |key: &str, group_id: &str| Record {
+            key: key.to_string(),
+            id: format!("id-{}", key),
+            group_id: Some(group_id.to_string()),
+        };
+        record_manager
+            .update(&[record("a", "one.txt"), record("b", "two.txt")], 1)
+            .await
+            .unwrap();
+        record_manager.touch(&["a".to_string()], 2).await.unwrap();
+
+        assert_eq!(
+            record_manager
+                .exists(&["a".to_string(), "c".to_string()])
+                .await
+                .unwrap(),
+            vec![true, false]
+        );
+        assert_eq!(
+            record_manager.list(2, None).await.unwrap(),
+            vec![record("b", "two.txt")]
+        );
+        assert!(record_manager
+            .list(2, Some(&["one.txt".to_string()]))
+            .await
+            .unwrap()
+            .is_empty());
+
+        record_manager.delete(&["b".to_string()]).await.unwrap();
+        assert!(record_manager.list(2, None).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,88 @@
+use std::{future::Future, pin::Pin};
+
+use futures::{Stream, StreamExt};
+pub use tokio_util::sync::CancellationToken;
+
+use super::LLMError;
+
+/// Runs `future` until it completes or `token` is cancelled. On cancellation the future is
+/// dropped, which aborts the request it has in flight.
+pub(crate) async fn run_cancellable<F, T>(
+    token: Option<&CancellationToken>,
+    future: F,
+) -> Result<T, LLMError>
+where
+    F: Future<Output = Result<T, LLMError>>,
+{
+    match token {
+        Some(token) => token
+            .run_until_cancelled(future)
+            .await
+            .unwrap_or(Err(LLMError::Cancelled)),
+        None => future.await,
+    }
+}
+
+/// Ends `stream` with a `LLMError::Cancelled` error once `token` is cancelled, dropping the
+/// stream and the connection it reads from.
+pub(crate) fn cancellable_stream<T: Send + 'static>(
+    stream: Pin<Box<dyn Stream<Item = Result<T, LLMError>> + Send>>,
+    token: Option<CancellationToken>,
+) -> Pin<Box<dyn Stream<Item = Result<T, LLMError>> + Send>> {
+    let Some(token) = token else {
+        return stream;
+    };
+    Box::pin(futures::stream::unfold(Some(stream), move |stream| {
+        let token = token.clone();
+        async move {
+            let mut stream = stream?;
+            match token.run_until_cancelled(stream.next()).await {
+                Some(item) => item.map(|item| (item, Some(stream))),
+                None => Some((Err(LLMError::Cancelled), None)),
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_cancellable() {
+        let token = CancellationToken::new();
+        let result = run_cancellable(Some(&token), async { Ok::<_, LLMError>(1) }).await;
+        assert_eq!(result.unwrap(), 1);
+
+        let cancel = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            cancel.cancel();
+        });
+        let result = run_cancellable(Some(&token), async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok::<_, LLMError>(1)
+        })
+        .await;
+        assert!(matches!(result, Err(LLMError::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn test_cancellable_stream() {
+        let token = CancellationToken::new();
+        let stream = futures::stream::iter(vec![Ok::<_, LLMError>(1), Ok(2)])
+            .chain(futures::stream::pending());
+        let mut stream = cancellable_stream(Box::pin(stream), Some(token.clone()));
+
+        assert_eq!(stream.next().await.unwrap().unwrap(), 1);
+        assert_eq!(stream.next().await.unwrap().unwrap(), 2);
+        token.cancel();
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(LLMError::Cancelled))
+        ));
+        assert!(stream.next().await.is_none());
+    }
+}
@@ -1,3 +1,4 @@
+#[cfg(feature = "openai")]
 use async_openai::error::OpenAIError;
 #[cfg(feature = "ollama")]
 use ollama_rs::error::OllamaError;
@@ -6,13 +7,16 @@ use serde_json::Error as SerdeJsonError;
 use thiserror::Error;
 use tokio::time::error::Elapsed;
 
+#[cfg(feature = "claude")]
 use crate::llm::AnthropicError;
 
 #[derive(Error, Debug)]
 pub enum LLMError {
+    #[cfg(feature = "openai")]
     #[error("OpenAI error: {0}")]
     OpenAIError(#[from] OpenAIError),
 
+    #[cfg(feature = "claude")]
     #[error("Anthropic error: {0}")]
     AnthropicError(#[from] AnthropicError),
 
@@ -32,6 +36,9 @@ pub enum LLMError {
     #[error("Operation timed out")]
     Timeout(#[from] Elapsed),
 
+    #[error("Operation cancelled")]
+    Cancelled,
+
     #[error("Invalid URL: {0}")]
     InvalidUrl(String),
 
@@ -1,9 +1,15 @@
-use std::pin::Pin;
+use std::{pin::Pin, time::Duration};
 
 use async_trait::async_trait;
 use futures::Stream;
 
-use crate::schemas::{Message, StreamData};
+use crate::{
+    resilience::{RetryPolicy, WithFallbacks, WithRetry, WithTimeout},
+    schemas::{Message, StreamData},
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::platform::block_on;
 
 use super::{options::CallOptions, GenerateResult, LLMError};
 
@@ -20,6 +26,26 @@ pub trait LLM: Sync + Send + LLMClone {
         _messages: &[Message],
     ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamData, LLMError>> + Send>>, LLMError>;
 
+    /// Generates from synchronous code, blocking the thread until the generation completes.
+    ///
+    /// # Panics
+    ///
+    /// Panics when called from within an async runtime; await `generate` there.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn generate_blocking(&self, messages: &[Message]) -> Result<GenerateResult, LLMError> {
+        block_on(self.generate(messages))
+    }
+
+    /// Invokes from synchronous code, blocking the thread until the generation completes.
+    ///
+    /// # Panics
+    ///
+    /// Panics when called from within an async runtime; await `invoke` there.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn invoke_blocking(&self, prompt: &str) -> Result<String, LLMError> {
+        block_on(self.invoke(prompt))
+    }
+
     /// This is usefull when you want to create a chain and override
     /// LLM options
     fn add_options(&mut self, _options: CallOptions) {
@@ -33,6 +59,30 @@ pub trait LLM: Sync + Send + LLMClone {
             .collect::<Vec<String>>()
             .join("\n")
     }
+
+    /// Retries the calls that fail with a transient error, following `policy`.
+    fn with_retry(self, policy: RetryPolicy) -> WithRetry<Self>
+    where
+        Self: Sized,
+    {
+        WithRetry::new(self, policy)
+    }
+
+    /// Calls the `fallbacks` in order when a call fails.
+    fn with_fallbacks(self, fallbacks: Vec<Box<dyn LLM>>) -> WithFallbacks<Self, Box<dyn LLM>>
+    where
+        Self: Sized,
+    {
+        WithFallbacks::new(self, fallbacks)
+    }
+
+    /// Fails the calls that take longer than `timeout` with `LLMError::Timeout`.
+    fn with_timeout(self, timeout: Duration) -> WithTimeout<Self>
+    where
+        Self: Sized,
+    {
+        WithTimeout::new(self, timeout)
+    }
 }
 
 pub trait LLMClone {
@@ -48,6 +98,12 @@ where
     }
 }
 
+impl Clone for Box<dyn LLM> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
 impl<L> From<L> for Box<dyn LLM>
 where
     L: 'static + LLM,
@@ -8,12 +8,46 @@ pub mod options;
 mod error;
 pub use error::*;
 
+mod cancellation;
+pub use cancellation::*;
+
 //TODO: check if its this should have a data:serde::Value to save all other things, like OpenAI
 //function responses
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct GenerateResult {
     pub tokens: Option<TokenUsage>,
     pub generation: String,
+    #[serde(default, skip_serializing_if = "GenerationInfo::is_empty")]
+    pub info: GenerationInfo,
+}
+
+/// What the provider reports about a generation besides its text, to debug truncated outputs
+/// or trace an issue back to an upstream request. Fields the provider does not report are
+/// `None`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct GenerationInfo {
+    /// Why the generation stopped, as named by the provider, e.g. `stop`, `length` or
+    /// `end_turn`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finish_reason: Option<String>,
+    /// The model that actually served the request, which may be a dated version of the
+    /// requested one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_fingerprint: Option<String>,
+    /// The ID of the response, e.g. `chatcmpl-...` for OpenAI or `msg_...` for Anthropic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_id: Option<String>,
+    /// The ID of the HTTP request, from the `request-id` or `x-request-id` response header.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl GenerationInfo {
+    pub fn is_empty(&self) -> bool {
+        self == &GenerationInfo::default()
+    }
 }
 
 impl GenerateResult {
@@ -36,11 +70,24 @@ impl GenerateResult {
             map.insert("total_tokens".to_string(), tokens.total_tokens.to_string());
         }
 
+        let info = [
+            ("finish_reason", &self.info.finish_reason),
+            ("model", &self.info.model),
+            ("system_fingerprint", &self.info.system_fingerprint),
+            ("response_id", &self.info.response_id),
+            ("request_id", &self.info.request_id),
+        ];
+        for (key, value) in info {
+            if let Some(value) = value {
+                map.insert(key.to_string(), value.clone());
+            }
+        }
+
         map
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct TokenUsage {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
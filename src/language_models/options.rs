@@ -1,15 +1,20 @@
 use futures::Future;
+use serde::{Deserialize, Serialize};
 use std::{pin::Pin, sync::Arc};
 use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
 
 use crate::schemas::{FunctionCallBehavior, FunctionDefinition};
 
-#[derive(Clone)]
+/// Options of the calls to an LLM. The streaming function and the cancellation token are not
+/// serialized.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CallOptions {
     pub candidate_count: Option<usize>,
     pub max_tokens: Option<u32>,
     pub temperature: Option<f32>,
     pub stop_words: Option<Vec<String>>,
+    #[serde(skip)]
     pub streaming_func: Option<
         Arc<
             Mutex<dyn FnMut(String) -> Pin<Box<dyn Future<Output = Result<(), ()>> + Send>> + Send>,
@@ -27,6 +32,8 @@ pub struct CallOptions {
     pub functions: Option<Vec<FunctionDefinition>>,
     pub function_call_behavior: Option<FunctionCallBehavior>,
     pub stream_usage: Option<bool>,
+    #[serde(skip)]
+    pub cancellation_token: Option<CancellationToken>,
 }
 
 impl Default for CallOptions {
@@ -54,6 +61,7 @@ impl CallOptions {
             functions: None,
             function_call_behavior: None,
             stream_usage: None,
+            cancellation_token: None,
         }
     }
 
@@ -154,6 +162,13 @@ impl CallOptions {
         self
     }
 
+    /// Stops the calls once the token is cancelled, dropping the requests in flight; the
+    /// calls then fail with `LLMError::Cancelled`.
+    pub fn with_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+        self.cancellation_token = Some(cancellation_token);
+        self
+    }
+
     pub fn merge_options(&mut self, incoming_options: CallOptions) {
         // For simple scalar types wrapped in Option, prefer incoming option if it is Some
         self.candidate_count = incoming_options.candidate_count.or(self.candidate_count);
@@ -176,6 +191,9 @@ impl CallOptions {
             .function_call_behavior
             .or(self.function_call_behavior.clone());
         self.stream_usage = incoming_options.stream_usage.or(self.stream_usage);
+        self.cancellation_token = incoming_options
+            .cancellation_token
+            .or(self.cancellation_token.clone());
 
         // For `Vec<String>`, merge if both are Some; prefer incoming if only incoming is Some
         if let Some(mut new_stop_words) = incoming_options.stop_words {
@@ -202,3 +220,29 @@ impl CallOptions {
             .or_else(|| self.streaming_func.clone());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_options_serde() {
+        let options = CallOptions::new()
+            .with_temperature(0.5)
+            .with_stop_words(vec!["\n".to_string()])
+            .with_function_call_behavior(FunctionCallBehavior::Named("search".to_string()))
+            .with_streaming_func(|_| async { Ok(()) })
+            .with_cancellation_token(CancellationToken::new());
+
+        let json = serde_json::to_value(&options).unwrap();
+        let deserialized: CallOptions = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(deserialized.temperature, Some(0.5));
+        assert_eq!(
+            deserialized.function_call_behavior,
+            Some(FunctionCallBehavior::Named("search".to_string()))
+        );
+        assert!(deserialized.streaming_func.is_none());
+        assert!(deserialized.cancellation_token.is_none());
+        assert_eq!(serde_json::to_value(&deserialized).unwrap(), json);
+    }
+}
@@ -1,17 +1,25 @@
 #![allow(dead_code)]
 pub mod agent;
+pub mod callbacks;
 pub mod chain;
 pub mod document_loaders;
 pub mod embedding;
+pub mod error;
+pub mod evaluation;
+pub mod indexing;
 pub mod language_models;
 pub mod llm;
 pub mod memory;
+pub mod moderation;
 pub mod output_parsers;
+mod platform;
 pub mod prompt;
+pub mod resilience;
 pub mod schemas;
 pub mod semantic_router;
 pub mod text_splitter;
 pub mod tools;
 pub mod vectorstore;
 
+pub use schemars;
 pub use url;
@@ -1,11 +1,16 @@
 use crate::{
-    language_models::{llm::LLM, options::CallOptions, GenerateResult, LLMError, TokenUsage},
+    callbacks::{emit_llm_new_token, trace_llm, trace_llm_stream},
+    language_models::{
+        cancellable_stream, llm::LLM, options::CallOptions, run_cancellable, GenerateResult,
+        GenerationInfo, LLMError, TokenUsage,
+    },
     llm::AnthropicError,
+    platform::{send, send_stream},
     schemas::{Message, MessageType, StreamData},
 };
 use async_trait::async_trait;
 use futures::{Stream, StreamExt};
-use reqwest::Client;
+use reqwest::{header::HeaderMap, Client};
 use serde_json::Value;
 use std::{collections::HashMap, pin::Pin};
 
@@ -78,32 +83,37 @@ impl Claude {
         let is_stream = self.options.streaming_func.is_some();
 
         let payload = self.build_payload(messages, is_stream);
-        let res = client
-            .post("https://api.anthropic.com/v1/messages")
-            .header("x-api-key", &self.api_key)
-            .header("anthropic-version", self.anthropic_version.clone())
-            .header("content-type", "application/json; charset=utf-8")
-            .json(&payload)
-            .send()
-            .await?;
-        let res = match res.status().as_u16() {
-            401 => Err(LLMError::AnthropicError(
-                AnthropicError::AuthenticationError("Invalid API Key".to_string()),
-            )),
-            403 => Err(LLMError::AnthropicError(AnthropicError::PermissionError(
-                "Permission Denied".to_string(),
-            ))),
-            404 => Err(LLMError::AnthropicError(AnthropicError::NotFoundError(
-                "Not Found".to_string(),
-            ))),
-            429 => Err(LLMError::AnthropicError(AnthropicError::RateLimitError(
-                "Rate Limit Exceeded".to_string(),
-            ))),
-            503 => Err(LLMError::AnthropicError(AnthropicError::OverloadedError(
-                "Service Unavailable".to_string(),
-            ))),
-            _ => Ok(res.json::<ApiResponse>().await?),
-        }?;
+        let (request_id, res) = send(async {
+            let res = client
+                .post("https://api.anthropic.com/v1/messages")
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", self.anthropic_version.clone())
+                .header("content-type", "application/json; charset=utf-8")
+                .json(&payload)
+                .send()
+                .await?;
+            let res = match res.status().as_u16() {
+                401 => Err(LLMError::AnthropicError(
+                    AnthropicError::AuthenticationError("Invalid API Key".to_string()),
+                )),
+                403 => Err(LLMError::AnthropicError(AnthropicError::PermissionError(
+                    "Permission Denied".to_string(),
+                ))),
+                404 => Err(LLMError::AnthropicError(AnthropicError::NotFoundError(
+                    "Not Found".to_string(),
+                ))),
+                429 => Err(LLMError::AnthropicError(AnthropicError::RateLimitError(
+                    "Rate Limit Exceeded".to_string(),
+                ))),
+                503 => Err(LLMError::AnthropicError(AnthropicError::OverloadedError(
+                    "Service Unavailable".to_string(),
+                ))),
+                _ => Ok(res),
+            }?;
+            let request_id = request_id(res.headers());
+            Ok::<_, LLMError>((request_id, res.json::<ApiResponse>().await?))
+        })
+        .await?;
 
         let generation = res
             .content
@@ -117,7 +127,57 @@ impl Claude {
             total_tokens: res.usage.input_tokens + res.usage.output_tokens,
         });
 
-        Ok(GenerateResult { tokens, generation })
+        Ok(GenerateResult {
+            tokens,
+            generation,
+            info: GenerationInfo {
+                finish_reason: res.stop_reason,
+                model: Some(res.model),
+                response_id: Some(res.id),
+                request_id,
+                ..Default::default()
+            },
+        })
+    }
+
+    async fn stream_response(
+        &self,
+        messages: &[Message],
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamData, LLMError>> + Send>>, LLMError> {
+        let client = Client::new();
+        let payload = self.build_payload(messages, true);
+        let request = client
+            .post("https://api.anthropic.com/v1/messages")
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", &self.anthropic_version)
+            .header("content-type", "application/json; charset=utf-8")
+            .json(&payload)
+            .build()?;
+
+        // Instead of sending the request directly, return a stream wrapper
+        let stream = send(client.execute(request)).await?;
+        let stream = send_stream(stream.bytes_stream());
+        // Process each chunk as it arrives
+        let processed_stream = stream.then(move |result| {
+            async move {
+                match result {
+                    Ok(bytes) => {
+                        let value: Value = parse_sse_to_json(&String::from_utf8_lossy(&bytes))?;
+                        if value["type"].as_str().unwrap_or("") == "content_block_delta" {
+                            let content = value["delta"]["text"].clone();
+                            // Return StreamData based on the parsed content
+                            // TODO get tokens from the response
+                            Ok(StreamData::new(value, None, content.as_str().unwrap_or("")))
+                        } else {
+                            Ok(StreamData::new(value, None, ""))
+                        }
+                    }
+                    Err(e) => Err(LLMError::RequestError(e)),
+                }
+            }
+        });
+
+        Ok(Box::pin(processed_stream))
     }
 
     fn build_payload(&self, messages: &[Message], stream: bool) -> Payload {
@@ -148,65 +208,60 @@ impl Claude {
 #[async_trait]
 impl LLM for Claude {
     async fn generate(&self, messages: &[Message]) -> Result<GenerateResult, LLMError> {
-        match &self.options.streaming_func {
-            Some(func) => {
-                let mut complete_response = String::new();
-                let mut stream = self.stream(messages).await?;
-                while let Some(data) = stream.next().await {
-                    match data {
-                        Ok(value) => {
-                            let mut func = func.lock().await;
-                            complete_response.push_str(&value.content);
-                            let _ = func(value.content).await;
+        let token = self.options.cancellation_token.as_ref();
+        let generation = run_cancellable(token, async {
+            match &self.options.streaming_func {
+                Some(func) => {
+                    let mut complete_response = String::new();
+                    let mut info = GenerationInfo::default();
+                    let mut stream = self.stream_response(messages).await?;
+                    while let Some(data) = stream.next().await {
+                        match data {
+                            Ok(value) => {
+                                match value.value["type"].as_str() {
+                                    Some("message_start") => {
+                                        let message = &value.value["message"];
+                                        info.model = message["model"].as_str().map(String::from);
+                                        info.response_id = message["id"].as_str().map(String::from);
+                                    }
+                                    Some("message_delta") => {
+                                        info.finish_reason = value.value["delta"]["stop_reason"]
+                                            .as_str()
+                                            .map(String::from);
+                                    }
+                                    _ => {}
+                                }
+                                let mut func = func.lock().await;
+                                emit_llm_new_token(&value.content);
+                                complete_response.push_str(&value.content);
+                                let _ = func(value.content).await;
+                            }
+                            Err(e) => return Err(e),
                         }
-                        Err(e) => return Err(e),
                     }
+                    Ok(GenerateResult {
+                        generation: complete_response,
+                        info,
+                        ..Default::default()
+                    })
                 }
-                let mut generate_result = GenerateResult::default();
-                generate_result.generation = complete_response;
-                Ok(generate_result)
+                None => self.generate(messages).await,
             }
-            None => self.generate(messages).await,
-        }
+        });
+        trace_llm("Claude", &self.model, messages, generation).await
     }
     async fn stream(
         &self,
         messages: &[Message],
     ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamData, LLMError>> + Send>>, LLMError> {
-        let client = Client::new();
-        let payload = self.build_payload(messages, true);
-        let request = client
-            .post("https://api.anthropic.com/v1/messages")
-            .header("x-api-key", &self.api_key)
-            .header("anthropic-version", &self.anthropic_version)
-            .header("content-type", "application/json; charset=utf-8")
-            .json(&payload)
-            .build()?;
-
-        // Instead of sending the request directly, return a stream wrapper
-        let stream = client.execute(request).await?;
-        let stream = stream.bytes_stream();
-        // Process each chunk as it arrives
-        let processed_stream = stream.then(move |result| {
-            async move {
-                match result {
-                    Ok(bytes) => {
-                        let value: Value = parse_sse_to_json(&String::from_utf8_lossy(&bytes))?;
-                        if value["type"].as_str().unwrap_or("") == "content_block_delta" {
-                            let content = value["delta"]["text"].clone();
-                            // Return StreamData based on the parsed content
-                            // TODO get tokens from the response
-                            Ok(StreamData::new(value, None, content.as_str().unwrap_or("")))
-                        } else {
-                            Ok(StreamData::new(value, None, ""))
-                        }
-                    }
-                    Err(e) => Err(LLMError::RequestError(e)),
-                }
-            }
-        });
-
-        Ok(Box::pin(processed_stream))
+        let token = self.options.cancellation_token.as_ref();
+        let stream = run_cancellable(token, self.stream_response(messages)).await?;
+        Ok(trace_llm_stream(
+            "Claude",
+            &self.model,
+            messages,
+            cancellable_stream(stream, token.cloned()),
+        ))
     }
 
     fn add_options(&mut self, options: CallOptions) {
@@ -214,6 +269,13 @@ impl LLM for Claude {
     }
 }
 
+fn request_id(headers: &HeaderMap) -> Option<String> {
+    ["request-id", "x-request-id"]
+        .into_iter()
+        .find_map(|name| headers.get(name)?.to_str().ok())
+        .map(String::from)
+}
+
 fn parse_sse_to_json(sse_data: &str) -> Result<Value, LLMError> {
     if let Ok(json) = serde_json::from_str::<Value>(sse_data) {
         return parse_error(&json);
@@ -235,7 +297,7 @@ fn parse_sse_to_json(sse_data: &str) -> Result<Value, LLMError> {
             _ => Ok(data),
         };
     }
-    log::error!("No data field in the SSE event");
+    tracing::error!("No data field in the SSE event");
     Err(LLMError::ContentNotFound("data".to_string()))
 }
 
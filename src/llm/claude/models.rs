@@ -1,26 +1,130 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use crate::schemas::{Message, MessageType};
+use crate::schemas::{ContentPart, ImageContent, Message, MessageType};
 
 #[derive(Serialize, Deserialize)]
 pub(crate) struct ClaudeMessage {
     pub role: String,
-    pub content: String,
+    pub content: ClaudeContent,
 }
+
+/// The content of a message, either plain text or a list of blocks.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ClaudeContent {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum ContentBlock {
+    Text {
+        text: String,
+    },
+    Image {
+        source: ImageSource,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+        input: Value,
+    },
+    ToolResult {
+        tool_use_id: String,
+        content: String,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum ImageSource {
+    Base64 { media_type: String, data: String },
+    Url { url: String },
+}
+
+impl From<&ImageContent> for ImageSource {
+    /// Data URLs, `data:<media type>;base64,<data>`, are sent inline, other URLs by reference.
+    fn from(image: &ImageContent) -> Self {
+        let inline = image
+            .image_url
+            .strip_prefix("data:")
+            .and_then(|rest| rest.split_once(";base64,"));
+        match inline {
+            Some((media_type, data)) => ImageSource::Base64 {
+                media_type: media_type.to_string(),
+                data: data.to_string(),
+            },
+            None => ImageSource::Url {
+                url: image.image_url.clone(),
+            },
+        }
+    }
+}
+
 impl ClaudeMessage {
     pub fn new<S: Into<String>>(role: S, content: S) -> Self {
         Self {
             role: role.into(),
-            content: content.into(),
+            content: ClaudeContent::Text(content.into()),
         }
     }
 
+    /// Tool calls become `tool_use` blocks of the assistant message, and tool messages
+    /// `tool_result` blocks of a user message, as Claude has no tool role.
     pub fn from_message(message: &Message) -> Self {
         match message.message_type {
             MessageType::SystemMessage => Self::new("system", &message.content),
-            MessageType::AIMessage => Self::new("assistant", &message.content),
-            MessageType::HumanMessage => Self::new("user", &message.content),
-            MessageType::ToolMessage => Self::new("tool", &message.content),
+            MessageType::AIMessage => match &message.tool_calls {
+                Some(tool_calls) => {
+                    let mut blocks = Vec::new();
+                    if !message.content.is_empty() {
+                        blocks.push(ContentBlock::Text {
+                            text: message.content.clone(),
+                        });
+                    }
+                    blocks.extend(tool_calls.iter().map(|tool_call| {
+                        ContentBlock::ToolUse {
+                            id: tool_call.id.clone(),
+                            name: tool_call.name.clone(),
+                            input: serde_json::from_str(&tool_call.arguments)
+                                .unwrap_or_else(|_| Value::String(tool_call.arguments.clone())),
+                        }
+                    }));
+                    Self {
+                        role: "assistant".into(),
+                        content: ClaudeContent::Blocks(blocks),
+                    }
+                }
+                None => Self::new("assistant", &message.content),
+            },
+            MessageType::HumanMessage => match &message.content_parts {
+                Some(parts) => Self {
+                    role: "user".into(),
+                    content: ClaudeContent::Blocks(
+                        parts
+                            .iter()
+                            .map(|part| match part {
+                                ContentPart::Text { text } => {
+                                    ContentBlock::Text { text: text.clone() }
+                                }
+                                ContentPart::Image(image) => ContentBlock::Image {
+                                    source: image.into(),
+                                },
+                            })
+                            .collect(),
+                    ),
+                },
+                None => Self::new("user", &message.content),
+            },
+            MessageType::ToolMessage => Self {
+                role: "user".into(),
+                content: ClaudeContent::Blocks(vec![ContentBlock::ToolResult {
+                    tool_use_id: message.id.clone().unwrap_or_default(),
+                    content: message.content.clone(),
+                }]),
+            },
         }
     }
 }
@@ -69,3 +173,55 @@ pub(crate) struct Usage {
     pub input_tokens: u32,
     pub output_tokens: u32,
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::schemas::ToolCall;
+
+    use super::*;
+
+    #[test]
+    fn test_claude_message_from_message() {
+        let to_json =
+            |message: &Message| serde_json::to_value(ClaudeMessage::from_message(message));
+
+        let message = Message::new_human_message_with_parts(vec![
+            ContentPart::text("What is this?"),
+            ContentPart::image("data:image/png;base64,iVBORw0KGgo="),
+        ]);
+        assert_eq!(
+            to_json(&message).unwrap(),
+            json!({"role": "user", "content": [
+                {"type": "text", "text": "What is this?"},
+                {"type": "image", "source": {"type": "base64", "media_type": "image/png", "data": "iVBORw0KGgo="}},
+            ]})
+        );
+
+        let message = Message::new_ai_message("").with_tool_calls(vec![ToolCall::new(
+            "call_1",
+            "search",
+            r#"{"query":"rust"}"#,
+        )]);
+        assert_eq!(
+            to_json(&message).unwrap(),
+            json!({"role": "assistant", "content": [
+                {"type": "tool_use", "id": "call_1", "name": "search", "input": {"query": "rust"}},
+            ]})
+        );
+
+        let message = Message::new_tool_message("Rust is a language", "call_1");
+        assert_eq!(
+            to_json(&message).unwrap(),
+            json!({"role": "user", "content": [
+                {"type": "tool_result", "tool_use_id": "call_1", "content": "Rust is a language"},
+            ]})
+        );
+
+        assert_eq!(
+            to_json(&Message::new_human_message("Hi")).unwrap(),
+            json!({"role": "user", "content": "Hi"})
+        );
+    }
+}
@@ -1,8 +1,13 @@
+#[cfg(feature = "openai")]
 pub mod openai;
+#[cfg(feature = "openai")]
 pub use openai::*;
 
+#[cfg(feature = "claude")]
 pub mod claude;
+#[cfg(feature = "claude")]
 pub use claude::*;
 
 pub mod ollama;
+#[cfg(feature = "ollama")]
 pub use ollama::*;
@@ -1,6 +1,7 @@
 use crate::{
-    language_models::{llm::LLM, GenerateResult, LLMError, TokenUsage},
-    schemas::{Message, MessageType, StreamData},
+    callbacks::{trace_llm, trace_llm_stream},
+    language_models::{llm::LLM, GenerateResult, GenerationInfo, LLMError, TokenUsage},
+    schemas::{ContentPart, Message, MessageType, StreamData},
 };
 use async_trait::async_trait;
 use futures::Stream;
@@ -54,20 +55,54 @@ impl Ollama {
         let mapped_messages = messages.iter().map(|message| message.into()).collect();
         ChatMessageRequest::new(self.model.clone(), mapped_messages)
     }
+
+    async fn generate_response(&self, messages: &[Message]) -> Result<GenerateResult, LLMError> {
+        let request = self.generate_request(messages);
+        let result = self.client.send_chat_messages(request).await?;
+
+        let generation = match result.message {
+            Some(message) => message.content,
+            None => return Err(OllamaError::from("No message in response".to_string()).into()),
+        };
+
+        let tokens = result.final_data.map(|final_data| {
+            let prompt_tokens = final_data.prompt_eval_count as u32;
+            let completion_tokens = final_data.eval_count as u32;
+            TokenUsage {
+                prompt_tokens,
+                completion_tokens,
+                total_tokens: prompt_tokens + completion_tokens,
+            }
+        });
+
+        Ok(GenerateResult {
+            tokens,
+            generation,
+            info: GenerationInfo {
+                model: Some(result.model),
+                ..Default::default()
+            },
+        })
+    }
 }
 
 impl From<&Message> for ChatMessage {
     fn from(message: &Message) -> Self {
-        let images = match message.images.clone() {
-            Some(images) => {
-                let images = images
-                    .iter()
-                    .map(|image| Image::from_base64(&image.image_url))
-                    .collect();
-                Some(images)
-            }
-            None => None,
-        };
+        // Ollama takes the base64 data of images, without the prefix of data URLs.
+        let images = message.content_parts.as_ref().map(|parts| {
+            parts
+                .iter()
+                .filter_map(|part| match part {
+                    ContentPart::Image(image) => Some(Image::from_base64(
+                        image
+                            .image_url
+                            .split_once(";base64,")
+                            .map_or(image.image_url.as_str(), |(_, data)| data),
+                    )),
+                    ContentPart::Text { .. } => None,
+                })
+                .collect()
+        });
         ChatMessage {
             content: message.content.clone(),
             images,
@@ -97,25 +132,13 @@ impl Default for Ollama {
 #[async_trait]
 impl LLM for Ollama {
     async fn generate(&self, messages: &[Message]) -> Result<GenerateResult, LLMError> {
-        let request = self.generate_request(messages);
-        let result = self.client.send_chat_messages(request).await?;
-
-        let generation = match result.message {
-            Some(message) => message.content,
-            None => return Err(OllamaError::from("No message in response".to_string()).into()),
-        };
-
-        let tokens = result.final_data.map(|final_data| {
-            let prompt_tokens = final_data.prompt_eval_count as u32;
-            let completion_tokens = final_data.eval_count as u32;
-            TokenUsage {
-                prompt_tokens,
-                completion_tokens,
-                total_tokens: prompt_tokens + completion_tokens,
-            }
-        });
-
-        Ok(GenerateResult { tokens, generation })
+        trace_llm(
+            "Ollama",
+            &self.model,
+            messages,
+            self.generate_response(messages),
+        )
+        .await
     }
 
     async fn stream(
@@ -140,7 +163,12 @@ impl LLM for Ollama {
             Err(_) => Err(OllamaError::from("Stream error".to_string()).into()),
         });
 
-        Ok(Box::pin(stream))
+        Ok(trace_llm_stream(
+            "Ollama",
+            &self.model,
+            messages,
+            Box::pin(stream),
+        ))
     }
 }
 
@@ -1,4 +1,5 @@
 #[cfg(feature = "ollama")]
 pub mod client;
 
+#[cfg(feature = "openai")]
 pub mod openai;
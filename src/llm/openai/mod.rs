@@ -6,11 +6,12 @@ use async_openai::{
     types::{
         ChatChoiceStream, ChatCompletionMessageToolCall, ChatCompletionRequestAssistantMessageArgs,
         ChatCompletionRequestMessage, ChatCompletionRequestMessageContentPartImageArgs,
-        ChatCompletionRequestSystemMessageArgs, ChatCompletionRequestToolMessageArgs,
-        ChatCompletionRequestUserMessageArgs, ChatCompletionRequestUserMessageContent,
-        ChatCompletionRequestUserMessageContentPart, ChatCompletionStreamOptions,
-        ChatCompletionToolArgs, ChatCompletionToolType, CreateChatCompletionRequest,
-        CreateChatCompletionRequestArgs, FunctionObjectArgs,
+        ChatCompletionRequestMessageContentPartTextArgs, ChatCompletionRequestSystemMessageArgs,
+        ChatCompletionRequestToolMessageArgs, ChatCompletionRequestUserMessageArgs,
+        ChatCompletionRequestUserMessageContent, ChatCompletionRequestUserMessageContentPart,
+        ChatCompletionStreamOptions, ChatCompletionToolArgs, ChatCompletionToolType,
+        CreateChatCompletionRequest, CreateChatCompletionRequestArgs, FinishReason, FunctionCall,
+        FunctionObjectArgs, ImageDetail, ImageUrl,
     },
     Client,
 };
@@ -18,9 +19,13 @@ use async_trait::async_trait;
 use futures::{Stream, StreamExt};
 
 use crate::{
-    language_models::{llm::LLM, options::CallOptions, GenerateResult, LLMError, TokenUsage},
+    callbacks::{emit_llm_new_token, trace_llm, trace_llm_stream},
+    language_models::{
+        cancellable_stream, llm::LLM, options::CallOptions, run_cancellable, GenerateResult,
+        GenerationInfo, LLMError, TokenUsage,
+    },
     schemas::{
-        messages::{Message, MessageType},
+        messages::{ContentPart, Message, MessageType},
         FunctionCallBehavior, StreamData,
     },
 };
@@ -90,9 +95,8 @@ impl Default for OpenAI<OpenAIConfig> {
     }
 }
 
-#[async_trait]
-impl<C: Config + Send + Sync + 'static> LLM for OpenAI<C> {
-    async fn generate(&self, prompt: &[Message]) -> Result<GenerateResult, LLMError> {
+impl<C: Config> OpenAI<C> {
+    async fn generate_response(&self, prompt: &[Message]) -> Result<GenerateResult, LLMError> {
         let client = Client::with_config(self.config.clone());
         let request = self.generate_request(prompt, self.options.streaming_func.is_some())?;
         match &self.options.streaming_func {
@@ -102,6 +106,20 @@ impl<C: Config + Send + Sync + 'static> LLM for OpenAI<C> {
                 while let Some(result) = stream.next().await {
                     match result {
                         Ok(response) => {
+                            let info = &mut generate_result.info;
+                            info.model = Some(response.model.clone());
+                            info.response_id = Some(response.id.clone());
+                            info.system_fingerprint = response
+                                .system_fingerprint
+                                .clone()
+                                .or(info.system_fingerprint.take());
+                            if let Some(reason) = response
+                                .choices
+                                .first()
+                                .and_then(|choice| choice.finish_reason.as_ref())
+                            {
+                                info.finish_reason = finish_reason_name(reason);
+                            }
                             if let Some(usage) = response.usage {
                                 generate_result.tokens = Some(TokenUsage {
                                     prompt_tokens: usage.prompt_tokens,
@@ -119,12 +137,13 @@ impl<C: Config + Send + Sync + 'static> LLM for OpenAI<C> {
                                     .await;
                                 }
                                 if let Some(content) = chat_choice.delta.content {
+                                    emit_llm_new_token(&content);
                                     generate_result.generation.push_str(&content);
                                 }
                             }
                         }
                         Err(err) => {
-                            eprintln!("Error from streaming response: {:?}", err);
+                            tracing::error!(error = ?err, "Error from streaming response");
                         }
                     }
                 }
@@ -142,6 +161,18 @@ impl<C: Config + Send + Sync + 'static> LLM for OpenAI<C> {
                     });
                 }
 
+                generate_result.info = GenerationInfo {
+                    model: Some(response.model.clone()),
+                    system_fingerprint: response.system_fingerprint.clone(),
+                    response_id: Some(response.id.clone()),
+                    finish_reason: response
+                        .choices
+                        .first()
+                        .and_then(|choice| choice.finish_reason.as_ref())
+                        .and_then(finish_reason_name),
+                    ..Default::default()
+                };
+
                 if let Some(choice) = &response.choices.first() {
                     generate_result.generation = choice.message.content.clone().unwrap_or_default();
                     if let Some(function) = &choice.message.tool_calls {
@@ -156,6 +187,22 @@ impl<C: Config + Send + Sync + 'static> LLM for OpenAI<C> {
             }
         }
     }
+}
+
+#[async_trait]
+impl<C: Config + Send + Sync + 'static> LLM for OpenAI<C> {
+    async fn generate(&self, prompt: &[Message]) -> Result<GenerateResult, LLMError> {
+        trace_llm(
+            "OpenAI",
+            &self.model,
+            prompt,
+            run_cancellable(
+                self.options.cancellation_token.as_ref(),
+                self.generate_response(prompt),
+            ),
+        )
+        .await
+    }
 
     async fn invoke(&self, prompt: &str) -> Result<String, LLMError> {
         self.generate(&[Message::new_human_message(prompt)])
@@ -170,7 +217,11 @@ impl<C: Config + Send + Sync + 'static> LLM for OpenAI<C> {
         let client = Client::with_config(self.config.clone());
         let request = self.generate_request(messages, true)?;
 
-        let original_stream = client.chat().create_stream(request).await?;
+        let token = self.options.cancellation_token.as_ref();
+        let original_stream = run_cancellable(token, async {
+            Ok(client.chat().create_stream(request).await?)
+        })
+        .await?;
 
         let new_stream = original_stream.map(|result| match result {
             Ok(completion) => {
@@ -197,7 +248,12 @@ impl<C: Config + Send + Sync + 'static> LLM for OpenAI<C> {
             Err(e) => Err(LLMError::from(e)),
         });
 
-        Ok(Box::pin(new_stream))
+        Ok(trace_llm_stream(
+            "OpenAI",
+            &self.model,
+            messages,
+            cancellable_stream(Box::pin(new_stream), token.cloned()),
+        ))
     }
 
     fn add_options(&mut self, options: CallOptions) {
@@ -214,9 +270,18 @@ impl<C: Config> OpenAI<C> {
         for m in messages {
             match m.message_type {
                 MessageType::AIMessage => openai_messages.push(match &m.tool_calls {
-                    Some(value) => {
-                        let function: Vec<ChatCompletionMessageToolCall> =
-                            serde_json::from_value(value.clone())?;
+                    Some(tool_calls) => {
+                        let function: Vec<ChatCompletionMessageToolCall> = tool_calls
+                            .iter()
+                            .map(|tool_call| ChatCompletionMessageToolCall {
+                                id: tool_call.id.clone(),
+                                r#type: ChatCompletionToolType::Function,
+                                function: FunctionCall {
+                                    name: tool_call.name.clone(),
+                                    arguments: tool_call.arguments.clone(),
+                                },
+                            })
+                            .collect();
                         ChatCompletionRequestAssistantMessageArgs::default()
                             .tool_calls(function)
                             .content(m.content.clone())
@@ -229,18 +294,36 @@ impl<C: Config> OpenAI<C> {
                         .into(),
                 }),
                 MessageType::HumanMessage => {
-                    let content: ChatCompletionRequestUserMessageContent = match m.images.clone() {
-                        Some(images) => {
+                    let content: ChatCompletionRequestUserMessageContent = match &m.content_parts {
+                        Some(parts) => {
                             let content: Result<
                                 Vec<ChatCompletionRequestUserMessageContentPart>,
                                 OpenAIError,
-                            > = images
-                                .into_iter()
-                                .map(|image| {
-                                    Ok(ChatCompletionRequestMessageContentPartImageArgs::default()
-                                        .image_url(image.image_url)
-                                        .build()?
-                                        .into())
+                            > = parts
+                                .iter()
+                                .map(|part| match part {
+                                    ContentPart::Text { text } => Ok(
+                                        ChatCompletionRequestMessageContentPartTextArgs::default()
+                                            .text(text.clone())
+                                            .build()?
+                                            .into(),
+                                    ),
+                                    ContentPart::Image(image) => Ok(
+                                        ChatCompletionRequestMessageContentPartImageArgs::default()
+                                            .image_url(ImageUrl {
+                                                url: image.image_url.clone(),
+                                                detail: image.detail.as_deref().and_then(
+                                                    |detail| match detail {
+                                                        "low" => Some(ImageDetail::Low),
+                                                        "high" => Some(ImageDetail::High),
+                                                        "auto" => Some(ImageDetail::Auto),
+                                                        _ => None,
+                                                    },
+                                                ),
+                                            })
+                                            .build()?
+                                            .into(),
+                                    ),
                                 })
                                 .collect();
 
@@ -328,10 +411,17 @@ impl<C: Config> OpenAI<C> {
         Ok(request_builder.build()?)
     }
 }
+/// The name OpenAI gives to the finish reason, e.g. `stop` or `length`.
+fn finish_reason_name(reason: &FinishReason) -> Option<String> {
+    serde_json::to_value(reason)
+        .ok()
+        .and_then(|value| value.as_str().map(String::from))
+}
+
 #[cfg(test)]
 mod tests {
 
-    use crate::schemas::FunctionDefinition;
+    use crate::schemas::{FunctionDefinition, ImageContent, ToolCall};
 
     use super::*;
 
@@ -507,4 +597,56 @@ mod tests {
         let response = open_ai.generate(&messages).await.unwrap();
         println!("Response: {:?}", response);
     }
+
+    #[test]
+    async fn test_to_openai_messages() {
+        let open_ai = OpenAI::default();
+        let messages = open_ai
+            .to_openai_messages(&[
+                Message::new_human_message_with_parts(vec![
+                    ContentPart::text("Describe this image"),
+                    ContentPart::Image(ImageContent {
+                        image_url: "https://example.com/cat.png".to_string(),
+                        detail: Some("low".to_string()),
+                    }),
+                ]),
+                Message::new_ai_message("").with_tool_calls(vec![ToolCall::new(
+                    "call_1",
+                    "search",
+                    r#"{"query":"cats"}"#,
+                )]),
+                Message::new_tool_message("A cat", "call_1"),
+            ])
+            .unwrap();
+
+        let messages = serde_json::to_value(messages).unwrap();
+        assert_eq!(
+            messages[0]["content"],
+            json!([
+                {"type": "text", "text": "Describe this image"},
+                {"type": "image_url", "image_url": {"url": "https://example.com/cat.png", "detail": "low"}},
+            ])
+        );
+        assert_eq!(
+            messages[1]["tool_calls"],
+            json!([{
+                "id": "call_1",
+                "type": "function",
+                "function": {"name": "search", "arguments": "{\"query\":\"cats\"}"},
+            }])
+        );
+        assert_eq!(messages[2]["tool_call_id"], "call_1");
+    }
+
+    #[test]
+    async fn test_finish_reason_name() {
+        assert_eq!(
+            finish_reason_name(&FinishReason::Length).as_deref(),
+            Some("length")
+        );
+        assert_eq!(
+            finish_reason_name(&FinishReason::ToolCalls).as_deref(),
+            Some("tool_calls")
+        );
+    }
 }
@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum TranscriptError {
+    #[error("Serde json error: {0}")]
+    SerdeJsonError(#[from] serde_json::Error),
+
+    #[error("Transcript version {version} is not supported, the latest supported is {supported}")]
+    UnsupportedVersion { version: u64, supported: u32 },
+}
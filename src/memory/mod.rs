@@ -1,7 +1,11 @@
 mod dummy_memory;
+mod error;
 mod simple_memory;
+mod transcript;
 mod window_buffer;
 
 pub use dummy_memory::*;
+pub use error::*;
 pub use simple_memory::*;
+pub use transcript::*;
 pub use window_buffer::*;
@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::schemas::Message;
+
+use super::TranscriptError;
+
+/// The version of the transcript format written by `Transcript::to_json`.
+pub const TRANSCRIPT_VERSION: u32 = 1;
+
+/// A conversation exported from a memory, to archive a session, move it to another memory
+/// backend or replay it in tests.
+///
+/// Messages keep their tool calls, tool call IDs and content parts. `metadata` holds anything
+/// else about the session, such as a user or session ID.
+///
+/// # Usage
+/// ```rust,ignore
+/// let json = memory
+///     .export_transcript()
+///     .with_metadata("session_id", "42")
+///     .to_json()?;
+///
+/// let mut restored = WindowBufferMemory::default();
+/// restored.import_transcript(&Transcript::from_json(&json)?);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Transcript {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub metadata: HashMap<String, Value>,
+    pub messages: Vec<Message>,
+}
+
+impl Transcript {
+    pub fn new(messages: Vec<Message>) -> Self {
+        Self {
+            version: TRANSCRIPT_VERSION,
+            created_at: Utc::now(),
+            metadata: HashMap::new(),
+            messages,
+        }
+    }
+
+    pub fn with_metadata<K: Into<String>, V: Into<Value>>(mut self, key: K, value: V) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn to_json(&self) -> Result<String, TranscriptError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parses a transcript, failing with `TranscriptError::UnsupportedVersion` when it was
+    /// written by a newer version of the format.
+    pub fn from_json(json: &str) -> Result<Self, TranscriptError> {
+        let value: Value = serde_json::from_str(json)?;
+        if let Some(version) = value["version"].as_u64() {
+            if version > TRANSCRIPT_VERSION as u64 {
+                return Err(TranscriptError::UnsupportedVersion {
+                    version,
+                    supported: TRANSCRIPT_VERSION,
+                });
+            }
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        memory::{SimpleMemory, WindowBufferMemory},
+        schemas::{memory::BaseMemory, ContentPart, ToolCall},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_transcript_round_trip() {
+        let mut memory = SimpleMemory::new();
+        memory.add_message(Message::new_system_message("You are a helpful assistant"));
+        memory.add_message(Message::new_human_message_with_parts(vec![
+            ContentPart::text("What is in this image?"),
+            ContentPart::image("https://example.com/cat.png"),
+        ]));
+        memory.add_message(
+            Message::new_ai_message("").with_tool_calls(vec![ToolCall::new(
+                "call_1",
+                "search",
+                r#"{"query":"cat"}"#,
+            )]),
+        );
+        memory.add_message(Message::new_tool_message("A cat", "call_1"));
+        memory.add_ai_message(&"It is a cat.");
+
+        let json = memory
+            .export_transcript()
+            .with_metadata("session_id", "42")
+            .to_json()
+            .unwrap();
+        let transcript = Transcript::from_json(&json).unwrap();
+        assert_eq!(transcript.version, TRANSCRIPT_VERSION);
+        assert_eq!(transcript.metadata["session_id"], "42");
+
+        let mut restored = WindowBufferMemory::new(10);
+        restored.add_user_message(&"Replaced");
+        restored.import_transcript(&transcript);
+        assert_eq!(restored.messages(), memory.messages());
+    }
+
+    #[test]
+    fn test_transcript_unsupported_version() {
+        let json = r#"{"version": 99, "created_at": "2024-01-01T00:00:00Z", "messages": []}"#;
+        assert!(matches!(
+            Transcript::from_json(json),
+            Err(TranscriptError::UnsupportedVersion { version: 99, .. })
+        ));
+    }
+}
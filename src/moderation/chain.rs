@@ -0,0 +1,269 @@
+use std::{collections::HashMap, pin::Pin, sync::Arc};
+
+use async_trait::async_trait;
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    chain::{output_generation, Chain, ChainError},
+    language_models::GenerateResult,
+    prompt::PromptArgs,
+    schemas::StreamData,
+};
+
+use super::{ModerationAction, ModerationError, ModerationPolicy, ModerationResult, Moderator};
+
+pub(crate) const INPUT_MODERATION_KEY: &str = "input_moderation";
+pub(crate) const OUTPUT_MODERATION_KEY: &str = "output_moderation";
+
+/// The moderation of the input or the output of a chain.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModerationCheck {
+    pub result: ModerationResult,
+    /// The categories that violate the policy of the chain.
+    pub violations: Vec<String>,
+}
+
+/// Wraps a chain to classify its inputs before calling it and its generation after, with the
+/// violations of the policy either blocking the chain or being flagged.
+///
+/// The string input variables are moderated together. `execute` returns the checks under the
+/// `input_moderation` and `output_moderation` keys, with the category scores. Streams only
+/// moderate the input.
+///
+/// Created by the `with_moderation` method of `Chain`.
+///
+/// # Usage
+/// ```rust,ignore
+/// let chain = chain
+///     .with_moderation(OpenAIModerator::default())
+///     .with_policy(ModerationPolicy::flag().with_threshold("violence", 0.5))
+///     .with_output_moderation(false);
+/// ```
+pub struct ModerationChain<C> {
+    inner: C,
+    moderator: Arc<dyn Moderator>,
+    policy: ModerationPolicy,
+    moderate_input: bool,
+    moderate_output: bool,
+}
+
+impl<C: Chain> ModerationChain<C> {
+    pub fn new<M: Moderator + 'static>(inner: C, moderator: M) -> Self {
+        Self {
+            inner,
+            moderator: Arc::new(moderator),
+            policy: ModerationPolicy::default(),
+            moderate_input: true,
+            moderate_output: true,
+        }
+    }
+
+    pub fn with_policy(mut self, policy: ModerationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn with_input_moderation(mut self, moderate_input: bool) -> Self {
+        self.moderate_input = moderate_input;
+        self
+    }
+
+    pub fn with_output_moderation(mut self, moderate_output: bool) -> Self {
+        self.moderate_output = moderate_output;
+        self
+    }
+
+    /// Moderates `text`, failing when it violates a blocking policy.
+    async fn check(&self, stage: &str, text: &str) -> Result<ModerationCheck, ChainError> {
+        let result = self.moderator.moderate(text).await?;
+        let violations = self.policy.violations(&result);
+        if !violations.is_empty() {
+            match self.policy.action() {
+                ModerationAction::Block => {
+                    return Err(ModerationError::Blocked {
+                        stage: stage.to_string(),
+                        categories: violations,
+                    }
+                    .into())
+                }
+                ModerationAction::Flag => {
+                    tracing::warn!("The {} was flagged by moderation: {:?}", stage, violations)
+                }
+            }
+        }
+        Ok(ModerationCheck { result, violations })
+    }
+
+    async fn check_input(
+        &self,
+        input_variables: &PromptArgs,
+    ) -> Result<Option<ModerationCheck>, ChainError> {
+        if !self.moderate_input {
+            return Ok(None);
+        }
+        let mut inputs: Vec<(&String, &str)> = input_variables
+            .iter()
+            .filter_map(|(key, value)| value.as_str().map(|value| (key, value)))
+            .collect();
+        if inputs.is_empty() {
+            return Ok(None);
+        }
+        inputs.sort();
+        let text = inputs
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        self.check("input", &text).await.map(Some)
+    }
+
+    async fn check_output(&self, generation: &str) -> Result<Option<ModerationCheck>, ChainError> {
+        if !self.moderate_output || generation.is_empty() {
+            return Ok(None);
+        }
+        self.check("output", generation).await.map(Some)
+    }
+}
+
+#[async_trait]
+impl<C: Chain> Chain for ModerationChain<C> {
+    async fn call(&self, input_variables: PromptArgs) -> Result<GenerateResult, ChainError> {
+        self.check_input(&input_variables).await?;
+        let result = self.inner.call(input_variables).await?;
+        self.check_output(&result.generation).await?;
+        Ok(result)
+    }
+
+    async fn execute(
+        &self,
+        input_variables: PromptArgs,
+    ) -> Result<HashMap<String, Value>, ChainError> {
+        let input_check = self.check_input(&input_variables).await?;
+        let mut output = self.inner.execute(input_variables).await?;
+        let generation = output_generation(&output).unwrap_or_default();
+        let output_check = self.check_output(&generation).await?;
+
+        if let Some(check) = input_check {
+            output.insert(INPUT_MODERATION_KEY.to_string(), json!(check));
+        }
+        if let Some(check) = output_check {
+            output.insert(OUTPUT_MODERATION_KEY.to_string(), json!(check));
+        }
+        Ok(output)
+    }
+
+    async fn stream(
+        &self,
+        input_variables: PromptArgs,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamData, ChainError>> + Send>>, ChainError>
+    {
+        self.check_input(&input_variables).await?;
+        self.inner.stream(input_variables).await
+    }
+
+    fn get_input_keys(&self) -> Vec<String> {
+        self.inner.get_input_keys()
+    }
+
+    fn get_output_keys(&self) -> Vec<String> {
+        let mut keys = self.inner.get_output_keys();
+        if self.moderate_input {
+            keys.push(INPUT_MODERATION_KEY.to_string());
+        }
+        if self.moderate_output {
+            keys.push(OUTPUT_MODERATION_KEY.to_string());
+        }
+        keys
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prompt_args;
+
+    use super::*;
+
+    /// Scores the texts containing "attack" as violent.
+    struct KeywordModerator;
+
+    #[async_trait]
+    impl Moderator for KeywordModerator {
+        async fn moderate(&self, text: &str) -> Result<ModerationResult, ModerationError> {
+            let violent = text.contains("attack");
+            Ok(ModerationResult {
+                flagged_categories: if violent {
+                    vec!["violence".to_string()]
+                } else {
+                    vec![]
+                },
+                category_scores: HashMap::from([(
+                    "violence".to_string(),
+                    if violent { 0.9 } else { 0.1 },
+                )]),
+            })
+        }
+    }
+
+    struct EchoChain;
+
+    #[async_trait]
+    impl Chain for EchoChain {
+        async fn call(&self, input_variables: PromptArgs) -> Result<GenerateResult, ChainError> {
+            Ok(GenerateResult {
+                generation: input_variables["input"]
+                    .as_str()
+                    .unwrap()
+                    .replace("plan", "attack"),
+                ..Default::default()
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_moderation_chain_block() {
+        let chain = EchoChain.with_moderation(KeywordModerator);
+        let error = chain
+            .call(prompt_args! { "input" => "Launch the attack" })
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            ChainError::ModerationError(ModerationError::Blocked { ref stage, .. }) if stage == "input"
+        ));
+
+        let error = chain
+            .call(prompt_args! { "input" => "Make a plan" })
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            ChainError::ModerationError(ModerationError::Blocked { ref stage, .. }) if stage == "output"
+        ));
+
+        assert!(chain
+            .call(prompt_args! { "input" => "Hello" })
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_moderation_chain_flag() {
+        let chain = EchoChain
+            .with_moderation(KeywordModerator)
+            .with_policy(ModerationPolicy::flag());
+        let output = chain
+            .execute(prompt_args! { "input" => "Make a plan" })
+            .await
+            .unwrap();
+
+        let input: ModerationCheck =
+            serde_json::from_value(output[INPUT_MODERATION_KEY].clone()).unwrap();
+        assert!(input.violations.is_empty());
+        assert_eq!(input.result.category_scores["violence"], 0.1);
+        let output: ModerationCheck =
+            serde_json::from_value(output[OUTPUT_MODERATION_KEY].clone()).unwrap();
+        assert_eq!(output.violations, vec!["violence"]);
+    }
+}
@@ -0,0 +1,22 @@
+#[cfg(feature = "openai")]
+use async_openai::error::OpenAIError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ModerationError {
+    #[cfg(feature = "openai")]
+    #[error("OpenAI error: {0}")]
+    OpenAIError(#[from] OpenAIError),
+
+    #[error("Serde json error: {0}")]
+    SerdeJsonError(#[from] serde_json::Error),
+
+    #[error("The {stage} was blocked by moderation: {}", categories.join(", "))]
+    Blocked {
+        stage: String,
+        categories: Vec<String>,
+    },
+
+    #[error("Error: {0}")]
+    OtherError(String),
+}
@@ -0,0 +1,16 @@
+//! Content moderation of chain inputs and outputs, with the OpenAI moderation endpoint or any
+//! classifier implementing `Moderator`, through the `with_moderation` method of `Chain`.
+
+mod error;
+pub use error::*;
+
+mod moderator;
+pub use moderator::*;
+
+#[cfg(feature = "openai")]
+mod openai;
+#[cfg(feature = "openai")]
+pub use openai::*;
+
+mod chain;
+pub use chain::*;
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::ModerationError;
+
+/// The classification of a text by a `Moderator`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModerationResult {
+    /// The categories the moderator flagged the text for.
+    pub flagged_categories: Vec<String>,
+    /// The score of each category, between 0 and 1.
+    pub category_scores: HashMap<String, f64>,
+}
+
+impl ModerationResult {
+    pub fn is_flagged(&self) -> bool {
+        !self.flagged_categories.is_empty()
+    }
+}
+
+#[async_trait]
+pub trait Moderator: Send + Sync {
+    async fn moderate(&self, text: &str) -> Result<ModerationResult, ModerationError>;
+}
+
+/// What a `ModerationChain` does with the texts that violate its policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ModerationAction {
+    /// Fails the chain with a `ModerationError::Blocked` error.
+    #[default]
+    Block,
+    /// Lets the chain run, logging the violations and returning them with the outputs.
+    Flag,
+}
+
+/// Decides which categories of a `ModerationResult` are violations.
+///
+/// A category with a threshold is a violation when its score reaches the threshold, and one
+/// without is a violation when the moderator flagged it.
+///
+/// # Usage
+/// ```rust,ignore
+/// let policy = ModerationPolicy::flag()
+///     .with_threshold("violence", 0.2)
+///     .with_default_threshold(0.8);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ModerationPolicy {
+    action: ModerationAction,
+    thresholds: HashMap<String, f64>,
+    default_threshold: Option<f64>,
+}
+
+impl ModerationPolicy {
+    pub fn block() -> Self {
+        Self::default()
+    }
+
+    pub fn flag() -> Self {
+        Self {
+            action: ModerationAction::Flag,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_threshold<S: Into<String>>(mut self, category: S, threshold: f64) -> Self {
+        self.thresholds.insert(category.into(), threshold);
+        self
+    }
+
+    /// Sets the threshold of the categories without one of their own, instead of relying on
+    /// the categories the moderator flagged.
+    pub fn with_default_threshold(mut self, threshold: f64) -> Self {
+        self.default_threshold = Some(threshold);
+        self
+    }
+
+    pub fn action(&self) -> ModerationAction {
+        self.action
+    }
+
+    /// The categories of `result` that violate the policy, sorted.
+    pub fn violations(&self, result: &ModerationResult) -> Vec<String> {
+        let mut violations: Vec<String> = result
+            .category_scores
+            .iter()
+            .filter(|(category, score)| {
+                match self
+                    .thresholds
+                    .get(*category)
+                    .or(self.default_threshold.as_ref())
+                {
+                    Some(threshold) => *score >= threshold,
+                    None => result.flagged_categories.contains(category),
+                }
+            })
+            .map(|(category, _)| category.clone())
+            .collect();
+        violations.sort();
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_moderation_policy_violations() {
+        let result = ModerationResult {
+            flagged_categories: vec!["hate".to_string()],
+            category_scores: HashMap::from([
+                ("hate".to_string(), 0.9),
+                ("violence".to_string(), 0.3),
+                ("sexual".to_string(), 0.1),
+            ]),
+        };
+        assert_eq!(ModerationPolicy::block().violations(&result), vec!["hate"]);
+        assert_eq!(
+            ModerationPolicy::block()
+                .with_threshold("violence", 0.2)
+                .violations(&result),
+            vec!["hate", "violence"]
+        );
+        assert_eq!(
+            ModerationPolicy::flag()
+                .with_threshold("hate", 0.95)
+                .with_default_threshold(0.05)
+                .violations(&result),
+            vec!["sexual", "violence"]
+        );
+    }
+}
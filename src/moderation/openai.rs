@@ -0,0 +1,95 @@
+use async_openai::{
+    config::{Config, OpenAIConfig},
+    types::{CreateModerationRequestArgs, ModerationInput},
+    Client,
+};
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::{ModerationError, ModerationResult, Moderator};
+
+/// Classifies texts with the OpenAI moderation endpoint.
+///
+/// # Usage
+/// ```rust,ignore
+/// let chain = LLMChainBuilder::new()
+///     .prompt(prompt)
+///     .llm(OpenAI::default())
+///     .build()?
+///     .with_moderation(OpenAIModerator::default());
+/// ```
+#[derive(Debug, Clone)]
+pub struct OpenAIModerator<C: Config> {
+    config: C,
+    model: Option<String>,
+}
+
+impl<C: Config> OpenAIModerator<C> {
+    pub fn new(config: C) -> Self {
+        Self {
+            config,
+            model: None,
+        }
+    }
+
+    /// Sets the moderation model, the latest omni moderation model by default.
+    pub fn with_model<S: Into<String>>(mut self, model: S) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    pub fn with_config(mut self, config: C) -> Self {
+        self.config = config;
+        self
+    }
+}
+
+impl Default for OpenAIModerator<OpenAIConfig> {
+    fn default() -> Self {
+        Self::new(OpenAIConfig::default())
+    }
+}
+
+/// Reads a map of category names, such as `self-harm/intent`, from a category struct of the
+/// moderation response.
+fn category_map<T: serde::Serialize>(
+    categories: &T,
+) -> Result<Vec<(String, Value)>, ModerationError> {
+    match serde_json::to_value(categories)? {
+        Value::Object(map) => Ok(map.into_iter().collect()),
+        _ => Err(ModerationError::OtherError(
+            "Unexpected moderation categories".to_string(),
+        )),
+    }
+}
+
+#[async_trait]
+impl<C: Config + Send + Sync> Moderator for OpenAIModerator<C> {
+    async fn moderate(&self, text: &str) -> Result<ModerationResult, ModerationError> {
+        let client = Client::with_config(self.config.clone());
+
+        let mut request = CreateModerationRequestArgs::default();
+        request.input(ModerationInput::String(text.to_string()));
+        if let Some(model) = &self.model {
+            request.model(model);
+        }
+        let response = client.moderations().create(request.build()?).await?;
+
+        let mut result = ModerationResult::default();
+        for moderation in &response.results {
+            for (category, flagged) in category_map(&moderation.categories)? {
+                if flagged.as_bool() == Some(true) && !result.flagged_categories.contains(&category)
+                {
+                    result.flagged_categories.push(category);
+                }
+            }
+            for (category, score) in category_map(&moderation.category_scores)? {
+                let score = score.as_f64().unwrap_or_default();
+                let max: &mut f64 = result.category_scores.entry(category).or_default();
+                *max = max.max(score);
+            }
+        }
+        result.flagged_categories.sort();
+        Ok(result)
+    }
+}
@@ -0,0 +1,249 @@
+use async_trait::async_trait;
+
+use super::{OutputParser, OutputParserError};
+
+/// Constrains output to one of a fixed set of labels, returning the matching value of a
+/// typed enum. This is the building block for routing and guard checks.
+///
+/// Labels and aliases are matched case-insensitively, ignoring surrounding quotes and
+/// punctuation. When the output is a sentence rather than a bare label, the parser accepts
+/// it if it mentions exactly one label or alias as a whole word. As an `OutputParser` it
+/// returns the canonical label.
+///
+/// # Usage
+/// ```rust,ignore
+/// #[derive(Clone)]
+/// enum Route { Billing, Support }
+///
+/// let parser = EnumParser::new(vec![("billing", Route::Billing), ("support", Route::Support)])
+///     .with_alias("invoice", Route::Billing);
+/// let prompt = format!("Which team should handle this?\n{}", parser.format_instructions());
+/// let route = parser.parse_enum(&llm.invoke(&prompt).await?)?;
+/// ```
+pub struct EnumParser<T> {
+    labels: Vec<(String, T)>,
+    aliases: Vec<(String, usize)>,
+}
+
+impl<T: Clone + Send + Sync> EnumParser<T> {
+    pub fn new<S: Into<String>>(labels: Vec<(S, T)>) -> Self {
+        Self {
+            labels: labels
+                .into_iter()
+                .map(|(label, value)| (label.into(), value))
+                .collect(),
+            aliases: Vec::new(),
+        }
+    }
+
+    /// Adds another word accepted for the label whose value is `value`.
+    pub fn with_alias<S: Into<String>>(mut self, alias: S, value: T) -> Self
+    where
+        T: PartialEq,
+    {
+        if let Some(index) = self.labels.iter().position(|(_, v)| *v == value) {
+            self.aliases.push((normalize(&alias.into()), index));
+        }
+        self
+    }
+
+    /// Instructions to add to the prompt so the model answers with one of the labels.
+    pub fn format_instructions(&self) -> String {
+        format!(
+            "Select one of the following options: {}. Answer with the option only.",
+            self.labels
+                .iter()
+                .map(|(label, _)| label.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    pub fn parse_enum(&self, output: &str) -> Result<T, OutputParserError> {
+        let index = self.match_label(output)?;
+        Ok(self.labels[index].1.clone())
+    }
+
+    fn match_label(&self, output: &str) -> Result<usize, OutputParserError> {
+        let candidates: Vec<(String, usize)> = self
+            .labels
+            .iter()
+            .enumerate()
+            .map(|(index, (label, _))| (normalize(label), index))
+            .chain(self.aliases.iter().cloned())
+            .collect();
+
+        let answer = normalize(output);
+        if let Some((_, index)) = candidates.iter().find(|(word, _)| *word == answer) {
+            return Ok(*index);
+        }
+
+        let words: Vec<&str> = answer.split_whitespace().collect();
+        let mut found: Vec<usize> = candidates
+            .iter()
+            .filter(|(candidate, _)| {
+                let candidate: Vec<&str> = candidate.split_whitespace().collect();
+                !candidate.is_empty() && words.windows(candidate.len()).any(|w| w == candidate)
+            })
+            .map(|(_, index)| *index)
+            .collect();
+        found.sort_unstable();
+        found.dedup();
+
+        match found.as_slice() {
+            [index] => Ok(*index),
+            [] => Err(OutputParserError::ParsingError(format!(
+                "Output `{}` does not match any of: {}",
+                output.trim(),
+                self.label_list()
+            ))),
+            _ => Err(OutputParserError::ParsingError(format!(
+                "Output `{}` matches more than one of: {}",
+                output.trim(),
+                self.label_list()
+            ))),
+        }
+    }
+
+    fn label_list(&self) -> String {
+        self.labels
+            .iter()
+            .map(|(label, _)| label.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[async_trait]
+impl<T: Clone + Send + Sync> OutputParser for EnumParser<T> {
+    async fn parse(&self, output: &str) -> Result<String, OutputParserError> {
+        let index = self.match_label(output)?;
+        Ok(self.labels[index].0.clone())
+    }
+}
+
+/// Parses a yes/no answer into a `bool`. Accepts `yes`, `y`, `true` and `1` as true, `no`,
+/// `n`, `false` and `0` as false, plus any extra values given with `with_true_values` and
+/// `with_false_values`. As an `OutputParser` it returns `"true"` or `"false"`.
+pub struct BooleanParser {
+    inner: EnumParser<bool>,
+}
+
+impl BooleanParser {
+    pub fn new() -> Self {
+        Self {
+            inner: EnumParser::new(vec![("yes", true), ("no", false)])
+                .with_alias("y", true)
+                .with_alias("true", true)
+                .with_alias("1", true)
+                .with_alias("n", false)
+                .with_alias("false", false)
+                .with_alias("0", false),
+        }
+    }
+
+    pub fn with_true_values<S: Into<String>>(mut self, values: Vec<S>) -> Self {
+        for value in values {
+            self.inner = self.inner.with_alias(value, true);
+        }
+        self
+    }
+
+    pub fn with_false_values<S: Into<String>>(mut self, values: Vec<S>) -> Self {
+        for value in values {
+            self.inner = self.inner.with_alias(value, false);
+        }
+        self
+    }
+
+    /// Instructions to add to the prompt so the model answers yes or no.
+    pub fn format_instructions(&self) -> String {
+        "Answer with YES or NO only.".to_string()
+    }
+
+    pub fn parse_bool(&self, output: &str) -> Result<bool, OutputParserError> {
+        self.inner.parse_enum(output)
+    }
+}
+
+impl Default for BooleanParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl OutputParser for BooleanParser {
+    async fn parse(&self, output: &str) -> Result<String, OutputParserError> {
+        Ok(self.parse_bool(output)?.to_string())
+    }
+}
+
+/// Lowercases `text` and replaces punctuation with spaces, keeping `_` and `-`.
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c.is_whitespace() || c == '_' || c == '-' {
+                c
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum Sentiment {
+        Positive,
+        Negative,
+        Neutral,
+    }
+
+    #[tokio::test]
+    async fn test_enum_parser() {
+        let parser = EnumParser::new(vec![
+            ("positive", Sentiment::Positive),
+            ("negative", Sentiment::Negative),
+            ("neutral", Sentiment::Neutral),
+        ])
+        .with_alias("good", Sentiment::Positive);
+
+        assert_eq!(
+            parser.format_instructions(),
+            "Select one of the following options: positive, negative, neutral. Answer with the option only."
+        );
+        assert_eq!(
+            parser.parse_enum(" POSITIVE.\n").unwrap(),
+            Sentiment::Positive
+        );
+        assert_eq!(parser.parse_enum("\"Good\"").unwrap(), Sentiment::Positive);
+        assert_eq!(
+            parser
+                .parse_enum("The sentiment of this review is negative.")
+                .unwrap(),
+            Sentiment::Negative
+        );
+        assert_eq!(parser.parse("good").await.unwrap(), "positive");
+
+        assert!(parser.parse_enum("mixed").is_err());
+        assert!(parser.parse_enum("either positive or negative").is_err());
+    }
+
+    #[test]
+    fn test_boolean_parser() {
+        let parser = BooleanParser::new().with_true_values(vec!["sí"]);
+        assert!(parser.parse_bool("Yes").unwrap());
+        assert!(parser.parse_bool("SÍ").unwrap());
+        assert!(!parser.parse_bool("No, it is not.").unwrap());
+        assert!(!parser.parse_bool("false").unwrap());
+        assert!(parser.parse_bool("maybe").is_err());
+    }
+}
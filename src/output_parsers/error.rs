@@ -1,6 +1,10 @@
+use std::fmt;
+
 use regex::Error as RegexError;
 use thiserror::Error;
 
+use crate::language_models::LLMError;
+
 #[derive(Error, Debug)]
 pub enum OutputParserError {
     #[error("Regex error: {0}")]
@@ -8,4 +12,40 @@ pub enum OutputParserError {
 
     #[error("Parsing error: {0}")]
     ParsingError(String),
+
+    #[error("Serde json error: {0}")]
+    SerdeJsonError(#[from] serde_json::Error),
+
+    #[error("LLM error: {0}")]
+    LLMError(#[from] LLMError),
+
+    #[error("Validation error: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    ValidationError(Vec<FieldError>),
+}
+
+/// A field of the output that does not match the expected schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldError {
+    /// Path of the field, e.g. `cast[0].name`, empty for the whole output.
+    pub path: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new<S: Into<String>>(path: &str, message: S) -> Self {
+        Self {
+            path: path.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
 }
@@ -0,0 +1,332 @@
+use async_trait::async_trait;
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use super::{OutputParser, OutputParserError};
+
+/// Extracts JSON from LLM output and parses it, repairing the syntax errors models
+/// commonly make.
+///
+/// The JSON is taken from the first fenced code block if there is one, otherwise from the
+/// first `{` or `[`, so leading and trailing prose is ignored. When strict parsing fails the
+/// candidate is repaired with [`repair_json`] and parsed again.
+///
+/// As an `OutputParser` it returns the parsed JSON serialized back to a compact string;
+/// use `parse_value` or `parse_into` to get the parsed value directly.
+///
+/// # Usage
+/// ```rust,ignore
+/// let parser = JsonParser::new();
+/// let value = parser.parse_value("Sure! ```json\n{'name': 'Ada', tags: [1, 2,],}\n```")?;
+/// assert_eq!(value["name"], "Ada");
+/// ```
+pub struct JsonParser {
+    repair: bool,
+}
+
+impl JsonParser {
+    pub fn new() -> Self {
+        Self { repair: true }
+    }
+
+    /// Repairs common syntax errors when strict parsing fails (the default).
+    pub fn with_repair(mut self, repair: bool) -> Self {
+        self.repair = repair;
+        self
+    }
+
+    pub fn parse_value(&self, output: &str) -> Result<Value, OutputParserError> {
+        let candidate = extract_json(output)?;
+        match serde_json::from_str(candidate) {
+            Ok(value) => Ok(value),
+            Err(_) if self.repair => Ok(serde_json::from_str(&repair_json(candidate))?),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Parses the output and deserializes it into `T`.
+    pub fn parse_into<T: DeserializeOwned>(&self, output: &str) -> Result<T, OutputParserError> {
+        Ok(serde_json::from_value(self.parse_value(output)?)?)
+    }
+}
+
+impl Default for JsonParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl OutputParser for JsonParser {
+    async fn parse(&self, output: &str) -> Result<String, OutputParserError> {
+        Ok(self.parse_value(output)?.to_string())
+    }
+}
+
+/// Finds the JSON candidate in `output`: the content of the first fenced code block,
+/// otherwise the text from the first `{` or `[` to its matching closing bracket, or to the
+/// end of the output if the JSON is truncated.
+pub(crate) fn extract_json(output: &str) -> Result<&str, OutputParserError> {
+    let fence = Regex::new(r"```(?:\w+)?\s*([\s\S]*?)\s*```")?;
+    let text = match fence.captures(output).and_then(|cap| cap.get(1)) {
+        Some(block) => block.as_str(),
+        None => output,
+    };
+
+    let start = text
+        .find(['{', '['])
+        .ok_or_else(|| OutputParserError::ParsingError("No JSON found in output".into()))?;
+
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for (i, c) in text[start..].char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '{' | '[' => depth += 1,
+            '}' | ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(&text[start..start + i + 1]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(text[start..].trim_end())
+}
+
+/// Repairs common syntax errors in JSON written by language models:
+/// - single quoted strings and unquoted keys
+/// - trailing commas and missing commas between values
+/// - `//` and `/* */` comments
+/// - Python literals `True`, `False` and `None`
+/// - raw newlines and tabs inside strings
+/// - unterminated strings, objects and arrays of truncated output
+pub fn repair_json(input: &str) -> String {
+    let chars: Vec<char> = input.chars().collect();
+    let mut out = String::with_capacity(input.len());
+    let mut stack: Vec<char> = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' | '\'' => {
+                insert_missing_comma(&mut out, &stack);
+                i = copy_string(&chars, i, &mut out);
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+                continue;
+            }
+            '{' | '[' => {
+                insert_missing_comma(&mut out, &stack);
+                stack.push(if c == '{' { '}' } else { ']' });
+                out.push(c);
+            }
+            '}' | ']' => {
+                remove_trailing_comma(&mut out);
+                if stack.last() == Some(&c) {
+                    stack.pop();
+                    out.push(c);
+                }
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '.' => {
+                insert_missing_comma(&mut out, &stack);
+                while i < chars.len()
+                    && (chars[i].is_ascii_alphanumeric() || matches!(chars[i], '.' | '-' | '+'))
+                {
+                    out.push(chars[i]);
+                    i += 1;
+                }
+                continue;
+            }
+            c if c.is_alphabetic() || c == '_' || c == '$' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '$' | '-'))
+                {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                insert_missing_comma(&mut out, &stack);
+                match word.as_str() {
+                    "true" | "True" => out.push_str("true"),
+                    "false" | "False" => out.push_str("false"),
+                    "null" | "None" | "undefined" => out.push_str("null"),
+                    _ => out.push_str(&Value::from(word).to_string()),
+                }
+                continue;
+            }
+            _ => out.push(c),
+        }
+        i += 1;
+    }
+
+    remove_trailing_comma(&mut out);
+    while let Some(close) = stack.pop() {
+        remove_trailing_comma(&mut out);
+        if out.trim_end().ends_with(':') {
+            out.push_str("null");
+        }
+        out.push(close);
+    }
+
+    out
+}
+
+/// Copies the string starting at `chars[start]` to `out` as a double quoted JSON string
+/// and returns the index following it.
+fn copy_string(chars: &[char], start: usize, out: &mut String) -> usize {
+    let quote = chars[start];
+    let mut i = start + 1;
+    out.push('"');
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' => {
+                match chars.get(i + 1) {
+                    // \' is not a valid JSON escape
+                    Some('\'') => out.push('\''),
+                    Some(next) => {
+                        out.push('\\');
+                        out.push(*next);
+                    }
+                    None => {}
+                }
+                i += 2;
+                continue;
+            }
+            c if c == quote => {
+                out.push('"');
+                return i + 1;
+            }
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            _ => out.push(c),
+        }
+        i += 1;
+    }
+    out.push('"');
+    i
+}
+
+fn insert_missing_comma(out: &mut String, stack: &[char]) {
+    if stack.is_empty() {
+        return;
+    }
+    let ends_value = out
+        .trim_end()
+        .chars()
+        .last()
+        .is_some_and(|last| matches!(last, '"' | '}' | ']') || last.is_ascii_alphanumeric());
+    if ends_value {
+        out.push(',');
+    }
+}
+
+fn remove_trailing_comma(out: &mut String) {
+    let trimmed = out.trim_end();
+    if trimmed.ends_with(',') {
+        out.truncate(trimmed.len() - 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_json_parser_extracts_from_prose_and_fences() {
+        let parser = JsonParser::new();
+
+        let output =
+            "Here is the result:\n```json\n{\"name\": \"Ada\", \"age\": 36}\n```\nAnything else?";
+        let parsed: Value = serde_json::from_str(&parser.parse(output).await.unwrap()).unwrap();
+        assert_eq!(parsed, serde_json::json!({"name": "Ada", "age": 36}));
+
+        let output = "The answer is [1, 2, 3] as requested.";
+        assert_eq!(
+            parser.parse_value(output).unwrap(),
+            serde_json::json!([1, 2, 3])
+        );
+
+        assert!(parser.parse_value("no json here").is_err());
+    }
+
+    #[test]
+    fn test_json_parser_repairs_output() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Person {
+            name: String,
+            tags: Vec<String>,
+            active: bool,
+            note: Option<String>,
+        }
+
+        let output = r#"{
+  // the person
+  name: 'Ada "the countess" Lovelace',
+  'tags': ["math", "poetry",],
+  "active": True
+  "note": None,
+}"#;
+        let person: Person = JsonParser::new().parse_into(output).unwrap();
+        assert_eq!(
+            person,
+            Person {
+                name: "Ada \"the countess\" Lovelace".to_string(),
+                tags: vec!["math".to_string(), "poetry".to_string()],
+                active: true,
+                note: None,
+            }
+        );
+
+        assert!(JsonParser::new()
+            .with_repair(false)
+            .parse_value(output)
+            .is_err());
+    }
+
+    #[test]
+    fn test_repair_truncated_json() {
+        let repaired = repair_json(
+            r#"{"items": [{"id": 1}, {"id": 2, "text": "line one
+line two"#,
+        );
+        let value: Value = serde_json::from_str(&repaired).unwrap();
+        assert_eq!(value["items"][1]["text"], "line one\nline two");
+
+        let repaired = repair_json(r#"{"a": 1, "b":"#);
+        let value: Value = serde_json::from_str(&repaired).unwrap();
+        assert_eq!(value["b"], Value::Null);
+    }
+}
@@ -0,0 +1,166 @@
+use async_trait::async_trait;
+use regex::Regex;
+use serde_json::Value;
+
+use super::{OutputParser, OutputParserError};
+
+/// Parses a list of items separated by a delimiter, a comma by default.
+///
+/// Items are trimmed, surrounding quotes are removed and empty items are dropped. As an
+/// `OutputParser` it returns the items as a JSON array; use `parse_list` to get them as a
+/// `Vec<String>`.
+///
+/// # Usage
+/// ```rust,ignore
+/// let parser = DelimitedListParser::new();
+/// let prompt = format!("List three colors.\n{}", parser.format_instructions());
+/// let colors = parser.parse_list(&llm.invoke(&prompt).await?)?;
+/// ```
+pub struct DelimitedListParser {
+    delimiter: String,
+}
+
+impl DelimitedListParser {
+    pub fn new() -> Self {
+        Self {
+            delimiter: ",".to_string(),
+        }
+    }
+
+    pub fn with_delimiter<S: Into<String>>(mut self, delimiter: S) -> Self {
+        self.delimiter = delimiter.into();
+        self
+    }
+
+    /// Instructions to add to the prompt so the model answers with a delimited list.
+    pub fn format_instructions(&self) -> String {
+        match self.delimiter.as_str() {
+            "," => "Your response should be a list of comma separated values, eg: `foo, bar, baz`"
+                .to_string(),
+            delimiter => format!(
+                "Your response should be a list of values separated by `{0}`, eg: `foo{0}bar{0}baz`",
+                delimiter
+            ),
+        }
+    }
+
+    pub fn parse_list(&self, output: &str) -> Result<Vec<String>, OutputParserError> {
+        let items: Vec<String> = output
+            .trim()
+            .split(self.delimiter.as_str())
+            .map(clean_item)
+            .filter(|item| !item.is_empty())
+            .collect();
+        if items.is_empty() {
+            return Err(OutputParserError::ParsingError("No items found".into()));
+        }
+        Ok(items)
+    }
+}
+
+impl Default for DelimitedListParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl OutputParser for DelimitedListParser {
+    async fn parse(&self, output: &str) -> Result<String, OutputParserError> {
+        Ok(Value::from(self.parse_list(output)?).to_string())
+    }
+}
+
+/// Parses a numbered list such as `1. foo` or `2) bar`, one item per line. Lines that are
+/// not list items, like an introduction before the list, are ignored.
+///
+/// As an `OutputParser` it returns the items as a JSON array; use `parse_list` to get them
+/// as a `Vec<String>`.
+pub struct NumberedListParser {}
+
+impl NumberedListParser {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Instructions to add to the prompt so the model answers with a numbered list.
+    pub fn format_instructions(&self) -> String {
+        "Your response should be a numbered list with each item on a new line. For example: \n\n1. foo\n\n2. bar\n\n3. baz"
+            .to_string()
+    }
+
+    pub fn parse_list(&self, output: &str) -> Result<Vec<String>, OutputParserError> {
+        let re = Regex::new(r"^\s*\d+[.)]\s+(.+)$")?;
+        let items: Vec<String> = output
+            .lines()
+            .filter_map(|line| re.captures(line))
+            .map(|cap| clean_item(&cap[1]))
+            .filter(|item| !item.is_empty())
+            .collect();
+        if items.is_empty() {
+            return Err(OutputParserError::ParsingError(
+                "No numbered list found".into(),
+            ));
+        }
+        Ok(items)
+    }
+}
+
+impl Default for NumberedListParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl OutputParser for NumberedListParser {
+    async fn parse(&self, output: &str) -> Result<String, OutputParserError> {
+        Ok(Value::from(self.parse_list(output)?).to_string())
+    }
+}
+
+fn clean_item(item: &str) -> String {
+    let item = item.trim();
+    let unquoted = ['"', '\'', '`'].iter().find_map(|quote| {
+        item.strip_prefix(*quote)
+            .and_then(|rest| rest.strip_suffix(*quote))
+    });
+    unquoted.unwrap_or(item).trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_delimited_list_parser() {
+        let parser = DelimitedListParser::new();
+        assert_eq!(
+            parser.parse_list(" red, 'green' ,blue,\n").unwrap(),
+            vec!["red", "green", "blue"]
+        );
+        assert_eq!(
+            parser.parse("a, b").await.unwrap(),
+            r#"["a","b"]"#.to_string()
+        );
+
+        let parser = DelimitedListParser::new().with_delimiter(";");
+        assert!(parser.format_instructions().contains("foo;bar;baz"));
+        assert_eq!(
+            parser.parse_list("one, two; three").unwrap(),
+            vec!["one, two", "three"]
+        );
+        assert!(parser.parse_list("  ").is_err());
+    }
+
+    #[test]
+    fn test_numbered_list_parser() {
+        let parser = NumberedListParser::new();
+        let output = "Here are some ideas:\n\n1. Walk the dog\n2) \"Read a book\"\n  10. Cook dinner\nHope this helps!";
+        assert_eq!(
+            parser.parse_list(output).unwrap(),
+            vec!["Walk the dog", "Read a book", "Cook dinner"]
+        );
+        assert!(parser.parse_list("no list here").is_err());
+    }
+}
@@ -4,8 +4,26 @@ pub use output_parser::*;
 mod markdown_parser;
 pub use markdown_parser::*;
 
+mod enum_parser;
+pub use enum_parser::*;
+
+mod json_parser;
+pub use json_parser::*;
+
+mod structured_parser;
+pub use structured_parser::*;
+
+mod list_parser;
+pub use list_parser::*;
+
+mod output_fixing_parser;
+pub use output_fixing_parser::*;
+
 mod simple_parser;
 pub use simple_parser::*;
 
+mod xml_parser;
+pub use xml_parser::*;
+
 mod error;
 pub use error::*;
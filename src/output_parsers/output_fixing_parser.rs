@@ -0,0 +1,229 @@
+use async_trait::async_trait;
+
+use crate::language_models::llm::LLM;
+
+use super::{OutputParser, OutputParserError};
+
+const FIX_TEMPLATE: &str = "Instructions:
+--------------
+{instructions}
+--------------
+Completion:
+--------------
+{completion}
+--------------
+
+Above, the Completion did not satisfy the constraints given in the Instructions.
+Error:
+--------------
+{error}
+--------------
+
+Please try again. Please only respond with an answer that satisfies the constraints laid out in the Instructions:";
+
+const RETRY_TEMPLATE: &str = "Prompt:
+{prompt}
+Completion:
+{completion}
+
+Above, the Completion did not satisfy the constraints given in the Prompt.
+Details: {error}
+Please try again:";
+
+/// Wraps an output parser so that, when parsing fails, the malformed output and the error
+/// are sent to an LLM to fix, up to `max_retries` times, instead of failing the chain.
+///
+/// `parse` only shows the model the output, the error and the optional format
+/// instructions; `parse_with_prompt` also shows the original prompt, which helps when the
+/// output is incomplete rather than malformed.
+///
+/// # Usage
+/// ```rust,ignore
+/// let json = JsonParser::new();
+/// let parser = OutputFixingParser::new(json, OpenAI::default())
+///     .with_instructions("Answer with a JSON object with a `name` field.")
+///     .with_max_retries(2);
+/// let chain = LLMChainBuilder::new()
+///     .prompt(prompt)
+///     .llm(llm)
+///     .output_parser(parser)
+///     .build()?;
+/// ```
+pub struct OutputFixingParser {
+    parser: Box<dyn OutputParser>,
+    llm: Box<dyn LLM>,
+    instructions: Option<String>,
+    max_retries: usize,
+}
+
+impl OutputFixingParser {
+    pub fn new<P: Into<Box<dyn OutputParser>>, L: Into<Box<dyn LLM>>>(parser: P, llm: L) -> Self {
+        Self {
+            parser: parser.into(),
+            llm: llm.into(),
+            instructions: None,
+            max_retries: 1,
+        }
+    }
+
+    /// The format instructions the output should follow, shown to the model when fixing it.
+    pub fn with_instructions<S: Into<String>>(mut self, instructions: S) -> Self {
+        self.instructions = Some(instructions.into());
+        self
+    }
+
+    /// How many times the model is asked to fix the output before giving up.
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Parses `output`, asking the model to answer `prompt` again on failure.
+    pub async fn parse_with_prompt(
+        &self,
+        output: &str,
+        prompt: &str,
+    ) -> Result<String, OutputParserError> {
+        self.parse_with_fixes(output, |completion, error| {
+            RETRY_TEMPLATE
+                .replace("{prompt}", prompt)
+                .replace("{completion}", completion)
+                .replace("{error}", error)
+        })
+        .await
+    }
+
+    async fn parse_with_fixes<F>(
+        &self,
+        output: &str,
+        fix_prompt: F,
+    ) -> Result<String, OutputParserError>
+    where
+        F: Fn(&str, &str) -> String + Send + Sync,
+    {
+        let mut completion = output.to_string();
+        let mut retries = 0;
+        loop {
+            match self.parser.parse(&completion).await {
+                Ok(parsed) => return Ok(parsed),
+                Err(e) if retries < self.max_retries => {
+                    retries += 1;
+                    tracing::warn!(
+                        "Failed to parse output, asking the model to fix it ({}/{}): {}",
+                        retries,
+                        self.max_retries,
+                        e
+                    );
+                    completion = self
+                        .llm
+                        .invoke(&fix_prompt(&completion, &e.to_string()))
+                        .await?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl OutputParser for OutputFixingParser {
+    async fn parse(&self, output: &str) -> Result<String, OutputParserError> {
+        let instructions = self.instructions.as_deref().unwrap_or_default();
+        self.parse_with_fixes(output, |completion, error| {
+            FIX_TEMPLATE
+                .replace("{instructions}", instructions)
+                .replace("{completion}", completion)
+                .replace("{error}", error)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        pin::Pin,
+        sync::{Arc, Mutex},
+    };
+
+    use futures::Stream;
+
+    use crate::{
+        language_models::{GenerateResult, LLMError},
+        output_parsers::JsonParser,
+        schemas::{Message, StreamData},
+    };
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct ScriptedLLM {
+        responses: Arc<Mutex<Vec<String>>>,
+        prompts: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl ScriptedLLM {
+        fn new(responses: &[&str]) -> Self {
+            Self {
+                responses: Arc::new(Mutex::new(
+                    responses.iter().rev().map(|r| r.to_string()).collect(),
+                )),
+                prompts: Arc::new(Mutex::new(Vec::new())),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl LLM for ScriptedLLM {
+        async fn generate(&self, messages: &[Message]) -> Result<GenerateResult, LLMError> {
+            self.prompts
+                .lock()
+                .unwrap()
+                .push(messages[0].content.clone());
+            Ok(GenerateResult {
+                tokens: None,
+                generation: self.responses.lock().unwrap().pop().unwrap_or_default(),
+                ..Default::default()
+            })
+        }
+
+        async fn stream(
+            &self,
+            _messages: &[Message],
+        ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamData, LLMError>> + Send>>, LLMError>
+        {
+            unimplemented!()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_output_fixing_parser() {
+        let llm = ScriptedLLM::new(&["still broken", "{\"name\": \"Ada\"}"]);
+        let parser = OutputFixingParser::new(JsonParser::new(), llm.clone())
+            .with_instructions("Answer with a JSON object")
+            .with_max_retries(2);
+
+        let parsed = parser.parse("name is Ada").await.unwrap();
+        assert_eq!(parsed, r#"{"name":"Ada"}"#);
+
+        let prompts = llm.prompts.lock().unwrap();
+        assert_eq!(prompts.len(), 2);
+        assert!(prompts[0].contains("Answer with a JSON object"));
+        assert!(prompts[0].contains("name is Ada"));
+        assert!(prompts[1].contains("still broken"));
+    }
+
+    #[tokio::test]
+    async fn test_output_fixing_parser_gives_up() {
+        let llm = ScriptedLLM::new(&["nope"]);
+        let parser = OutputFixingParser::new(JsonParser::new(), llm.clone());
+
+        assert!(parser
+            .parse_with_prompt("no json", "Give me JSON")
+            .await
+            .is_err());
+        let prompts = llm.prompts.lock().unwrap();
+        assert_eq!(prompts.len(), 1);
+        assert!(prompts[0].contains("Give me JSON"));
+    }
+}
@@ -0,0 +1,335 @@
+use std::marker::PhantomData;
+
+use async_trait::async_trait;
+use schemars::{schema_for, JsonSchema};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use super::{FieldError, JsonParser, OutputParser, OutputParserError};
+
+/// Parses model output into `T`, using the JSON schema of `T` both to tell the model what
+/// to produce and to validate what it produced.
+///
+/// Put `format_instructions` in the prompt, then call `parse_typed` on the response. The
+/// JSON is extracted and repaired like `JsonParser` does, then validated against the schema;
+/// every mismatch is reported in `OutputParserError::ValidationError` with the path of the
+/// offending field, e.g. `address.city: missing required field`.
+///
+/// # Usage
+/// ```rust,ignore
+/// #[derive(Deserialize, JsonSchema)]
+/// struct Movie {
+///     title: String,
+///     year: u32,
+/// }
+///
+/// let parser = StructuredOutputParser::<Movie>::new();
+/// let prompt = format!("Name a movie.\n{}", parser.format_instructions());
+/// let movie = parser.parse_typed(&llm.invoke(&prompt).await?)?;
+/// ```
+pub struct StructuredOutputParser<T> {
+    schema: Value,
+    json_parser: JsonParser,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned + JsonSchema> StructuredOutputParser<T> {
+    pub fn new() -> Self {
+        Self {
+            schema: schema_for!(T).to_value(),
+            json_parser: JsonParser::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Repairs common JSON syntax errors before validating (the default).
+    pub fn with_repair(mut self, repair: bool) -> Self {
+        self.json_parser = self.json_parser.with_repair(repair);
+        self
+    }
+
+    pub fn schema(&self) -> &Value {
+        &self.schema
+    }
+
+    /// Instructions to add to the prompt so the model answers with JSON matching the schema.
+    pub fn format_instructions(&self) -> String {
+        let mut schema = self.schema.clone();
+        if let Some(schema) = schema.as_object_mut() {
+            schema.remove("$schema");
+        }
+        format!(
+            "The output should be formatted as a JSON instance that conforms to the JSON schema below.\n\n\
+             As an example, for the schema {{\"properties\": {{\"foo\": {{\"type\": \"array\", \"items\": {{\"type\": \"string\"}}}}}}, \"required\": [\"foo\"]}}\n\
+             the object {{\"foo\": [\"bar\", \"baz\"]}} is a well-formatted instance of the schema. \
+             The object {{\"properties\": {{\"foo\": [\"bar\", \"baz\"]}}}} is not well-formatted.\n\n\
+             Here is the output schema:\n```json\n{}\n```",
+            schema
+        )
+    }
+
+    pub fn parse_typed(&self, output: &str) -> Result<T, OutputParserError> {
+        let value = self.parse_validated(output)?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    fn parse_validated(&self, output: &str) -> Result<Value, OutputParserError> {
+        let value = self.json_parser.parse_value(output)?;
+        let mut errors = Vec::new();
+        validate(&value, &self.schema, &self.schema, "", &mut errors);
+        if errors.is_empty() {
+            Ok(value)
+        } else {
+            Err(OutputParserError::ValidationError(errors))
+        }
+    }
+}
+
+impl<T: DeserializeOwned + JsonSchema> Default for StructuredOutputParser<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl<T: DeserializeOwned + JsonSchema> OutputParser for StructuredOutputParser<T> {
+    async fn parse(&self, output: &str) -> Result<String, OutputParserError> {
+        let value = self.parse_validated(output)?;
+        serde_json::from_value::<T>(value.clone())?;
+        Ok(value.to_string())
+    }
+}
+
+/// Validates `value` against the subset of JSON schema generated by schemars, pushing one
+/// error per mismatching field.
+fn validate(value: &Value, schema: &Value, root: &Value, path: &str, errors: &mut Vec<FieldError>) {
+    let Some(schema) = schema.as_object() else {
+        // `true` accepts anything, `false` nothing
+        if schema == &Value::Bool(false) {
+            errors.push(FieldError::new(path, "no value is allowed here"));
+        }
+        return;
+    };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let pointer = reference.trim_start_matches('#');
+        if let Some(resolved) = root.pointer(pointer) {
+            validate(value, resolved, root, path, errors);
+        }
+    }
+
+    for subschema in schema
+        .get("allOf")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        validate(value, subschema, root, path, errors);
+    }
+
+    for key in ["anyOf", "oneOf"] {
+        if let Some(subschemas) = schema.get(key).and_then(Value::as_array) {
+            let matches = subschemas.iter().any(|subschema| {
+                let mut sub_errors = Vec::new();
+                validate(value, subschema, root, path, &mut sub_errors);
+                sub_errors.is_empty()
+            });
+            if !matches {
+                errors.push(FieldError::new(
+                    path,
+                    format!("{value} does not match any of the allowed schemas"),
+                ));
+            }
+        }
+    }
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        if !types.is_empty() && !types.iter().any(|t| has_type(value, t)) {
+            errors.push(FieldError::new(
+                path,
+                format!("expected {}, got {}", types.join(" or "), type_name(value)),
+            ));
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            let allowed = allowed
+                .iter()
+                .map(Value::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            errors.push(FieldError::new(
+                path,
+                format!("{value} is not one of {allowed}"),
+            ));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            errors.push(FieldError::new(path, format!("expected {expected}")));
+        }
+    }
+
+    if let Some(number) = value.as_f64() {
+        if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64) {
+            if number < minimum {
+                errors.push(FieldError::new(path, format!("must be at least {minimum}")));
+            }
+        }
+        if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64) {
+            if number > maximum {
+                errors.push(FieldError::new(path, format!("must be at most {maximum}")));
+            }
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        for required in schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            if !object.contains_key(required) {
+                errors.push(FieldError::new(
+                    &join_path(path, required),
+                    "missing required field",
+                ));
+            }
+        }
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (key, field) in object {
+            let field_path = join_path(path, key);
+            match properties.and_then(|properties| properties.get(key)) {
+                Some(field_schema) => validate(field, field_schema, root, &field_path, errors),
+                None => match schema.get("additionalProperties") {
+                    Some(Value::Bool(false)) => {
+                        errors.push(FieldError::new(&field_path, "unknown field"))
+                    }
+                    Some(additional) => validate(field, additional, root, &field_path, errors),
+                    None => {}
+                },
+            }
+        }
+    }
+
+    if let (Some(items), Some(item_schema)) = (value.as_array(), schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            validate(item, item_schema, root, &format!("{path}[{i}]"), errors);
+        }
+    }
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Deserialize, JsonSchema, Debug, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Genre {
+        Drama,
+        Comedy,
+    }
+
+    #[derive(Deserialize, JsonSchema, Debug, PartialEq)]
+    struct Cast {
+        name: String,
+        age: Option<u32>,
+    }
+
+    #[derive(Deserialize, JsonSchema, Debug, PartialEq)]
+    struct Movie {
+        title: String,
+        year: u32,
+        genre: Genre,
+        cast: Vec<Cast>,
+    }
+
+    #[test]
+    fn test_structured_output_parser() {
+        let parser = StructuredOutputParser::<Movie>::new();
+        assert!(parser.format_instructions().contains("\"title\""));
+
+        let movie = parser
+            .parse_typed(
+                "```json\n{\"title\": \"Up\", \"year\": 2009, \"genre\": \"comedy\", \"cast\": [{\"name\": \"Ed\", \"age\": null}]}\n```",
+            )
+            .unwrap();
+        assert_eq!(
+            movie,
+            Movie {
+                title: "Up".to_string(),
+                year: 2009,
+                genre: Genre::Comedy,
+                cast: vec![Cast {
+                    name: "Ed".to_string(),
+                    age: None
+                }],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_structured_output_parser_field_errors() {
+        let parser = StructuredOutputParser::<Movie>::new();
+        let error = parser
+            .parse(r#"{"year": -1, "genre": "horror", "cast": [{"age": "old"}]}"#)
+            .await
+            .unwrap_err();
+
+        let OutputParserError::ValidationError(errors) = error else {
+            panic!("expected a validation error, got {error:?}");
+        };
+        let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert!(paths.contains(&"title"));
+        assert!(paths.contains(&"year"));
+        assert!(paths.contains(&"genre"));
+        assert!(paths.contains(&"cast[0].name"));
+        assert!(paths.contains(&"cast[0].age"));
+    }
+}
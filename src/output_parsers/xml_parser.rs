@@ -0,0 +1,249 @@
+use async_trait::async_trait;
+use regex::Regex;
+use serde_json::{Map, Value};
+
+use super::{OutputParser, OutputParserError};
+
+/// Parses the XML tags of model output into a JSON value, for prompt styles that ask the
+/// model to answer in XML rather than JSON.
+///
+/// Elements holding only text become strings, other elements become objects keyed by
+/// child tag, and repeated tags become arrays. Attributes are kept under `@name` keys and
+/// the text of an element that also has children under `#text`. The parser is lenient:
+/// prose and code fences around the XML are ignored and unclosed tags are closed.
+///
+/// # Usage
+/// ```rust,ignore
+/// let parser = XmlParser::new().with_tags(vec!["answer", "reasoning"]);
+/// let value = parser.parse_value("<answer>42</answer><reasoning>It is.</reasoning>")?;
+/// assert_eq!(value["answer"], "42");
+/// ```
+pub struct XmlParser {
+    tags: Vec<String>,
+}
+
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<(String, Value)>,
+    text: String,
+}
+
+impl Element {
+    fn new(name: &str, attributes: Vec<(String, String)>) -> Self {
+        Self {
+            name: name.to_string(),
+            attributes,
+            children: Vec::new(),
+            text: String::new(),
+        }
+    }
+
+    fn into_value(self) -> Value {
+        let text = html_escape::decode_html_entities(self.text.trim()).to_string();
+        if self.children.is_empty() && self.attributes.is_empty() {
+            return Value::String(text);
+        }
+
+        let mut object = Map::new();
+        for (name, value) in self.attributes {
+            object.insert(format!("@{name}"), Value::String(value));
+        }
+        for (name, value) in self.children {
+            insert_child(&mut object, name, value);
+        }
+        if !text.is_empty() {
+            object.insert("#text".to_string(), Value::String(text));
+        }
+        Value::Object(object)
+    }
+}
+
+impl XmlParser {
+    pub fn new() -> Self {
+        Self { tags: Vec::new() }
+    }
+
+    /// Sets the tags the model should use, listed in `format_instructions`.
+    pub fn with_tags<S: Into<String>>(mut self, tags: Vec<S>) -> Self {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Instructions to add to the prompt so the model answers in XML.
+    pub fn format_instructions(&self) -> String {
+        let mut instructions = String::from(
+            "The output should be formatted as XML. Wrap each value in an opening and a closing tag, \
+             e.g. <answer>foo</answer>, and nest tags to group related values.",
+        );
+        if !self.tags.is_empty() {
+            instructions.push_str(&format!(
+                " Use the following tags: {}.",
+                self.tags
+                    .iter()
+                    .map(|tag| format!("<{tag}>"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        instructions.push_str(" Do not add any text outside of the tags.");
+        instructions
+    }
+
+    pub fn parse_value(&self, output: &str) -> Result<Value, OutputParserError> {
+        let fence = Regex::new(r"```(?:xml)?\s*([\s\S]*?)\s*```")?;
+        let text = match fence.captures(output).and_then(|cap| cap.get(1)) {
+            Some(block) => block.as_str(),
+            None => output,
+        };
+        let ignored = Regex::new(r"(?s)<\?.*?\?>|<!--.*?-->|<!\[CDATA\[|\]\]>")?;
+        let text = ignored.replace_all(text, "");
+        let tag = Regex::new(
+            r#"<(/?)([A-Za-z_][\w.:-]*)((?:\s+[^\s=>/]+\s*=\s*(?:"[^"]*"|'[^']*'))*)\s*(/?)>"#,
+        )?;
+        let attribute = Regex::new(r#"([^\s=]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#)?;
+
+        let mut root = Element::new("", Vec::new());
+        let mut stack: Vec<Element> = Vec::new();
+        let mut last = 0;
+        for cap in tag.captures_iter(&text) {
+            let whole = cap.get(0).unwrap();
+            if let Some(open) = stack.last_mut() {
+                open.text.push_str(&text[last..whole.start()]);
+            }
+            last = whole.end();
+
+            let name = &cap[2];
+            if &cap[1] == "/" {
+                // Close the matching element and any unclosed element inside it
+                if let Some(position) = stack.iter().rposition(|element| element.name == name) {
+                    while stack.len() > position {
+                        close_element(&mut stack, &mut root);
+                    }
+                }
+                continue;
+            }
+
+            let attributes = attribute
+                .captures_iter(&cap[3])
+                .map(|attr| {
+                    let value = attr.get(2).or(attr.get(3)).map_or("", |v| v.as_str());
+                    (
+                        attr[1].to_string(),
+                        html_escape::decode_html_entities(value).to_string(),
+                    )
+                })
+                .collect();
+            stack.push(Element::new(name, attributes));
+            if &cap[4] == "/" {
+                close_element(&mut stack, &mut root);
+            }
+        }
+        if let Some(open) = stack.last_mut() {
+            open.text.push_str(&text[last..]);
+        }
+        while !stack.is_empty() {
+            close_element(&mut stack, &mut root);
+        }
+
+        if root.children.is_empty() {
+            return Err(OutputParserError::ParsingError("No XML tags found".into()));
+        }
+        let mut object = Map::new();
+        for (name, value) in root.children {
+            insert_child(&mut object, name, value);
+        }
+        Ok(Value::Object(object))
+    }
+}
+
+impl Default for XmlParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl OutputParser for XmlParser {
+    async fn parse(&self, output: &str) -> Result<String, OutputParserError> {
+        Ok(self.parse_value(output)?.to_string())
+    }
+}
+
+fn close_element(stack: &mut Vec<Element>, root: &mut Element) {
+    if let Some(element) = stack.pop() {
+        let name = element.name.clone();
+        let parent = stack.last_mut().unwrap_or(root);
+        parent.children.push((name, element.into_value()));
+    }
+}
+
+fn insert_child(object: &mut Map<String, Value>, name: String, value: Value) {
+    match object.get_mut(&name) {
+        Some(Value::Array(values)) => values.push(value),
+        Some(existing) => {
+            let first = existing.take();
+            *existing = Value::Array(vec![first, value]);
+        }
+        None => {
+            object.insert(name, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_xml_parser() {
+        let parser = XmlParser::new().with_tags(vec!["movies", "movie"]);
+        assert!(parser.format_instructions().contains("<movies>, <movie>"));
+
+        let output = r#"Sure, here you go:
+```xml
+<?xml version="1.0"?>
+<movies>
+  <movie id="1"><title>Up</title><year>2009</year></movie>
+  <movie id="2"><title>Heat &amp; Dust</title></movie>
+  <!-- that's all -->
+</movies>
+```"#;
+        assert_eq!(
+            parser.parse_value(output).unwrap(),
+            json!({
+                "movies": {
+                    "movie": [
+                        {"@id": "1", "title": "Up", "year": "2009"},
+                        {"@id": "2", "title": "Heat & Dust"}
+                    ]
+                }
+            })
+        );
+
+        assert_eq!(
+            parser
+                .parse("<answer>42</answer> <reasoning>Because</reasoning>")
+                .await
+                .unwrap(),
+            r#"{"answer":"42","reasoning":"Because"}"#
+        );
+    }
+
+    #[test]
+    fn test_xml_parser_lenient() {
+        let parser = XmlParser::new();
+        assert_eq!(
+            parser
+                .parse_value("<result><item>a</item><item>b<br/></result><empty/>")
+                .unwrap(),
+            json!({
+                "result": {"item": ["a", {"br": "", "#text": "b"}]},
+                "empty": ""
+            })
+        );
+        assert!(parser.parse_value("no tags at all").is_err());
+    }
+}
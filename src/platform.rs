@@ -0,0 +1,60 @@
+//! Helpers over the differences between native and `wasm32` targets.
+//!
+//! On `wasm32` the futures and streams of `reqwest` wrap browser promises, which are not
+//! `Send`, while the traits of this crate require `Send` futures. `wasm32` runs on a single
+//! thread, so `send` and `send_stream` mark them `Send` there, and are no-ops natively.
+
+use std::future::Future;
+
+use futures::Stream;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn send<F: Future + Send>(future: F) -> F {
+    future
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn send<F: Future>(future: F) -> send_wrapper::SendWrapper<F> {
+    send_wrapper::SendWrapper::new(future)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn send_stream<S: Stream + Send>(stream: S) -> S {
+    stream
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn send_stream<S: Stream>(stream: S) -> send_wrapper::SendWrapper<S> {
+    send_wrapper::SendWrapper::new(stream)
+}
+
+/// Runs `future` to completion on a runtime shared by the blocking methods, such as
+/// `Chain::invoke_blocking`, so callers without an async runtime need not set one up.
+///
+/// # Panics
+///
+/// Panics when called from within an async runtime, where the async method should be awaited.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    static RUNTIME: std::sync::OnceLock<tokio::runtime::Runtime> = std::sync::OnceLock::new();
+    RUNTIME
+        .get_or_init(|| {
+            tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .expect("failed to build the runtime of the blocking methods")
+        })
+        .block_on(future)
+}
+
+/// Runs a background task, on the Tokio runtime natively and on the browser event loop on
+/// `wasm32`.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn spawn<F: Future<Output = ()> + Send + 'static>(future: F) {
+    tokio::spawn(future);
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn spawn<F: Future<Output = ()> + 'static>(future: F) {
+    wasm_bindgen_futures::spawn_local(future);
+}
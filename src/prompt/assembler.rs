@@ -0,0 +1,264 @@
+use std::sync::Arc;
+
+use tiktoken_rs::{cl100k_base_singleton, get_bpe_from_model, model::get_context_size};
+
+use crate::schemas::Message;
+
+use super::{LengthFunction, PromptError};
+
+/// The end of a section its items are dropped from when it is trimmed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrimFrom {
+    Start,
+    End,
+}
+
+/// A part of a prompt assembled by `PromptAssembler`, made of items such as examples,
+/// retrieved documents or history messages, which are dropped one at a time when the prompt
+/// does not fit.
+#[derive(Debug, Clone)]
+pub struct PromptSection {
+    name: String,
+    items: Vec<String>,
+    separator: String,
+    priority: u32,
+    trim_from: TrimFrom,
+    required: bool,
+}
+
+impl PromptSection {
+    /// A section made of a single item, dropped as a whole.
+    pub fn new<S: Into<String>, T: Into<String>>(name: S, text: T) -> Self {
+        Self::from_items(name, vec![text.into()])
+    }
+
+    /// A section whose items are dropped from the end first.
+    pub fn from_items<S: Into<String>>(name: S, items: Vec<String>) -> Self {
+        Self {
+            name: name.into(),
+            items,
+            separator: "\n".to_string(),
+            priority: 0,
+            trim_from: TrimFrom::End,
+            required: false,
+        }
+    }
+
+    /// A section of chat messages, formatted as `Message::messages_to_string` does, whose
+    /// oldest messages are dropped first.
+    pub fn from_messages<S: Into<String>>(name: S, messages: &[Message]) -> Self {
+        let items = messages
+            .iter()
+            .map(|message| Message::messages_to_string(std::slice::from_ref(message)))
+            .collect();
+        Self::from_items(name, items).with_trim_from(TrimFrom::Start)
+    }
+
+    /// Sections of lower priority are trimmed first, 0 by default.
+    pub fn with_priority(mut self, priority: u32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets the string joining the items of the section, `"\n"` by default.
+    pub fn with_separator<S: Into<String>>(mut self, separator: S) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    pub fn with_trim_from(mut self, trim_from: TrimFrom) -> Self {
+        self.trim_from = trim_from;
+        self
+    }
+
+    /// Never trims the section; assembling fails if the required sections do not fit.
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Assembles a prompt from sections, trimming the sections of lowest priority until it fits
+/// in a token budget, so long histories or retrieved contexts shrink instead of exceeding
+/// the context window of the model.
+///
+/// Tokens are counted with the `cl100k_base` tokenizer by default, or the tokenizer of the
+/// model given to `for_model`. Empty sections are left out.
+///
+/// # Usage
+/// ```rust,ignore
+/// let prompt = PromptAssembler::for_model("gpt-4o", 1024)?
+///     .with_section(PromptSection::new("system", system_prompt).required())
+///     .with_section(PromptSection::from_items("context", documents).with_priority(2))
+///     .with_section(PromptSection::from_messages("history", &history).with_priority(1))
+///     .with_section(PromptSection::new("question", question).required())
+///     .assemble()?;
+/// ```
+pub struct PromptAssembler {
+    sections: Vec<PromptSection>,
+    max_tokens: usize,
+    separator: String,
+    length_function: LengthFunction,
+}
+
+impl PromptAssembler {
+    pub fn new(max_tokens: usize) -> Self {
+        Self {
+            sections: Vec::new(),
+            max_tokens,
+            separator: "\n\n".to_string(),
+            length_function: Arc::new(|text| {
+                cl100k_base_singleton()
+                    .lock()
+                    .encode_with_special_tokens(text)
+                    .len()
+            }),
+        }
+    }
+
+    /// Uses the context window and tokenizer of `model`, keeping `reserved_tokens` of the
+    /// window for the completion.
+    pub fn for_model(model: &str, reserved_tokens: usize) -> Result<Self, PromptError> {
+        let bpe = get_bpe_from_model(model).map_err(|e| PromptError::OtherError(e.to_string()))?;
+        let max_tokens = get_context_size(model).saturating_sub(reserved_tokens);
+        Ok(Self::new(max_tokens)
+            .with_length_function(move |text| bpe.encode_with_special_tokens(text).len()))
+    }
+
+    pub fn with_length_function<F>(mut self, length_function: F) -> Self
+    where
+        F: Fn(&str) -> usize + Send + Sync + 'static,
+    {
+        self.length_function = Arc::new(length_function);
+        self
+    }
+
+    /// Sets the string joining the sections, `"\n\n"` by default.
+    pub fn with_separator<S: Into<String>>(mut self, separator: S) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    pub fn with_section(mut self, section: PromptSection) -> Self {
+        self.sections.push(section);
+        self
+    }
+
+    pub fn count_tokens(&self, text: &str) -> usize {
+        (self.length_function)(text)
+    }
+
+    /// Assembles the sections in the order they were added, dropping items of the sections
+    /// of lowest priority, the last added first on ties, until the prompt fits.
+    pub fn assemble(&self) -> Result<String, PromptError> {
+        let mut items: Vec<Vec<String>> = self
+            .sections
+            .iter()
+            .map(|section| section.items.clone())
+            .collect();
+
+        loop {
+            let prompt = self.render(&items);
+            let tokens = self.count_tokens(&prompt);
+            if tokens <= self.max_tokens {
+                return Ok(prompt);
+            }
+
+            let trimmed = self
+                .sections
+                .iter()
+                .enumerate()
+                .filter(|(i, section)| !section.required && !items[*i].is_empty())
+                .min_by_key(|(i, section)| (section.priority, std::cmp::Reverse(*i)));
+            let Some((i, section)) = trimmed else {
+                return Err(PromptError::TokenLimitExceeded {
+                    tokens,
+                    max_tokens: self.max_tokens,
+                });
+            };
+            tracing::debug!(
+                "Prompt has {} tokens, more than {}, trimming section `{}`",
+                tokens,
+                self.max_tokens,
+                section.name
+            );
+            match section.trim_from {
+                TrimFrom::Start => items[i].remove(0),
+                TrimFrom::End => items[i].pop().unwrap_or_default(),
+            };
+        }
+    }
+
+    fn render(&self, items: &[Vec<String>]) -> String {
+        self.sections
+            .iter()
+            .zip(items)
+            .filter(|(_, items)| !items.is_empty())
+            .map(|(section, items)| items.join(&section.separator))
+            .collect::<Vec<_>>()
+            .join(&self.separator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+
+    #[test]
+    fn test_prompt_assembler() {
+        let history = vec![
+            Message::new_human_message("first question"),
+            Message::new_ai_message("first answer"),
+            Message::new_human_message("second question"),
+        ];
+        let assembler = |max_tokens| {
+            PromptAssembler::new(max_tokens)
+                .with_length_function(words)
+                .with_section(PromptSection::new("system", "You are helpful").required())
+                .with_section(
+                    PromptSection::from_items(
+                        "context",
+                        vec!["doc one".to_string(), "doc two".to_string()],
+                    )
+                    .with_priority(2),
+                )
+                .with_section(PromptSection::from_messages("history", &history).with_priority(1))
+                .with_section(PromptSection::new("question", "Why?").required())
+        };
+
+        assert_eq!(
+            assembler(100).assemble().unwrap(),
+            "You are helpful\n\ndoc one\ndoc two\n\nHumanMessage: first question\nAIMessage: first answer\nHumanMessage: second question\n\nWhy?"
+        );
+        assert_eq!(
+            assembler(12).assemble().unwrap(),
+            "You are helpful\n\ndoc one\ndoc two\n\nHumanMessage: second question\n\nWhy?"
+        );
+        assert_eq!(
+            assembler(6).assemble().unwrap(),
+            "You are helpful\n\ndoc one\n\nWhy?"
+        );
+        assert!(matches!(
+            assembler(3).assemble(),
+            Err(PromptError::TokenLimitExceeded {
+                tokens: 4,
+                max_tokens: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn test_prompt_assembler_for_model() {
+        let assembler = PromptAssembler::for_model("gpt-4", 1000).unwrap();
+        assert_eq!(assembler.max_tokens, 8192 - 1000);
+        assert_eq!(assembler.count_tokens("hello world"), 2);
+    }
+}
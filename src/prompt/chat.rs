@@ -27,7 +27,7 @@ impl HumanMessagePromptTemplate {
 impl MessageFormatter for HumanMessagePromptTemplate {
     fn format_messages(&self, input_variables: PromptArgs) -> Result<Vec<Message>, PromptError> {
         let message = Message::new_human_message(self.prompt.format(input_variables)?);
-        log::debug!("message: {:?}", message);
+        tracing::debug!("message: {:?}", message);
         Ok(vec![message])
     }
     fn input_variables(&self) -> Vec<String> {
@@ -80,7 +80,7 @@ impl FormatPrompter for SystemMessagePromptTemplate {
 impl MessageFormatter for SystemMessagePromptTemplate {
     fn format_messages(&self, input_variables: PromptArgs) -> Result<Vec<Message>, PromptError> {
         let message = Message::new_system_message(self.prompt.format(input_variables)?);
-        log::debug!("message: {:?}", message);
+        tracing::debug!("message: {:?}", message);
         Ok(vec![message])
     }
     fn input_variables(&self) -> Vec<String> {
@@ -116,7 +116,7 @@ impl FormatPrompter for AIMessagePromptTemplate {
 impl MessageFormatter for AIMessagePromptTemplate {
     fn format_messages(&self, input_variables: PromptArgs) -> Result<Vec<Message>, PromptError> {
         let message = Message::new_ai_message(self.prompt.format(input_variables)?);
-        log::debug!("message: {:?}", message);
+        tracing::debug!("message: {:?}", message);
         Ok(vec![message])
     }
     fn input_variables(&self) -> Vec<String> {
@@ -188,11 +188,60 @@ pub struct MessageFormatterStruct {
     items: Vec<MessageOrTemplate>,
 }
 
+/// A chat prompt: a list of fixed messages, message templates and placeholders filled with
+/// the messages of an input variable, such as the conversation history or the agent
+/// scratchpad.
+///
+/// # Usage
+/// ```rust,ignore
+/// let prompt = ChatPromptTemplate::new()
+///     .with_system_template(template_fstring!("You are a {role}", "role"))
+///     .with_messages_placeholder("chat_history")
+///     .with_human_template(template_fstring!("{input}", "input"));
+///
+/// let messages = prompt.format_messages(prompt_args! {
+///     "role" => "helpful assistant",
+///     "chat_history" => memory.messages(),
+///     "input" => "Hi",
+/// })?;
+/// ```
+pub type ChatPromptTemplate = MessageFormatterStruct;
+
 impl MessageFormatterStruct {
     pub fn new() -> Self {
         Self { items: Vec::new() }
     }
 
+    pub fn with_message(mut self, message: Message) -> Self {
+        self.add_message(message);
+        self
+    }
+
+    pub fn with_template<T: Into<Box<dyn MessageFormatter>>>(mut self, template: T) -> Self {
+        self.add_template(template.into());
+        self
+    }
+
+    pub fn with_system_template(self, template: PromptTemplate) -> Self {
+        self.with_template(SystemMessagePromptTemplate::new(template))
+    }
+
+    pub fn with_human_template(self, template: PromptTemplate) -> Self {
+        self.with_template(HumanMessagePromptTemplate::new(template))
+    }
+
+    pub fn with_ai_template(self, template: PromptTemplate) -> Self {
+        self.with_template(AIMessagePromptTemplate::new(template))
+    }
+
+    /// Adds the messages of the input variable `placeholder`, which must be a list of
+    /// messages.
+    pub fn with_messages_placeholder<S: Into<String>>(mut self, placeholder: S) -> Self {
+        self.items
+            .push(MessageOrTemplate::MessagesPlaceholder(placeholder.into()));
+        self
+    }
+
     pub fn add_message(&mut self, message: Message) {
         self.items.push(MessageOrTemplate::Message(message));
     }
@@ -216,8 +265,10 @@ impl MessageFormatterStruct {
                     result.extend(tmpl.format_messages(input_variables.clone())?)
                 }
                 MessageOrTemplate::MessagesPlaceholder(placeholder) => {
-                    let messages = input_variables[placeholder].clone();
-                    result.extend(Message::messages_from_value(&messages)?);
+                    let messages = input_variables
+                        .get(placeholder)
+                        .ok_or_else(|| PromptError::MissingVariables(vec![placeholder.clone()]))?;
+                    result.extend(Message::messages_from_value(messages)?);
                 }
             }
         }
@@ -225,6 +276,12 @@ impl MessageFormatterStruct {
     }
 }
 
+impl Default for MessageFormatterStruct {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MessageFormatter for MessageFormatterStruct {
     fn format_messages(&self, input_variables: PromptArgs) -> Result<Vec<Message>, PromptError> {
         self.format(input_variables)
@@ -297,12 +354,62 @@ macro_rules! message_formatter {
 mod tests {
     use crate::{
         message_formatter,
-        prompt::{chat::AIMessagePromptTemplate, FormatPrompter},
+        prompt::{
+            chat::AIMessagePromptTemplate, ChatPromptTemplate, FormatPrompter, MessageFormatter,
+            PromptError,
+        },
         prompt_args,
-        schemas::messages::Message,
+        schemas::messages::{Message, MessageType},
         template_fstring,
     };
 
+    #[test]
+    fn test_chat_prompt_template() {
+        let prompt = ChatPromptTemplate::new()
+            .with_system_template(template_fstring!("You are a {role}", "role"))
+            .with_messages_placeholder("chat_history")
+            .with_human_template(template_fstring!("{input}", "input"))
+            .with_ai_template(template_fstring!("Let me think about {input}", "input"));
+        assert_eq!(
+            prompt.input_variables(),
+            vec!["role", "chat_history", "input", "input"]
+        );
+
+        let messages = prompt
+            .format_messages(prompt_args! {
+                "role" => "pirate",
+                "chat_history" => vec![
+                    Message::new_human_message("Hello"),
+                    Message::new_ai_message("Ahoy"),
+                ],
+                "input" => "the sea",
+            })
+            .unwrap();
+        let messages: Vec<(MessageType, &str)> = messages
+            .iter()
+            .map(|message| (message.message_type.clone(), message.content.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                (MessageType::SystemMessage, "You are a pirate"),
+                (MessageType::HumanMessage, "Hello"),
+                (MessageType::AIMessage, "Ahoy"),
+                (MessageType::HumanMessage, "the sea"),
+                (MessageType::AIMessage, "Let me think about the sea"),
+            ]
+        );
+
+        let result = prompt.format_messages(prompt_args! {
+            "role" => "pirate",
+            "input" => "the sea",
+        });
+        assert!(matches!(
+            result,
+            Err(PromptError::MissingVariables(missing)) if missing == vec!["chat_history"]
+        ));
+    }
+
     #[test]
     fn test_message_formatter_macro() {
         // Create a human message and system message
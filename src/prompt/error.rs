@@ -6,9 +6,21 @@ pub enum PromptError {
     #[error("Variable {0} is missing from input variables")]
     MissingVariable(String),
 
+    #[error("Missing input variables: {}", .0.join(", "))]
+    MissingVariables(Vec<String>),
+
     #[error("Serialization error: {0}")]
     SerializationError(#[from] SerdeJsonError),
 
+    #[error("Prompt needs {tokens} tokens, more than the {max_tokens} available")]
+    TokenLimitExceeded { tokens: usize, max_tokens: usize },
+
+    #[error("Invalid prompt template: {0}")]
+    InvalidTemplate(String),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
     #[error("Error: {0}")]
     OtherError(String),
 }
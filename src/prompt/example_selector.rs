@@ -0,0 +1,306 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::Value;
+use tiktoken_rs::cl100k_base_singleton;
+
+use crate::{
+    schemas::Document,
+    vectorstore::{VecStoreOptions, VectorStore},
+};
+
+use super::{PromptArgs, PromptError, PromptFromatter, PromptTemplate};
+
+/// Picks the examples a `FewShotPromptTemplate` includes for a given input.
+#[async_trait]
+pub trait ExampleSelector: Send + Sync {
+    async fn select_examples(
+        &self,
+        input_variables: &PromptArgs,
+    ) -> Result<Vec<PromptArgs>, PromptError>;
+}
+
+/// Counts the tokens of a text.
+pub type LengthFunction = Arc<dyn Fn(&str) -> usize + Send + Sync>;
+
+/// Selects examples, in order, until the next one would not fit in a token budget shared
+/// with the input, so long inputs get fewer examples.
+///
+/// Lengths are counted with the `cl100k_base` tokenizer by default.
+///
+/// # Usage
+/// ```rust,ignore
+/// let selector = LengthBasedExampleSelector::new(
+///     examples,
+///     template_fstring!("Input: {input}\nOutput: {output}", "input", "output"),
+///     500,
+/// );
+/// ```
+pub struct LengthBasedExampleSelector {
+    examples: Vec<PromptArgs>,
+    example_prompt: PromptTemplate,
+    max_tokens: usize,
+    length_function: LengthFunction,
+}
+
+impl LengthBasedExampleSelector {
+    pub fn new(
+        examples: Vec<PromptArgs>,
+        example_prompt: PromptTemplate,
+        max_tokens: usize,
+    ) -> Self {
+        Self {
+            examples,
+            example_prompt,
+            max_tokens,
+            length_function: Arc::new(|text| {
+                cl100k_base_singleton()
+                    .lock()
+                    .encode_with_special_tokens(text)
+                    .len()
+            }),
+        }
+    }
+
+    pub fn with_length_function<F>(mut self, length_function: F) -> Self
+    where
+        F: Fn(&str) -> usize + Send + Sync + 'static,
+    {
+        self.length_function = Arc::new(length_function);
+        self
+    }
+
+    pub fn add_example(&mut self, example: PromptArgs) {
+        self.examples.push(example);
+    }
+}
+
+#[async_trait]
+impl ExampleSelector for LengthBasedExampleSelector {
+    async fn select_examples(
+        &self,
+        input_variables: &PromptArgs,
+    ) -> Result<Vec<PromptArgs>, PromptError> {
+        let input = input_variables
+            .values()
+            .map(value_to_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut remaining = self
+            .max_tokens
+            .saturating_sub((self.length_function)(&input));
+
+        let mut selected = Vec::new();
+        for example in &self.examples {
+            let length = (self.length_function)(&self.example_prompt.format(example.clone())?);
+            if length > remaining {
+                break;
+            }
+            remaining -= length;
+            selected.push(example.clone());
+        }
+        Ok(selected)
+    }
+}
+
+/// Stores examples in a `VectorStore` and selects the `k` most similar to the input.
+///
+/// Examples are embedded as the values of their `input_keys`, or of all their keys by
+/// default, and stored whole in the document metadata. The input is embedded the same way,
+/// so set `input_keys` when the prompt takes variables that are not part of the examples.
+///
+/// # Usage
+/// ```rust,ignore
+/// let selector = SemanticSimilarityExampleSelector::new(store, 3).with_input_keys(&["input"]);
+/// selector.add_examples(&examples).await?;
+/// ```
+pub struct SemanticSimilarityExampleSelector {
+    vector_store: Box<dyn VectorStore>,
+    k: usize,
+    input_keys: Option<Vec<String>>,
+    options: VecStoreOptions,
+}
+
+impl SemanticSimilarityExampleSelector {
+    pub fn new<V: Into<Box<dyn VectorStore>>>(vector_store: V, k: usize) -> Self {
+        Self {
+            vector_store: vector_store.into(),
+            k,
+            input_keys: None,
+            options: VecStoreOptions::default(),
+        }
+    }
+
+    pub fn with_input_keys<S: AsRef<str>>(mut self, input_keys: &[S]) -> Self {
+        self.input_keys = Some(
+            input_keys
+                .iter()
+                .map(|key| key.as_ref().to_string())
+                .collect(),
+        );
+        self
+    }
+
+    pub fn with_options(mut self, options: VecStoreOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub async fn add_example(&self, example: PromptArgs) -> Result<(), PromptError> {
+        self.add_examples(&[example]).await
+    }
+
+    pub async fn add_examples(&self, examples: &[PromptArgs]) -> Result<(), PromptError> {
+        let documents: Vec<Document> = examples
+            .iter()
+            .map(|example| {
+                Document::new(self.embedding_text(example)).with_metadata(example.clone())
+            })
+            .collect();
+        self.vector_store
+            .add_documents(&documents, &self.options)
+            .await
+            .map_err(|e| PromptError::OtherError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// The values of the input keys, sorted by key so the text does not depend on map order.
+    fn embedding_text(&self, variables: &PromptArgs) -> String {
+        let mut keys: Vec<&String> = match &self.input_keys {
+            Some(input_keys) => input_keys
+                .iter()
+                .filter(|key| variables.contains_key(*key))
+                .collect(),
+            None => variables.keys().collect(),
+        };
+        keys.sort();
+        keys.iter()
+            .map(|key| value_to_string(&variables[*key]))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[async_trait]
+impl ExampleSelector for SemanticSimilarityExampleSelector {
+    async fn select_examples(
+        &self,
+        input_variables: &PromptArgs,
+    ) -> Result<Vec<PromptArgs>, PromptError> {
+        let documents = self
+            .vector_store
+            .similarity_search(&self.embedding_text(input_variables), self.k, &self.options)
+            .await
+            .map_err(|e| PromptError::OtherError(e.to_string()))?;
+        Ok(documents
+            .into_iter()
+            .map(|document| document.metadata)
+            .collect())
+    }
+}
+
+pub(crate) fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::{error::LangChainError, prompt_args, template_fstring};
+
+    use super::*;
+
+    /// Ranks documents by the number of words they share with the query.
+    #[derive(Default)]
+    struct WordOverlapStore {
+        documents: Mutex<Vec<Document>>,
+    }
+
+    #[async_trait]
+    impl VectorStore for WordOverlapStore {
+        async fn add_documents(
+            &self,
+            docs: &[Document],
+            _opt: &VecStoreOptions,
+        ) -> Result<Vec<String>, LangChainError> {
+            self.documents.lock().unwrap().extend_from_slice(docs);
+            Ok(vec![String::new(); docs.len()])
+        }
+
+        async fn similarity_search(
+            &self,
+            query: &str,
+            limit: usize,
+            _opt: &VecStoreOptions,
+        ) -> Result<Vec<Document>, LangChainError> {
+            let overlap = |document: &Document| {
+                document
+                    .page_content
+                    .split_whitespace()
+                    .filter(|word| query.split_whitespace().any(|q| q == *word))
+                    .count()
+            };
+            let mut documents = self.documents.lock().unwrap().clone();
+            documents.sort_by_key(|document| std::cmp::Reverse(overlap(document)));
+            documents.truncate(limit);
+            Ok(documents)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_semantic_similarity_example_selector() {
+        let selector = SemanticSimilarityExampleSelector::new(WordOverlapStore::default(), 1)
+            .with_input_keys(&["input"]);
+        selector
+            .add_examples(&[
+                prompt_args! { "input" => "the sun is bright", "output" => "weather" },
+                prompt_args! { "input" => "my cat sleeps", "output" => "pets" },
+            ])
+            .await
+            .unwrap();
+        selector
+            .add_example(prompt_args! { "input" => "stocks fell today", "output" => "finance" })
+            .await
+            .unwrap();
+
+        let selected = selector
+            .select_examples(
+                &prompt_args! { "input" => "why does my cat purr", "format" => "json" },
+            )
+            .await
+            .unwrap();
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0]["output"], "pets");
+    }
+
+    #[tokio::test]
+    async fn test_length_based_example_selector() {
+        let examples = vec![
+            prompt_args! { "input" => "happy", "output" => "sad" },
+            prompt_args! { "input" => "tall", "output" => "short" },
+            prompt_args! { "input" => "energetic", "output" => "lethargic" },
+        ];
+        let selector = LengthBasedExampleSelector::new(
+            examples,
+            template_fstring!("Input: {input}\nOutput: {output}", "input", "output"),
+            12,
+        )
+        .with_length_function(|text| text.split_whitespace().count());
+
+        let selected = selector
+            .select_examples(&prompt_args! { "adjective" => "big" })
+            .await
+            .unwrap();
+        assert_eq!(selected.len(), 2);
+
+        let selected = selector
+            .select_examples(&prompt_args! { "adjective" => "big and huge and massive" })
+            .await
+            .unwrap();
+        assert_eq!(selected.len(), 1);
+    }
+}
@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use crate::schemas::prompt::PromptValue;
+
+use super::{ExampleSelector, PromptArgs, PromptError, PromptFromatter, PromptTemplate};
+
+/// A prompt made of a prefix, examples formatted with `example_prompt` and a suffix, usually
+/// holding the actual input.
+///
+/// The examples are either fixed with `with_examples` or picked for each input by an
+/// `ExampleSelector`, which takes precedence. Since selectors may be async, formatting is
+/// async too; format the prompt before handing it to the LLM.
+///
+/// # Usage
+/// ```rust,ignore
+/// let example_prompt = template_fstring!("Input: {input}\nOutput: {output}", "input", "output");
+/// let prompt = FewShotPromptTemplate::new(
+///     example_prompt.clone(),
+///     template_fstring!("Input: {adjective}\nOutput:", "adjective"),
+/// )
+/// .with_prefix(template_fstring!("Give the antonym of every input",))
+/// .with_example_selector(LengthBasedExampleSelector::new(examples, example_prompt, 500));
+///
+/// let prompt = prompt.format_prompt(prompt_args! { "adjective" => "big" }).await?;
+/// let result = llm.generate(&prompt.to_chat_messages()).await?;
+/// ```
+#[derive(Clone)]
+pub struct FewShotPromptTemplate {
+    example_prompt: PromptTemplate,
+    examples: Vec<PromptArgs>,
+    example_selector: Option<Arc<dyn ExampleSelector>>,
+    prefix: Option<PromptTemplate>,
+    suffix: PromptTemplate,
+    example_separator: String,
+}
+
+impl FewShotPromptTemplate {
+    pub fn new(example_prompt: PromptTemplate, suffix: PromptTemplate) -> Self {
+        Self {
+            example_prompt,
+            examples: Vec::new(),
+            example_selector: None,
+            prefix: None,
+            suffix,
+            example_separator: "\n\n".to_string(),
+        }
+    }
+
+    pub fn with_examples(mut self, examples: Vec<PromptArgs>) -> Self {
+        self.examples = examples;
+        self
+    }
+
+    pub fn with_example_selector<S: ExampleSelector + 'static>(mut self, selector: S) -> Self {
+        self.example_selector = Some(Arc::new(selector));
+        self
+    }
+
+    pub fn with_prefix(mut self, prefix: PromptTemplate) -> Self {
+        self.prefix = Some(prefix);
+        self
+    }
+
+    /// Sets the string joining the prefix, the examples and the suffix, `"\n\n"` by default.
+    pub fn with_example_separator<S: Into<String>>(mut self, separator: S) -> Self {
+        self.example_separator = separator.into();
+        self
+    }
+
+    /// Input variables of the prefix and the suffix.
+    pub fn variables(&self) -> Vec<String> {
+        let mut variables = self
+            .prefix
+            .as_ref()
+            .map(|prefix| prefix.variables())
+            .unwrap_or_default();
+        for variable in self.suffix.variables() {
+            if !variables.contains(&variable) {
+                variables.push(variable);
+            }
+        }
+        variables
+    }
+
+    pub async fn format(&self, input_variables: PromptArgs) -> Result<String, PromptError> {
+        let examples = match &self.example_selector {
+            Some(selector) => selector.select_examples(&input_variables).await?,
+            None => self.examples.clone(),
+        };
+
+        let mut pieces = Vec::new();
+        if let Some(prefix) = &self.prefix {
+            pieces.push(prefix.format(input_variables.clone())?);
+        }
+        for example in examples {
+            pieces.push(self.example_prompt.format(example)?);
+        }
+        pieces.push(self.suffix.format(input_variables)?);
+
+        pieces.retain(|piece| !piece.is_empty());
+        Ok(pieces.join(&self.example_separator))
+    }
+
+    pub async fn format_prompt(
+        &self,
+        input_variables: PromptArgs,
+    ) -> Result<PromptValue, PromptError> {
+        Ok(PromptValue::from_string(
+            &self.format(input_variables).await?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{prompt::LengthBasedExampleSelector, prompt_args, template_fstring};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_few_shot_prompt_template() {
+        let example_prompt =
+            template_fstring!("Input: {input}\nOutput: {output}", "input", "output");
+        let examples = vec![
+            prompt_args! { "input" => "happy", "output" => "sad" },
+            prompt_args! { "input" => "tall", "output" => "short" },
+        ];
+        let prompt = FewShotPromptTemplate::new(
+            example_prompt.clone(),
+            template_fstring!("Input: {adjective}\nOutput:", "adjective"),
+        )
+        .with_prefix(template_fstring!("Give the {kind} of every input", "kind"))
+        .with_examples(examples.clone());
+        assert_eq!(prompt.variables(), vec!["kind", "adjective"]);
+
+        let result = prompt
+            .format(prompt_args! { "kind" => "antonym", "adjective" => "big" })
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            "Give the antonym of every input\n\nInput: happy\nOutput: sad\n\nInput: tall\nOutput: short\n\nInput: big\nOutput:"
+        );
+
+        let prompt = prompt.with_example_selector(
+            LengthBasedExampleSelector::new(examples, example_prompt, 8)
+                .with_length_function(|text| text.split_whitespace().count()),
+        );
+        let result = prompt
+            .format(prompt_args! { "kind" => "antonym", "adjective" => "big" })
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            "Give the antonym of every input\n\nInput: happy\nOutput: sad\n\nInput: big\nOutput:"
+        );
+    }
+}
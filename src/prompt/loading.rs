@@ -0,0 +1,260 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use regex::Regex;
+use serde::Deserialize;
+
+use super::{PromptArgs, PromptError, PromptTemplate, TemplateFormat};
+
+/// The content of a prompt file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PromptFile {
+    template: String,
+    input_variables: Option<Vec<String>>,
+    #[serde(default)]
+    template_format: TemplateFormat,
+    #[serde(default)]
+    partial_variables: PromptArgs,
+}
+
+impl PromptTemplate {
+    /// Loads a template from a YAML (`.yaml`, `.yml`) or JSON (`.json`) file:
+    ///
+    /// ```yaml
+    /// template: "Answer as a {persona}: {question}"
+    /// template_format: f-string # jinja2 or minijinja, f-string by default
+    /// input_variables: [persona, question] # optional, inferred from the template
+    /// partial_variables:
+    ///   persona: pirate
+    /// ```
+    ///
+    /// The variables are checked against the placeholders of the template: a declared
+    /// variable missing from the template, or a placeholder that is neither declared nor
+    /// partial, is an error.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, PromptError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+        let file: PromptFile = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("yaml") | Some("yml") => serde_yaml::from_str(&content)
+                .map_err(|e| PromptError::InvalidTemplate(format!("{}: {}", path.display(), e)))?,
+            Some("json") => serde_json::from_str(&content)
+                .map_err(|e| PromptError::InvalidTemplate(format!("{}: {}", path.display(), e)))?,
+            _ => {
+                return Err(PromptError::InvalidTemplate(format!(
+                    "{}: expected a .yaml, .yml or .json file",
+                    path.display()
+                )))
+            }
+        };
+        file.into_template()
+            .map_err(|e| PromptError::InvalidTemplate(format!("{}: {}", path.display(), e)))
+    }
+}
+
+impl PromptFile {
+    fn into_template(self) -> Result<PromptTemplate, String> {
+        let placeholders = template_placeholders(&self.template, &self.template_format)?;
+        let variables = match self.input_variables {
+            Some(variables) => {
+                if let Some(variable) = variables.iter().find(|v| !placeholders.contains(v)) {
+                    return Err(format!(
+                        "variable `{}` is not used in the template",
+                        variable
+                    ));
+                }
+                if let Some(placeholder) = placeholders.iter().find(|p| {
+                    !variables.contains(p) && !self.partial_variables.contains_key(p.as_str())
+                }) {
+                    return Err(format!(
+                        "placeholder `{}` is not an input or partial variable",
+                        placeholder
+                    ));
+                }
+                variables
+            }
+            None => placeholders,
+        };
+
+        let template = PromptTemplate::new(self.template, variables, self.template_format);
+        Ok(self
+            .partial_variables
+            .into_iter()
+            .fold(template, |template, (key, value)| {
+                template.partial(key, value)
+            }))
+    }
+}
+
+/// Returns the names of the placeholders of a template, in order of first appearance.
+pub(crate) fn template_placeholders(
+    template: &str,
+    format: &TemplateFormat,
+) -> Result<Vec<String>, String> {
+    static FSTRING: OnceLock<Regex> = OnceLock::new();
+    static JINJA2: OnceLock<Regex> = OnceLock::new();
+    let regex = match format {
+        TemplateFormat::FString => {
+            FSTRING.get_or_init(|| Regex::new(r"\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap())
+        }
+        TemplateFormat::Jinja2 => {
+            JINJA2.get_or_init(|| Regex::new(r"\{\{([A-Za-z_][A-Za-z0-9_]*)\}\}").unwrap())
+        }
+        #[cfg(feature = "minijinja")]
+        TemplateFormat::MiniJinja => {
+            let environment = minijinja::Environment::new();
+            let mut placeholders: Vec<String> = environment
+                .template_from_str(template)
+                .map_err(|e| e.to_string())?
+                .undeclared_variables(false)
+                .into_iter()
+                .collect();
+            placeholders.sort_by_key(|name| (template.find(name.as_str()), name.clone()));
+            return Ok(placeholders);
+        }
+    };
+
+    let mut placeholders: Vec<String> = Vec::new();
+    for capture in regex.captures_iter(template) {
+        let name = &capture[1];
+        if !placeholders.iter().any(|p| p == name) {
+            placeholders.push(name.to_string());
+        }
+    }
+    Ok(placeholders)
+}
+
+/// Prompt templates loaded from a directory of YAML and JSON files, so prompts can be
+/// versioned and edited without touching the code.
+///
+/// Each template is named after its path relative to the directory, without extension and
+/// with `/` separators, e.g. `agents/planner` for `agents/planner.yaml`. Every file is
+/// validated when the registry is loaded.
+///
+/// # Usage
+/// ```rust,ignore
+/// let registry = PromptRegistry::from_dir("prompts")?;
+/// let prompt = registry.get("agents/planner").unwrap();
+/// ```
+#[derive(Clone, Default)]
+pub struct PromptRegistry {
+    prompts: HashMap<String, PromptTemplate>,
+}
+
+impl PromptRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> Result<Self, PromptError> {
+        let dir = dir.as_ref();
+        let mut registry = Self::new();
+        for path in prompt_files(dir)? {
+            let name = path
+                .strip_prefix(dir)
+                .unwrap_or(&path)
+                .with_extension("")
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            registry.insert(name, PromptTemplate::from_file(&path)?);
+        }
+        Ok(registry)
+    }
+
+    pub fn insert<S: Into<String>>(&mut self, name: S, template: PromptTemplate) {
+        self.prompts.insert(name.into(), template);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PromptTemplate> {
+        self.prompts.get(name)
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.prompts.keys().map(String::as_str).collect();
+        names.sort();
+        names
+    }
+}
+
+fn prompt_files(dir: &Path) -> Result<Vec<PathBuf>, PromptError> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(prompt_files(&path)?);
+        } else if matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("yaml") | Some("yml") | Some("json")
+        ) {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{prompt::PromptFromatter, prompt_args};
+
+    use super::*;
+
+    #[test]
+    fn test_prompt_registry() {
+        let dir = std::env::temp_dir().join(format!("prompts-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("agents")).unwrap();
+        fs::write(
+            dir.join("greeting.yaml"),
+            "template: \"Hello {name}, I am {bot}\"\npartial_variables:\n  bot: Rusty\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("agents/planner.json"),
+            r#"{"template": "Plan: {{goal}}", "template_format": "jinja2", "input_variables": ["goal"]}"#,
+        )
+        .unwrap();
+        fs::write(dir.join("README.md"), "not a prompt").unwrap();
+
+        let registry = PromptRegistry::from_dir(&dir).unwrap();
+        assert_eq!(registry.names(), vec!["agents/planner", "greeting"]);
+
+        let greeting = registry.get("greeting").unwrap();
+        assert_eq!(greeting.variables(), vec!["name"]);
+        assert_eq!(
+            greeting.format(prompt_args! { "name" => "Ana" }).unwrap(),
+            "Hello Ana, I am Rusty"
+        );
+        assert_eq!(
+            registry.get("agents/planner").unwrap().variables(),
+            vec!["goal"]
+        );
+
+        fs::write(
+            dir.join("broken.yml"),
+            "template: \"{question} {context}\"\ninput_variables: [question]\n",
+        )
+        .unwrap();
+        let result = PromptRegistry::from_dir(&dir);
+        assert!(
+            matches!(result, Err(PromptError::InvalidTemplate(message)) if message.contains("`context`"))
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "minijinja")]
+    #[test]
+    fn test_minijinja_placeholders() {
+        let placeholders = template_placeholders(
+            "{% for doc in docs %}{{ doc }}{% endfor %}{{ question | upper }}",
+            &TemplateFormat::MiniJinja,
+        );
+        assert_eq!(placeholders.unwrap(), vec!["docs", "question"]);
+        assert!(template_placeholders("{% if %}", &TemplateFormat::MiniJinja).is_err());
+    }
+}
@@ -1,11 +1,19 @@
+mod assembler;
 mod chat;
 mod error;
+mod example_selector;
+mod few_shot;
+mod loading;
 mod prompt;
 
 use std::collections::HashMap;
 
+pub use assembler::*;
 pub use chat::*;
 pub use error::*;
+pub use example_selector::*;
+pub use few_shot::*;
+pub use loading::*;
 pub use prompt::*;
 use serde_json::Value;
 
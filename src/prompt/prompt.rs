@@ -1,11 +1,22 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
 use crate::schemas::{messages::Message, prompt::PromptValue};
 
 use super::{FormatPrompter, PromptArgs, PromptError, PromptFromatter};
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
 pub enum TemplateFormat {
+    #[default]
+    #[serde(rename = "f-string", alias = "fstring")]
     FString,
+    #[serde(rename = "jinja2")]
     Jinja2,
+    /// Rendered with minijinja, supporting the Jinja2 syntax: filters, conditionals and loops,
+    /// e.g. `{% if context %}Context: {{ context }}{% endif %}`.
+    #[cfg(feature = "minijinja")]
+    #[serde(rename = "minijinja")]
+    MiniJinja,
 }
 
 #[derive(Clone)]
@@ -13,6 +24,7 @@ pub struct PromptTemplate {
     template: String,
     variables: Vec<String>,
     format: TemplateFormat,
+    partial_variables: PromptArgs,
 }
 
 impl PromptTemplate {
@@ -21,8 +33,53 @@ impl PromptTemplate {
             template,
             variables,
             format,
+            partial_variables: PromptArgs::new(),
         }
     }
+
+    /// Fixes the value of a variable, which is then no longer an input variable of the
+    /// template. Values given at format time still take precedence.
+    ///
+    /// # Usage
+    /// ```rust,ignore
+    /// let template = template_fstring!("Today is {today}. {question}", "today", "question")
+    ///     .partial("today", "2024-05-01");
+    /// assert_eq!(template.variables(), vec!["question"]);
+    /// ```
+    pub fn partial<K: Into<String>, V: Into<Value>>(mut self, key: K, value: V) -> Self {
+        let key = key.into();
+        self.variables.retain(|variable| *variable != key);
+        self.partial_variables.insert(key, value.into());
+        self
+    }
+
+    /// Appends `other` to this template, merging their variables and partial variables.
+    /// Both templates must use the same format.
+    pub fn concat(mut self, other: PromptTemplate) -> Result<Self, PromptError> {
+        if self.format != other.format {
+            return Err(PromptError::OtherError(format!(
+                "Cannot concatenate a {:?} template with a {:?} template",
+                self.format, other.format
+            )));
+        }
+        self.template.push_str(&other.template);
+        for variable in other.variables {
+            if !self.variables.contains(&variable)
+                && !self.partial_variables.contains_key(&variable)
+            {
+                self.variables.push(variable);
+            }
+        }
+        for (key, value) in other.partial_variables {
+            self.variables.retain(|variable| *variable != key);
+            self.partial_variables.insert(key, value);
+        }
+        Ok(self)
+    }
+
+    pub fn partial_variables(&self) -> &PromptArgs {
+        &self.partial_variables
+    }
 }
 
 //PromptTemplate will be default transformed to an Human Input when used as FromatPrompter
@@ -49,16 +106,37 @@ impl PromptFromatter for PromptTemplate {
         let mut prompt = self.template();
 
         // check if all variables are in the input variables
-        for key in self.variables() {
-            if !input_variables.contains_key(key.as_str()) {
-                return Err(PromptError::MissingVariable(key));
-            }
+        let missing: Vec<String> = self
+            .variables()
+            .into_iter()
+            .filter(|key| !input_variables.contains_key(key.as_str()))
+            .collect();
+        if !missing.is_empty() {
+            return Err(PromptError::MissingVariables(missing));
+        }
+
+        let mut input_variables = input_variables;
+        for (key, value) in &self.partial_variables {
+            input_variables
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+
+        #[cfg(feature = "minijinja")]
+        if self.format == TemplateFormat::MiniJinja {
+            let mut environment = minijinja::Environment::new();
+            environment.set_keep_trailing_newline(true);
+            prompt = environment
+                .render_str(&prompt, &input_variables)
+                .map_err(|e| PromptError::InvalidTemplate(e.to_string()))?;
+            tracing::debug!("Formatted prompt: {}", prompt);
+            return Ok(prompt);
         }
 
         for (key, value) in input_variables {
             let key = match self.format {
-                TemplateFormat::FString => format!("{{{}}}", key),
                 TemplateFormat::Jinja2 => format!("{{{{{}}}}}", key),
+                _ => format!("{{{}}}", key),
             };
             let value_str = match &value {
                 serde_json::Value::String(s) => s.clone(),
@@ -67,7 +145,7 @@ impl PromptFromatter for PromptTemplate {
             prompt = prompt.replace(&key, &value_str);
         }
 
-        log::debug!("Formatted prompt: {}", prompt);
+        tracing::debug!("Formatted prompt: {}", prompt);
         Ok(prompt)
     }
 }
@@ -152,6 +230,28 @@ macro_rules! template_jinja2 {
     };
 }
 
+/// `template_minijinja` is a utility macro that creates a new `PromptTemplate` rendered with minijinja.
+///
+/// # Usage
+/// ```rust,ignore
+/// template_minijinja!(
+///     "{% if context %}Context: {{ context }}\n{% endif %}Question: {{ question }}",
+///     "context",
+///     "question"
+/// )
+/// ```
+#[cfg(feature = "minijinja")]
+#[macro_export]
+macro_rules! template_minijinja {
+    ($template:expr, $($var:expr),* $(,)?) => {
+        $crate::prompt::PromptTemplate::new(
+            $template.to_string(),
+            vec![$($var.to_string()),*],
+            $crate::prompt::TemplateFormat::MiniJinja,
+        )
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -218,6 +318,72 @@ mod tests {
         assert_eq!(args.get("age").unwrap(), &"18");
     }
 
+    #[cfg(feature = "minijinja")]
+    #[test]
+    fn test_minijinja_template() {
+        let template = template_minijinja!(
+            "{% if context %}Context:\n{% for doc in context %}- {{ doc }}\n{% endfor %}{% endif %}Question: {{ question | trim }}",
+            "context",
+            "question"
+        );
+
+        let result = template.format(prompt_args! {
+            "context" => vec!["Rust is fast", "Rust is safe"],
+            "question" => " Why Rust? ",
+        });
+        assert_eq!(
+            result.unwrap(),
+            "Context:\n- Rust is fast\n- Rust is safe\nQuestion: Why Rust?"
+        );
+
+        let result = template.format(prompt_args! {
+            "context" => Vec::<String>::new(),
+            "question" => "Why Rust?",
+        });
+        assert_eq!(result.unwrap(), "Question: Why Rust?");
+
+        let result = template_minijinja!("{% if x %}", "x").format(prompt_args! { "x" => 1 });
+        assert!(matches!(result, Err(PromptError::InvalidTemplate(_))));
+    }
+
+    #[test]
+    fn test_partial_and_concat() {
+        let template = template_fstring!("Today is {today}. ", "today")
+            .partial("today", "Monday")
+            .concat(template_fstring!("{question} {name}", "question", "name"))
+            .unwrap();
+        assert_eq!(template.variables(), vec!["question", "name"]);
+
+        let result = template.format(prompt_args! {});
+        match result {
+            Err(PromptError::MissingVariables(missing)) => {
+                assert_eq!(missing, vec!["question", "name"])
+            }
+            _ => panic!("expected missing variables"),
+        }
+        assert_eq!(
+            PromptError::MissingVariables(vec!["question".into(), "name".into()]).to_string(),
+            "Missing input variables: question, name"
+        );
+
+        let result = template.format(prompt_args! {
+            "question" => "What day is it?",
+            "name" => "Ana",
+        });
+        assert_eq!(result.unwrap(), "Today is Monday. What day is it? Ana");
+
+        let result = template.format(prompt_args! {
+            "today" => "Tuesday",
+            "question" => "What day is it?",
+            "name" => "Ana",
+        });
+        assert_eq!(result.unwrap(), "Today is Tuesday. What day is it? Ana");
+
+        assert!(template
+            .concat(template_jinja2!("{{other}}", "other"))
+            .is_err());
+    }
+
     #[test]
     fn test_chat_template_macros() {
         // Creating an FString chat template
@@ -0,0 +1,224 @@
+use std::{fmt::Display, future::Future, pin::Pin, sync::Arc};
+
+use async_trait::async_trait;
+use futures::Stream;
+use serde_json::Value;
+
+use crate::{
+    chain::{Chain, ChainError},
+    error::LangChainError,
+    language_models::{llm::LLM, options::CallOptions, GenerateResult, LLMError},
+    prompt::PromptArgs,
+    schemas::{Document, Message, Retriever, StreamData},
+    tools::Tool,
+};
+
+/// Calls the fallbacks in order when the wrapped LLM, tool, retriever or chain fails, and
+/// returns the first success, or the error of the last fallback. Cancelled calls do not fall
+/// back.
+///
+/// Created by the `with_fallbacks` method of `LLM`, `Tool`, `Retriever` and `Chain`. A tool
+/// keeps the name, description and parameters of the wrapped tool.
+///
+/// # Usage
+/// ```rust,ignore
+/// let llm = OpenAI::default()
+///     .with_retry(RetryPolicy::new(2))
+///     .with_fallbacks(vec![Claude::default().into()]);
+/// ```
+#[derive(Clone)]
+pub struct WithFallbacks<T, F> {
+    inner: T,
+    fallbacks: Vec<F>,
+}
+
+impl<T, F> WithFallbacks<T, F> {
+    pub fn new(inner: T, fallbacks: Vec<F>) -> Self {
+        Self { inner, fallbacks }
+    }
+}
+
+/// Awaits the fallbacks in order while `result` is an error `should_fall_back` accepts.
+async fn fall_back<T, E, Fut>(
+    mut result: Result<T, E>,
+    fallbacks: impl Iterator<Item = Fut>,
+    should_fall_back: impl Fn(&E) -> bool,
+) -> Result<T, E>
+where
+    E: Display,
+    Fut: Future<Output = Result<T, E>>,
+{
+    for fallback in fallbacks {
+        match &result {
+            Err(e) if should_fall_back(e) => {
+                tracing::warn!("Call failed, trying the next fallback: {}", e);
+                result = fallback.await;
+            }
+            _ => break,
+        }
+    }
+    result
+}
+
+#[async_trait]
+impl<L: LLM + Clone + 'static> LLM for WithFallbacks<L, Box<dyn LLM>> {
+    async fn generate(&self, messages: &[Message]) -> Result<GenerateResult, LLMError> {
+        fall_back(
+            self.inner.generate(messages).await,
+            self.fallbacks.iter().map(|llm| llm.generate(messages)),
+            |e| !matches!(e, LLMError::Cancelled),
+        )
+        .await
+    }
+
+    async fn stream(
+        &self,
+        messages: &[Message],
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamData, LLMError>> + Send>>, LLMError> {
+        fall_back(
+            self.inner.stream(messages).await,
+            self.fallbacks.iter().map(|llm| llm.stream(messages)),
+            |e| !matches!(e, LLMError::Cancelled),
+        )
+        .await
+    }
+
+    fn add_options(&mut self, options: CallOptions) {
+        for fallback in self.fallbacks.iter_mut() {
+            fallback.add_options(options.clone());
+        }
+        self.inner.add_options(options)
+    }
+}
+
+#[async_trait]
+impl<T: Tool> Tool for WithFallbacks<T, Arc<dyn Tool>> {
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn description(&self) -> String {
+        self.inner.description()
+    }
+
+    fn parameters(&self) -> Value {
+        self.inner.parameters()
+    }
+
+    async fn call(&self, input: &str) -> Result<String, LangChainError> {
+        fall_back(
+            self.inner.call(input).await,
+            self.fallbacks.iter().map(|tool| tool.call(input)),
+            |_| true,
+        )
+        .await
+    }
+
+    async fn run(&self, input: Value) -> Result<String, LangChainError> {
+        fall_back(
+            self.inner.run(input.clone()).await,
+            self.fallbacks.iter().map(|tool| tool.run(input.clone())),
+            |_| true,
+        )
+        .await
+    }
+
+    async fn parse_input(&self, input: &str) -> Value {
+        self.inner.parse_input(input).await
+    }
+}
+
+#[async_trait]
+impl<R: Retriever> Retriever for WithFallbacks<R, Box<dyn Retriever>> {
+    async fn get_relevant_documents(&self, query: &str) -> Result<Vec<Document>, LangChainError> {
+        fall_back(
+            self.inner.get_relevant_documents(query).await,
+            self.fallbacks
+                .iter()
+                .map(|retriever| retriever.get_relevant_documents(query)),
+            |_| true,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl<C: Chain> Chain for WithFallbacks<C, Box<dyn Chain>> {
+    async fn call(&self, input_variables: PromptArgs) -> Result<GenerateResult, ChainError> {
+        fall_back(
+            self.inner.call(input_variables.clone()).await,
+            self.fallbacks
+                .iter()
+                .map(|chain| chain.call(input_variables.clone())),
+            |e| {
+                !matches!(
+                    e,
+                    ChainError::Cancelled | ChainError::LLMError(LLMError::Cancelled)
+                )
+            },
+        )
+        .await
+    }
+
+    async fn stream(
+        &self,
+        input_variables: PromptArgs,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamData, ChainError>> + Send>>, ChainError>
+    {
+        fall_back(
+            self.inner.stream(input_variables.clone()).await,
+            self.fallbacks
+                .iter()
+                .map(|chain| chain.stream(input_variables.clone())),
+            |e| {
+                !matches!(
+                    e,
+                    ChainError::Cancelled | ChainError::LLMError(LLMError::Cancelled)
+                )
+            },
+        )
+        .await
+    }
+
+    fn get_input_keys(&self) -> Vec<String> {
+        self.inner.get_input_keys()
+    }
+
+    fn get_output_keys(&self) -> Vec<String> {
+        self.inner.get_output_keys()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StaticRetriever(Result<&'static str, &'static str>);
+
+    #[async_trait]
+    impl Retriever for StaticRetriever {
+        async fn get_relevant_documents(
+            &self,
+            _query: &str,
+        ) -> Result<Vec<Document>, LangChainError> {
+            self.0
+                .map(|content| vec![Document::new(content)])
+                .map_err(LangChainError::from)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retriever_with_fallbacks() {
+        let retriever = StaticRetriever(Err("unavailable")).with_fallbacks(vec![
+            Box::new(StaticRetriever(Err("unavailable"))),
+            Box::new(StaticRetriever(Ok("fallback"))),
+        ]);
+        let documents = retriever.get_relevant_documents("query").await.unwrap();
+        assert_eq!(documents[0].page_content, "fallback");
+
+        let retriever = StaticRetriever(Err("unavailable"))
+            .with_fallbacks(vec![Box::new(StaticRetriever(Err("still unavailable")))]);
+        let error = retriever.get_relevant_documents("query").await.unwrap_err();
+        assert_eq!(error.to_string(), "Error: still unavailable");
+    }
+}
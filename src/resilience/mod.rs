@@ -0,0 +1,11 @@
+//! Wrappers that add retries, fallbacks and timeouts to any `LLM`, `Tool`, `Retriever` or
+//! `Chain`, through the `with_retry`, `with_fallbacks` and `with_timeout` methods of the traits.
+
+mod retry;
+pub use retry::*;
+
+mod fallbacks;
+pub use fallbacks::*;
+
+mod timeout;
+pub use timeout::*;
@@ -0,0 +1,258 @@
+use std::{future::Future, pin::Pin, time::Duration};
+
+use async_trait::async_trait;
+use futures::Stream;
+use serde_json::Value;
+
+use crate::{
+    chain::{Chain, ChainError},
+    error::LangChainError,
+    language_models::{llm::LLM, options::CallOptions, GenerateResult, LLMError},
+    prompt::PromptArgs,
+    schemas::{Document, Message, Retriever, StreamData},
+    tools::Tool,
+};
+
+/// How many times, and how long apart, failed calls are retried.
+///
+/// Only transient errors, the ones `is_retryable` reports, are retried. The delay before the
+/// first retry is `initial_delay`, 500ms by default, and is multiplied by `multiplier`, 2 by
+/// default, before each next retry, up to `max_delay`, 30s by default.
+///
+/// # Usage
+/// ```rust,ignore
+/// let llm = OpenAI::default().with_retry(
+///     RetryPolicy::new(3).with_initial_delay(Duration::from_secs(1)),
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    max_retries: usize,
+    initial_delay: Duration,
+    max_delay: Duration,
+    multiplier: f64,
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: usize) -> Self {
+        Self {
+            max_retries,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2.0,
+        }
+    }
+
+    pub fn with_initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// The delay before the retry number `retry`, counted from 0.
+    pub fn delay(&self, retry: usize) -> Duration {
+        let factor = self.multiplier.powi(retry.min(i32::MAX as usize) as i32);
+        self.initial_delay.mul_f64(factor).min(self.max_delay)
+    }
+
+    /// Runs `operation` until it succeeds, fails with an error `is_retryable` rejects, or
+    /// runs out of retries.
+    pub(crate) async fn run<T, E, F, Fut>(
+        &self,
+        is_retryable: impl Fn(&E) -> bool,
+        mut operation: F,
+    ) -> Result<T, E>
+    where
+        E: std::fmt::Display,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut retry = 0;
+        loop {
+            match operation().await {
+                Err(e) if retry < self.max_retries && is_retryable(&e) => {
+                    let delay = self.delay(retry);
+                    tracing::warn!(
+                        "Attempt {} failed with a transient error, retrying in {:?}: {}",
+                        retry + 1,
+                        delay,
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+/// Retries the calls of the wrapped LLM, tool, retriever or chain that fail with a transient
+/// error, following a `RetryPolicy`.
+///
+/// Created by the `with_retry` method of `LLM`, `Tool`, `Retriever` and `Chain`. For LLMs
+/// only establishing a stream is retried, not the stream itself.
+#[derive(Clone)]
+pub struct WithRetry<T> {
+    inner: T,
+    policy: RetryPolicy,
+}
+
+impl<T> WithRetry<T> {
+    pub fn new(inner: T, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+}
+
+#[async_trait]
+impl<L: LLM + Clone + 'static> LLM for WithRetry<L> {
+    async fn generate(&self, messages: &[Message]) -> Result<GenerateResult, LLMError> {
+        self.policy
+            .run(LLMError::is_retryable, || self.inner.generate(messages))
+            .await
+    }
+
+    async fn stream(
+        &self,
+        messages: &[Message],
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamData, LLMError>> + Send>>, LLMError> {
+        self.policy
+            .run(LLMError::is_retryable, || self.inner.stream(messages))
+            .await
+    }
+
+    fn add_options(&mut self, options: CallOptions) {
+        self.inner.add_options(options)
+    }
+}
+
+#[async_trait]
+impl<T: Tool> Tool for WithRetry<T> {
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn description(&self) -> String {
+        self.inner.description()
+    }
+
+    fn parameters(&self) -> Value {
+        self.inner.parameters()
+    }
+
+    async fn call(&self, input: &str) -> Result<String, LangChainError> {
+        self.policy
+            .run(LangChainError::is_retryable, || self.inner.call(input))
+            .await
+    }
+
+    async fn run(&self, input: Value) -> Result<String, LangChainError> {
+        self.policy
+            .run(LangChainError::is_retryable, || {
+                self.inner.run(input.clone())
+            })
+            .await
+    }
+
+    async fn parse_input(&self, input: &str) -> Value {
+        self.inner.parse_input(input).await
+    }
+}
+
+#[async_trait]
+impl<R: Retriever> Retriever for WithRetry<R> {
+    async fn get_relevant_documents(&self, query: &str) -> Result<Vec<Document>, LangChainError> {
+        self.policy
+            .run(LangChainError::is_retryable, || {
+                self.inner.get_relevant_documents(query)
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl<C: Chain> Chain for WithRetry<C> {
+    async fn call(&self, input_variables: PromptArgs) -> Result<GenerateResult, ChainError> {
+        self.policy
+            .run(ChainError::is_retryable, || {
+                self.inner.call(input_variables.clone())
+            })
+            .await
+    }
+
+    async fn stream(
+        &self,
+        input_variables: PromptArgs,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamData, ChainError>> + Send>>, ChainError>
+    {
+        self.policy
+            .run(ChainError::is_retryable, || {
+                self.inner.stream(input_variables.clone())
+            })
+            .await
+    }
+
+    fn get_input_keys(&self) -> Vec<String> {
+        self.inner.get_input_keys()
+    }
+
+    fn get_output_keys(&self) -> Vec<String> {
+        self.inner.get_output_keys()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn test_retry_policy_delay() {
+        let policy = RetryPolicy::new(5)
+            .with_initial_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(500));
+        assert_eq!(policy.delay(0), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(400));
+        assert_eq!(policy.delay(3), Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_retry_policy_run() {
+        let policy = RetryPolicy::new(2).with_initial_delay(Duration::ZERO);
+        let attempts = AtomicUsize::new(0);
+        let result = policy
+            .run(LLMError::is_retryable, || async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err::<(), _>(LLMError::IoError(std::io::ErrorKind::TimedOut.into()))
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+        let attempts = AtomicUsize::new(0);
+        let result = policy
+            .run(LLMError::is_retryable, || async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err::<(), _>(LLMError::OtherError("invalid request".to_string()))
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,145 @@
+use std::{pin::Pin, time::Duration};
+
+use async_trait::async_trait;
+use futures::Stream;
+use serde_json::Value;
+use tokio::time::timeout;
+
+use crate::{
+    chain::{Chain, ChainError},
+    error::LangChainError,
+    language_models::{llm::LLM, options::CallOptions, GenerateResult, LLMError},
+    prompt::PromptArgs,
+    schemas::{Document, Message, Retriever, StreamData},
+    tools::Tool,
+};
+
+/// Fails the calls of the wrapped LLM, tool, retriever or chain that take longer than a
+/// duration with a `Timeout` error, dropping the call in flight.
+///
+/// Created by the `with_timeout` method of `LLM`, `Tool`, `Retriever` and `Chain`. For
+/// streams only establishing the stream is timed. Timeouts are retryable, so wrap the timeout
+/// in a retry to retry the calls that time out:
+///
+/// ```rust,ignore
+/// let llm = OpenAI::default()
+///     .with_timeout(Duration::from_secs(30))
+///     .with_retry(RetryPolicy::new(2));
+/// ```
+#[derive(Clone)]
+pub struct WithTimeout<T> {
+    inner: T,
+    timeout: Duration,
+}
+
+impl<T> WithTimeout<T> {
+    pub fn new(inner: T, timeout: Duration) -> Self {
+        Self { inner, timeout }
+    }
+}
+
+#[async_trait]
+impl<L: LLM + Clone + 'static> LLM for WithTimeout<L> {
+    async fn generate(&self, messages: &[Message]) -> Result<GenerateResult, LLMError> {
+        timeout(self.timeout, self.inner.generate(messages)).await?
+    }
+
+    async fn stream(
+        &self,
+        messages: &[Message],
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamData, LLMError>> + Send>>, LLMError> {
+        timeout(self.timeout, self.inner.stream(messages)).await?
+    }
+
+    fn add_options(&mut self, options: CallOptions) {
+        self.inner.add_options(options)
+    }
+}
+
+#[async_trait]
+impl<T: Tool> Tool for WithTimeout<T> {
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn description(&self) -> String {
+        self.inner.description()
+    }
+
+    fn parameters(&self) -> Value {
+        self.inner.parameters()
+    }
+
+    async fn call(&self, input: &str) -> Result<String, LangChainError> {
+        timeout(self.timeout, self.inner.call(input)).await?
+    }
+
+    async fn run(&self, input: Value) -> Result<String, LangChainError> {
+        timeout(self.timeout, self.inner.run(input)).await?
+    }
+
+    async fn parse_input(&self, input: &str) -> Value {
+        self.inner.parse_input(input).await
+    }
+}
+
+#[async_trait]
+impl<R: Retriever> Retriever for WithTimeout<R> {
+    async fn get_relevant_documents(&self, query: &str) -> Result<Vec<Document>, LangChainError> {
+        timeout(self.timeout, self.inner.get_relevant_documents(query)).await?
+    }
+}
+
+#[async_trait]
+impl<C: Chain> Chain for WithTimeout<C> {
+    async fn call(&self, input_variables: PromptArgs) -> Result<GenerateResult, ChainError> {
+        timeout(self.timeout, self.inner.call(input_variables)).await?
+    }
+
+    async fn stream(
+        &self,
+        input_variables: PromptArgs,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamData, ChainError>> + Send>>, ChainError>
+    {
+        timeout(self.timeout, self.inner.stream(input_variables)).await?
+    }
+
+    fn get_input_keys(&self) -> Vec<String> {
+        self.inner.get_input_keys()
+    }
+
+    fn get_output_keys(&self) -> Vec<String> {
+        self.inner.get_output_keys()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct SlowRetriever(Duration);
+
+    #[async_trait]
+    impl Retriever for SlowRetriever {
+        async fn get_relevant_documents(
+            &self,
+            _query: &str,
+        ) -> Result<Vec<Document>, LangChainError> {
+            tokio::time::sleep(self.0).await;
+            Ok(vec![])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retriever_with_timeout() {
+        let retriever =
+            SlowRetriever(Duration::from_millis(1)).with_timeout(Duration::from_secs(5));
+        assert!(retriever.get_relevant_documents("query").await.is_ok());
+
+        let retriever =
+            SlowRetriever(Duration::from_secs(5)).with_timeout(Duration::from_millis(1));
+        let error = retriever.get_relevant_documents("query").await.unwrap_err();
+        assert!(matches!(error, LangChainError::Timeout(_)));
+        assert!(error.is_retryable());
+    }
+}
@@ -3,13 +3,14 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub enum ToolInput {
     //Will implement this in the future
     StrInput(String),
     DictInput(HashMap<String, String>),
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct AgentAction {
     pub tool: String,
     pub tool_input: String, //this should be ToolInput in the future
@@ -17,18 +18,18 @@ pub struct AgentAction {
 }
 
 ///Log tools is a struct used by the openai-like agents
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct LogTools {
     pub tool_id: String,
     pub tools: String,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct AgentFinish {
     pub output: String,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub enum AgentEvent {
     Action(Vec<AgentAction>),
     Finish(AgentFinish),
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 /// The `Document` struct represents a document with content, metadata, and a score.
+/// The `id` field is an optional identifier, used by vector stores to store the document under a known ID.
 /// The `page_content` field is a string that contains the content of the document.
 /// The `metadata` field is a `HashMap` where the keys represent metadata properties and the values represent property values.
 /// The `score` field represents a relevance score for the document and is a floating point number.
@@ -16,19 +17,23 @@ use serde_json::Value;
 ///       metadata.insert("author".to_string(), json!("John Doe"));
 ///       metadata
 ///   })
+///    .with_id("doc-1")
 ///    .with_score(0.75);
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Document {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     pub page_content: String,
     pub metadata: HashMap<String, Value>,
     pub score: f64,
 }
 
 impl Document {
-    /// Constructs a new `Document` with provided `page_content`, an empty `metadata` map and a `score` of 0.
+    /// Constructs a new `Document` with provided `page_content`, no `id`, an empty `metadata` map and a `score` of 0.
     pub fn new<S: Into<String>>(page_content: S) -> Self {
         Document {
+            id: None,
             page_content: page_content.into(),
             metadata: HashMap::new(),
             score: 0.0,
@@ -41,6 +46,12 @@ impl Document {
         self
     }
 
+    /// Sets the `id` of the `Document`, which vector stores use instead of generating one.
+    pub fn with_id<S: Into<String>>(mut self, id: S) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
     /// Sets the `score` of the `Document` to the provided float.
     pub fn with_score(mut self, score: f64) -> Self {
         self.score = score;
@@ -49,9 +60,10 @@ impl Document {
 }
 
 impl Default for Document {
-    /// Provides a default `Document` with an empty `page_content`, an empty `metadata` map and a `score` of 0.
+    /// Provides a default `Document` with no `id`, an empty `page_content`, an empty `metadata` map and a `score` of 0.
     fn default() -> Self {
         Document {
+            id: None,
             page_content: "".to_string(),
             metadata: HashMap::new(),
             score: 0.0,
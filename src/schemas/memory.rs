@@ -1,3 +1,5 @@
+use crate::memory::Transcript;
+
 use super::messages::Message;
 
 pub trait BaseMemory: Send + Sync {
@@ -19,6 +21,19 @@ pub trait BaseMemory: Send + Sync {
 
     fn clear(&mut self);
 
+    /// Exports the messages as a `Transcript`, which can be saved as JSON.
+    fn export_transcript(&self) -> Transcript {
+        Transcript::new(self.messages())
+    }
+
+    /// Replaces the messages with the ones of `transcript`.
+    fn import_transcript(&mut self, transcript: &Transcript) {
+        self.clear();
+        for message in &transcript.messages {
+            self.add_message(message.clone());
+        }
+    }
+
     fn to_string(&self) -> String {
         self.messages()
             .iter()
@@ -2,6 +2,8 @@ use serde::Deserialize;
 use serde::Serialize;
 use serde_json::Value;
 
+use super::FunctionCallResponse;
+
 /// Enum `MessageType` represents the type of a message.
 /// It can be a `SystemMessage`, `AIMessage`, or `HumanMessage`.
 ///
@@ -41,7 +43,7 @@ impl MessageType {
 }
 
 /// Struct `ImageContent` represents an image provided to an LLM.
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
 pub struct ImageContent {
     pub image_url: String,
     pub detail: Option<String>,
@@ -56,21 +58,89 @@ impl<S: AsRef<str>> From<S> for ImageContent {
     }
 }
 
+/// Enum `ContentPart` represents a part of the content of a multimodal message.
+///
+/// # Usage
+/// ```rust,ignore
+/// let parts = vec![
+///     ContentPart::text("What is in this image?"),
+///     ContentPart::image("https://example.com/cat.png"),
+/// ];
+/// let message = Message::new_human_message_with_parts(parts);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    Text { text: String },
+    Image(ImageContent),
+}
+
+impl ContentPart {
+    pub fn text<S: Into<String>>(text: S) -> Self {
+        ContentPart::Text { text: text.into() }
+    }
+
+    pub fn image<T: Into<ImageContent>>(image: T) -> Self {
+        ContentPart::Image(image.into())
+    }
+}
+
+/// Struct `ToolCall` represents a call of a tool requested by the model in an AI message.
+/// The result of the call is sent back in a tool message with the `id` of the call.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ToolCall {
+    pub id: String,
+    pub name: String,
+    /// The arguments of the call as JSON text, passed as is to the tool, which knows how to
+    /// parse them.
+    pub arguments: String,
+}
+
+impl ToolCall {
+    pub fn new<I: Into<String>, N: Into<String>, A: Into<String>>(
+        id: I,
+        name: N,
+        arguments: A,
+    ) -> Self {
+        ToolCall {
+            id: id.into(),
+            name: name.into(),
+            arguments: arguments.into(),
+        }
+    }
+}
+
+impl From<FunctionCallResponse> for ToolCall {
+    fn from(response: FunctionCallResponse) -> Self {
+        ToolCall {
+            id: response.id,
+            name: response.function.name,
+            arguments: response.function.arguments,
+        }
+    }
+}
+
 /// Struct `Message` represents a message with its content and type.
 ///
+/// The `content` of a multimodal message is the text of its `content_parts`. Tool messages
+/// carry the `id` of the tool call they answer.
+///
 /// # Usage
 /// ```rust,ignore
 /// let human_message = Message::new_human_message("Hello");
 /// let system_message = Message::new_system_message("System Alert");
 /// let ai_message = Message::new_ai_message("AI Response");
+/// let ai_message = Message::new_ai_message("")
+///     .with_tool_calls(vec![ToolCall::new("call_1", "search", r#"{"query":"rust"}"#)]);
+/// let tool_message = Message::new_tool_message("Rust is a language", "call_1");
 /// ```
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
 pub struct Message {
     pub content: String,
     pub message_type: MessageType,
     pub id: Option<String>,
-    pub tool_calls: Option<Value>,
-    pub images: Option<Vec<ImageContent>>,
+    pub tool_calls: Option<Vec<ToolCall>>,
+    pub content_parts: Option<Vec<ContentPart>>,
 }
 
 impl Message {
@@ -81,17 +151,30 @@ impl Message {
             message_type: MessageType::HumanMessage,
             id: None,
             tool_calls: None,
-            images: None,
+            content_parts: None,
         }
     }
 
     pub fn new_human_message_with_images<T: Into<ImageContent>>(images: Vec<T>) -> Self {
+        Self::new_human_message_with_parts(images.into_iter().map(ContentPart::image).collect())
+    }
+
+    // Function to create a new Human message made of text and images
+    pub fn new_human_message_with_parts(parts: Vec<ContentPart>) -> Self {
+        let content = parts
+            .iter()
+            .filter_map(|part| match part {
+                ContentPart::Text { text } => Some(text.as_str()),
+                ContentPart::Image(_) => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
         Message {
-            content: String::default(),
+            content,
             message_type: MessageType::HumanMessage,
             id: None,
             tool_calls: None,
-            images: Some(images.into_iter().map(|i| i.into()).collect()),
+            content_parts: Some(parts),
         }
     }
 
@@ -102,7 +185,7 @@ impl Message {
             message_type: MessageType::SystemMessage,
             id: None,
             tool_calls: None,
-            images: None,
+            content_parts: None,
         }
     }
 
@@ -113,7 +196,7 @@ impl Message {
             message_type: MessageType::AIMessage,
             id: None,
             tool_calls: None,
-            images: None,
+            content_parts: None,
         }
     }
 
@@ -124,20 +207,19 @@ impl Message {
             message_type: MessageType::ToolMessage,
             id: Some(id.into()),
             tool_calls: None,
-            images: None,
+            content_parts: None,
         }
     }
 
-    /// Sets the tool calls for the OpenAI-like API call.
+    /// Sets the tool calls requested by the model in an AI message.
     ///
-    /// Use this method when you need to specify tool calls in the configuration.
-    /// This is particularly useful in scenarios where interactions with specific
-    /// tools are required for operation.
+    /// Each call is answered by a tool message created with `new_tool_message` and the `id`
+    /// of the call.
     ///
     /// # Arguments
     ///
-    /// * `tool_calls` - A `serde_json::Value` representing the tool call configurations.
-    pub fn with_tool_calls(mut self, tool_calls: Value) -> Self {
+    /// * `tool_calls` - The calls, in the order the model requested them.
+    pub fn with_tool_calls(mut self, tool_calls: Vec<ToolCall>) -> Self {
         self.tool_calls = Some(tool_calls);
         self
     }
@@ -154,3 +236,29 @@ impl Message {
             .join("\n")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_serde_round_trip() {
+        let messages = vec![
+            Message::new_system_message("You are helpful"),
+            Message::new_human_message_with_parts(vec![
+                ContentPart::text("What is this?"),
+                ContentPart::image("https://example.com/cat.png"),
+            ]),
+            Message::new_ai_message("").with_tool_calls(vec![ToolCall::new(
+                "call_1",
+                "search",
+                r#"{"query":"cat"}"#,
+            )]),
+            Message::new_tool_message("A cat", "call_1"),
+        ];
+
+        let json = serde_json::to_string(&messages).unwrap();
+        let deserialized: Vec<Message> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, messages);
+    }
+}
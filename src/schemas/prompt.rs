@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use super::messages::Message;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PromptValue {
     messages: Vec<Message>,
 }
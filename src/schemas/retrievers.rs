@@ -1,12 +1,44 @@
-use std::error::Error;
+use std::time::Duration;
 
 use async_trait::async_trait;
 
+use crate::{
+    error::LangChainError,
+    resilience::{RetryPolicy, WithFallbacks, WithRetry, WithTimeout},
+};
+
 use super::Document;
 
 #[async_trait]
 pub trait Retriever: Sync + Send {
-    async fn get_relevant_documents(&self, query: &str) -> Result<Vec<Document>, Box<dyn Error>>;
+    async fn get_relevant_documents(&self, query: &str) -> Result<Vec<Document>, LangChainError>;
+
+    /// Retries the calls that fail with a transient error, following `policy`.
+    fn with_retry(self, policy: RetryPolicy) -> WithRetry<Self>
+    where
+        Self: Sized,
+    {
+        WithRetry::new(self, policy)
+    }
+
+    /// Calls the `fallbacks` in order when a call fails.
+    fn with_fallbacks(
+        self,
+        fallbacks: Vec<Box<dyn Retriever>>,
+    ) -> WithFallbacks<Self, Box<dyn Retriever>>
+    where
+        Self: Sized,
+    {
+        WithFallbacks::new(self, fallbacks)
+    }
+
+    /// Fails the calls that take longer than `timeout` with `LangChainError::Timeout`.
+    fn with_timeout(self, timeout: Duration) -> WithTimeout<Self>
+    where
+        Self: Sized,
+    {
+        WithTimeout::new(self, timeout)
+    }
 }
 
 impl<R> From<R> for Box<dyn Retriever>
@@ -1,9 +1,10 @@
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::io::{self, Write};
 
 use crate::language_models::TokenUsage;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StreamData {
     pub value: Value,
     pub tokens: Option<TokenUsage>,
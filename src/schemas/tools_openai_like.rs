@@ -5,14 +5,14 @@ use serde_json::Value;
 
 use crate::tools::Tool;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum FunctionCallBehavior {
     None,
     Auto,
     Named(String),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct FunctionDefinition {
     pub name: String,
     pub description: String,
@@ -41,7 +41,7 @@ impl FunctionDefinition {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct FunctionCallResponse {
     pub id: String,
     #[serde(rename = "type")]
@@ -49,7 +49,7 @@ pub struct FunctionCallResponse {
     pub function: FunctionDetail,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct FunctionDetail {
     pub name: String,
     ///this should be an string, and this should be passed to the tool, to
@@ -4,11 +4,10 @@ use futures_util::future::try_join_all;
 
 use crate::{
     chain::{LLMChain, LLMChainBuilder},
-    embedding::{openai::OpenAiEmbedder, Embedder},
+    embedding::Embedder,
     language_models::llm::LLM,
-    llm::openai::OpenAI,
     prompt::HumanMessagePromptTemplate,
-    semantic_router::{Index, MemoryIndex, RouteLayerBuilderError, Router},
+    semantic_router::{Index, RouteLayerBuilderError, Router},
     template_jinja2,
 };
 
@@ -50,8 +49,13 @@ pub struct RouteLayerBuilder {
     top_k: usize,
     aggregation_method: AggregationMethod,
 }
+#[cfg(feature = "openai")]
 impl Default for RouteLayerBuilder {
     fn default() -> Self {
+        use crate::{
+            embedding::openai::OpenAiEmbedder, llm::openai::OpenAI, semantic_router::MemoryIndex,
+        };
+
         Self::new()
             .embedder(OpenAiEmbedder::default())
             .llm(OpenAI::default())
@@ -75,7 +79,7 @@ impl RouteLayerBuilder {
     pub fn top_k(mut self, top_k: usize) -> Self {
         let mut top_k = top_k;
         if top_k == 0 {
-            log::warn!("top_k cannot be 0, setting it to 1");
+            tracing::warn!("top_k cannot be 0, setting it to 1");
             top_k = 1;
         }
         self.top_k = top_k;
@@ -206,7 +206,7 @@ impl RouteLayer {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "openai"))]
 mod tests {
 
     use crate::{embedding::openai::OpenAiEmbedder, semantic_router::RouteLayerBuilder};
@@ -0,0 +1,256 @@
+use async_trait::async_trait;
+
+use super::{
+    RecursiveCharacterSplitterOptions, RecursiveCharacterTextSplitter, TextSplitter,
+    TextSplitterError,
+};
+
+/// Programming languages with dedicated separator sets for the `CodeSplitter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeLanguage {
+    Rust,
+    Python,
+    Javascript,
+    Typescript,
+    Go,
+    Java,
+    C,
+    Cpp,
+}
+
+impl CodeLanguage {
+    /// Guesses the language from a file extension such as `rs` or `.py`.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.trim_start_matches('.').to_lowercase().as_str() {
+            "rs" => Some(CodeLanguage::Rust),
+            "py" => Some(CodeLanguage::Python),
+            "js" | "jsx" | "mjs" | "cjs" => Some(CodeLanguage::Javascript),
+            "ts" | "tsx" => Some(CodeLanguage::Typescript),
+            "go" => Some(CodeLanguage::Go),
+            "java" => Some(CodeLanguage::Java),
+            "c" | "h" => Some(CodeLanguage::C),
+            "cpp" | "cc" | "cxx" | "hpp" | "hh" => Some(CodeLanguage::Cpp),
+            _ => None,
+        }
+    }
+
+    /// Separators from the coarsest syntactic boundary (items, classes, functions) down to
+    /// control flow, blank lines, lines, words and characters.
+    pub fn separators(&self) -> Vec<&'static str> {
+        let separators: &[&str] = match self {
+            CodeLanguage::Rust => &[
+                "\nimpl ",
+                "\nimpl<",
+                "\npub trait ",
+                "\ntrait ",
+                "\npub struct ",
+                "\nstruct ",
+                "\npub enum ",
+                "\nenum ",
+                "\npub mod ",
+                "\nmod ",
+                "\npub fn ",
+                "\npub async fn ",
+                "\nfn ",
+                "\nasync fn ",
+                "\n    pub fn ",
+                "\n    pub async fn ",
+                "\n    fn ",
+                "\n    async fn ",
+                "\nconst ",
+                "\nstatic ",
+                "\nlet ",
+                "\nif ",
+                "\nwhile ",
+                "\nfor ",
+                "\nloop ",
+                "\nmatch ",
+            ],
+            CodeLanguage::Python => &[
+                "\nclass ",
+                "\ndef ",
+                "\nasync def ",
+                "\n    def ",
+                "\n    async def ",
+                "\n\tdef ",
+            ],
+            CodeLanguage::Javascript => &[
+                "\nexport ",
+                "\nclass ",
+                "\nfunction ",
+                "\nasync function ",
+                "\nconst ",
+                "\nlet ",
+                "\nvar ",
+                "\nif ",
+                "\nfor ",
+                "\nwhile ",
+                "\nswitch ",
+                "\ncase ",
+                "\ndefault ",
+            ],
+            CodeLanguage::Typescript => &[
+                "\nexport ",
+                "\nenum ",
+                "\ninterface ",
+                "\nnamespace ",
+                "\ntype ",
+                "\nclass ",
+                "\nfunction ",
+                "\nasync function ",
+                "\nconst ",
+                "\nlet ",
+                "\nvar ",
+                "\nif ",
+                "\nfor ",
+                "\nwhile ",
+                "\nswitch ",
+                "\ncase ",
+                "\ndefault ",
+            ],
+            CodeLanguage::Go => &[
+                "\nfunc ",
+                "\ntype ",
+                "\nvar ",
+                "\nconst ",
+                "\nif ",
+                "\nfor ",
+                "\nswitch ",
+                "\ncase ",
+            ],
+            CodeLanguage::Java => &[
+                "\nclass ",
+                "\ninterface ",
+                "\nenum ",
+                "\npublic ",
+                "\nprotected ",
+                "\nprivate ",
+                "\n    public ",
+                "\n    protected ",
+                "\n    private ",
+                "\nstatic ",
+                "\nif ",
+                "\nfor ",
+                "\nwhile ",
+                "\nswitch ",
+                "\ncase ",
+            ],
+            CodeLanguage::C | CodeLanguage::Cpp => &[
+                "\nnamespace ",
+                "\nclass ",
+                "\nstruct ",
+                "\nvoid ",
+                "\nint ",
+                "\nfloat ",
+                "\ndouble ",
+                "\nif ",
+                "\nfor ",
+                "\nwhile ",
+                "\nswitch ",
+                "\ncase ",
+            ],
+        };
+
+        separators
+            .iter()
+            .copied()
+            .chain(["\n\n", "\n", " ", ""])
+            .collect()
+    }
+}
+
+/// A `RecursiveCharacterTextSplitter` using the separators of a programming language, so
+/// chunks break between items and functions instead of in the middle of a signature.
+///
+/// # Usage
+/// ```rust,ignore
+/// let splitter = CodeSplitter::new(
+///     CodeLanguage::Rust,
+///     RecursiveCharacterSplitterOptions::default().with_chunk_size(1500),
+/// );
+/// let chunks = splitter.split_text(&source).await?;
+/// ```
+pub struct CodeSplitter {
+    splitter: RecursiveCharacterTextSplitter,
+}
+
+impl CodeSplitter {
+    /// Creates a splitter for `language`; the separators of `options` are replaced by the
+    /// language ones.
+    pub fn new(language: CodeLanguage, options: RecursiveCharacterSplitterOptions) -> Self {
+        let options = options
+            .with_separators(language.separators())
+            .with_keep_separator(true);
+        CodeSplitter {
+            splitter: RecursiveCharacterTextSplitter::new(options),
+        }
+    }
+
+    pub fn from_language(language: CodeLanguage) -> Self {
+        Self::new(language, RecursiveCharacterSplitterOptions::default())
+    }
+}
+
+#[async_trait]
+impl TextSplitter for CodeSplitter {
+    async fn split_text(&self, text: &str) -> Result<Vec<String>, TextSplitterError> {
+        self.splitter.split_text(text).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_code_splitter() {
+        let code = r#"use std::fmt;
+
+pub struct Person {
+    name: String,
+}
+
+impl Person {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+fn main() {
+    let person = Person::new("Ferris".to_string());
+    println!("{}", person.name());
+}"#;
+
+        let splitter = CodeSplitter::new(
+            CodeLanguage::Rust,
+            RecursiveCharacterSplitterOptions::default()
+                .with_chunk_size(120)
+                .with_chunk_overlap(0),
+        );
+        let chunks = splitter.split_text(code).await.unwrap();
+
+        assert_eq!(
+            chunks,
+            vec![
+                "use std::fmt;\n\npub struct Person {\n    name: String,\n}",
+                "impl Person {\n    pub fn new(name: String) -> Self {\n        Self { name }\n    }",
+                "pub fn name(&self) -> &str {\n        &self.name\n    }\n}",
+                "fn main() {\n    let person = Person::new(\"Ferris\".to_string());\n    println!(\"{}\", person.name());\n}",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_code_language_from_extension() {
+        assert_eq!(CodeLanguage::from_extension("rs"), Some(CodeLanguage::Rust));
+        assert_eq!(
+            CodeLanguage::from_extension(".TSX"),
+            Some(CodeLanguage::Typescript)
+        );
+        assert_eq!(CodeLanguage::from_extension("md"), None);
+    }
+}
@@ -1,6 +1,8 @@
 use text_splitter::ChunkConfigError;
 use thiserror::Error;
 
+use crate::embedding::EmbedderError;
+
 #[derive(Error, Debug)]
 pub enum TextSplitterError {
     #[error("Empty input text")]
@@ -21,6 +23,9 @@ pub enum TextSplitterError {
     #[error("Invalid chunk overlap and size")]
     InvalidSplitterOptions,
 
+    #[error("Embedder error: {0}")]
+    EmbedderError(#[from] EmbedderError),
+
     #[error("Error: {0}")]
     OtherError(String),
 }
@@ -0,0 +1,404 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use scraper::{ElementRef, Html, Node};
+use serde_json::Value;
+
+use crate::schemas::Document;
+
+use super::{
+    add_chunk_provenance, RecursiveCharacterSplitterOptions, RecursiveCharacterTextSplitter,
+    TextSplitter, TextSplitterError,
+};
+
+const SKIPPED_TAGS: &[&str] = &[
+    "head", "script", "style", "noscript", "template", "svg", "iframe",
+];
+
+const BLOCK_TAGS: &[&str] = &[
+    "address",
+    "aside",
+    "blockquote",
+    "br",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "table",
+    "tr",
+    "ul",
+];
+
+/// Splits HTML on structural tags: a new chunk starts at every configured heading
+/// (`h1`–`h4` by default) and at every `section` or `article` element.
+///
+/// Each chunk records the heading hierarchy in its metadata, like
+/// `MarkdownHeaderSplitter` (`"Header 1": "Guide"`, `header_path`), along with an
+/// `element_path` such as `"article#post > section#install > h2"` locating it in the page.
+/// Headings found inside a `section` or `article` only apply until that element closes.
+///
+/// The splitter expects raw HTML, so pair it with `HtmlLoader::with_keep_html` or
+/// `WebLoaderOptions::with_keep_html`.
+///
+/// # Usage
+/// ```rust,ignore
+/// let splitter = HtmlSectionSplitter::default()
+///     .with_section_splitter(RecursiveCharacterSplitterOptions::default());
+/// let documents = loader.load_and_split(splitter).await?;
+/// ```
+pub struct HtmlSectionSplitter {
+    headers_to_split_on: Vec<(String, String)>,
+    section_tags: Vec<String>,
+    strip_headers: bool,
+    section_splitter: Option<RecursiveCharacterTextSplitter>,
+}
+
+impl Default for HtmlSectionSplitter {
+    fn default() -> Self {
+        HtmlSectionSplitter::new(vec![
+            ("h1", "Header 1"),
+            ("h2", "Header 2"),
+            ("h3", "Header 3"),
+            ("h4", "Header 4"),
+        ])
+    }
+}
+
+impl HtmlSectionSplitter {
+    /// Creates a splitter for the given heading tags and their metadata keys, from the
+    /// outermost to the innermost level, e.g. `vec![("h1", "Header 1"), ("h2", "Header 2")]`.
+    pub fn new<S: Into<String>>(headers_to_split_on: Vec<(S, S)>) -> Self {
+        HtmlSectionSplitter {
+            headers_to_split_on: headers_to_split_on
+                .into_iter()
+                .map(|(tag, name)| (tag.into().to_lowercase(), name.into()))
+                .collect(),
+            section_tags: vec!["section".to_string(), "article".to_string()],
+            strip_headers: true,
+            section_splitter: None,
+        }
+    }
+
+    /// Sets the container tags that start a new chunk, `section` and `article` by default.
+    pub fn with_section_tags<S: Into<String>>(mut self, section_tags: Vec<S>) -> Self {
+        self.section_tags = section_tags
+            .into_iter()
+            .map(|tag| tag.into().to_lowercase())
+            .collect();
+        self
+    }
+
+    /// Removes the heading text from the chunk content (the default).
+    pub fn with_strip_headers(mut self, strip_headers: bool) -> Self {
+        self.strip_headers = strip_headers;
+        self
+    }
+
+    /// Splits sections that are too long with a recursive character splitter.
+    pub fn with_section_splitter(mut self, options: RecursiveCharacterSplitterOptions) -> Self {
+        self.section_splitter = Some(RecursiveCharacterTextSplitter::new(options));
+        self
+    }
+
+    /// Splits the HTML into the text of each section along with its metadata.
+    pub fn split_sections(&self, html: &str) -> Vec<(String, HashMap<String, Value>)> {
+        let html = Html::parse_document(html);
+        let mut walker = SectionWalker {
+            splitter: self,
+            headers: Vec::new(),
+            path: Vec::new(),
+            current: String::new(),
+            pending_space: false,
+            sections: Vec::new(),
+        };
+        walker.walk(html.root_element());
+        walker.flush();
+        walker.sections
+    }
+}
+
+#[derive(Clone)]
+struct Heading {
+    level: usize,
+    tag: String,
+    name: String,
+    title: String,
+}
+
+struct SectionWalker<'a> {
+    splitter: &'a HtmlSectionSplitter,
+    headers: Vec<Heading>,
+    path: Vec<String>,
+    current: String,
+    pending_space: bool,
+    sections: Vec<(String, HashMap<String, Value>)>,
+}
+
+impl SectionWalker<'_> {
+    fn walk(&mut self, element: ElementRef) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => self.push_text(text),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.visit(child);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn visit(&mut self, element: ElementRef) {
+        let tag = element.value().name();
+        if SKIPPED_TAGS.contains(&tag) {
+            return;
+        }
+
+        if let Some(level) = self
+            .splitter
+            .headers_to_split_on
+            .iter()
+            .position(|(header_tag, _)| header_tag == tag)
+        {
+            self.flush();
+            let title = collapse_whitespace(&element.text().collect::<String>());
+            self.headers.retain(|heading| heading.level < level);
+            self.headers.push(Heading {
+                level,
+                tag: tag.to_string(),
+                name: self.splitter.headers_to_split_on[level].1.clone(),
+                title: title.clone(),
+            });
+            if !self.splitter.strip_headers {
+                self.current.push_str(&title);
+                self.push_break();
+            }
+            return;
+        }
+
+        if self.splitter.section_tags.iter().any(|t| t == tag) {
+            self.flush();
+            let headers = self.headers.clone();
+            self.path.push(match element.value().id() {
+                Some(id) => format!("{tag}#{id}"),
+                None => tag.to_string(),
+            });
+
+            self.walk(element);
+            self.flush();
+
+            self.path.pop();
+            self.headers = headers;
+            return;
+        }
+
+        if tag == "pre" {
+            self.push_break();
+            self.current.push_str(&element.text().collect::<String>());
+            self.push_break();
+            return;
+        }
+
+        let is_block = BLOCK_TAGS.contains(&tag);
+        if is_block {
+            self.push_break();
+        }
+        self.walk(element);
+        if is_block {
+            self.push_break();
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let collapsed = collapse_whitespace(text);
+        if collapsed.is_empty() {
+            self.pending_space |= !text.is_empty();
+            return;
+        }
+        let needs_space = self.pending_space || text.starts_with(char::is_whitespace);
+        if needs_space && !self.current.is_empty() && !self.current.ends_with(['\n', ' ']) {
+            self.current.push(' ');
+        }
+        self.current.push_str(&collapsed);
+        self.pending_space = text.ends_with(char::is_whitespace);
+    }
+
+    fn push_break(&mut self) {
+        let trimmed_len = self.current.trim_end_matches(' ').len();
+        self.current.truncate(trimmed_len);
+        if !self.current.is_empty() && !self.current.ends_with('\n') {
+            self.current.push('\n');
+        }
+        self.pending_space = false;
+    }
+
+    fn flush(&mut self) {
+        let content = self.current.trim().to_string();
+        self.current.clear();
+        self.pending_space = false;
+        if content.is_empty() {
+            return;
+        }
+
+        let mut metadata: HashMap<String, Value> = self
+            .headers
+            .iter()
+            .map(|heading| (heading.name.clone(), Value::from(heading.title.as_str())))
+            .collect();
+        if !self.headers.is_empty() {
+            let path = self
+                .headers
+                .iter()
+                .map(|heading| heading.title.as_str())
+                .collect::<Vec<_>>()
+                .join(" > ");
+            metadata.insert("header_path".to_string(), Value::from(path));
+        }
+
+        let element_path = self
+            .path
+            .iter()
+            .map(String::as_str)
+            .chain(self.headers.last().map(|heading| heading.tag.as_str()))
+            .collect::<Vec<_>>()
+            .join(" > ");
+        if !element_path.is_empty() {
+            metadata.insert("element_path".to_string(), Value::from(element_path));
+        }
+
+        self.sections.push((content, metadata));
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[async_trait]
+impl TextSplitter for HtmlSectionSplitter {
+    async fn split_text(&self, text: &str) -> Result<Vec<String>, TextSplitterError> {
+        let documents = self.create_documents(&[text.to_string()], &[]).await?;
+        Ok(documents.into_iter().map(|doc| doc.page_content).collect())
+    }
+
+    async fn create_documents(
+        &self,
+        text: &[String],
+        metadatas: &[HashMap<String, Value>],
+    ) -> Result<Vec<Document>, TextSplitterError> {
+        let mut metadatas = metadatas.to_vec();
+        if metadatas.is_empty() {
+            metadatas = vec![HashMap::new(); text.len()];
+        }
+
+        if text.len() != metadatas.len() {
+            return Err(TextSplitterError::MetadataTextMismatch);
+        }
+
+        let mut documents = Vec::new();
+        for (text, metadata) in text.iter().zip(metadatas) {
+            let mut text_documents = Vec::new();
+            for (section, section_metadata) in self.split_sections(text) {
+                let mut chunk_metadata = metadata.clone();
+                chunk_metadata.extend(section_metadata);
+
+                let chunks = match &self.section_splitter {
+                    Some(splitter) => splitter.split_text(&section).await?,
+                    None => vec![section],
+                };
+                for chunk in chunks {
+                    text_documents.push(Document::new(chunk).with_metadata(chunk_metadata.clone()));
+                }
+            }
+            add_chunk_provenance(&mut text_documents, text, &metadata);
+            documents.extend(text_documents);
+        }
+
+        Ok(documents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_html_section_splitter() {
+        let html = r#"<html>
+<head><title>Ignored</title><style>p { color: red; }</style></head>
+<body>
+  <p>Intro   text</p>
+  <h1>Guide</h1>
+  <p>Welcome to the <b>guide</b>.</p>
+  <article id="post">
+    <h2>Install</h2>
+    <p>Run the installer.</p>
+    <section id="linux">
+      <h3>Linux</h3>
+      <ul><li>apt</li><li>dnf</li></ul>
+    </section>
+  </article>
+  <p>Footer</p>
+</body>
+</html>"#;
+
+        let splitter = HtmlSectionSplitter::default();
+        let documents = splitter
+            .create_documents(&[html.to_string()], &[])
+            .await
+            .unwrap();
+
+        assert_eq!(documents.len(), 5);
+        assert_eq!(documents[0].page_content, "Intro text");
+        assert!(!documents[0].metadata.contains_key("header_path"));
+
+        assert_eq!(documents[1].page_content, "Welcome to the guide.");
+        assert_eq!(documents[1].metadata.get("Header 1").unwrap(), "Guide");
+        assert_eq!(documents[1].metadata.get("element_path").unwrap(), "h1");
+
+        assert_eq!(documents[2].page_content, "Run the installer.");
+        assert_eq!(
+            documents[2].metadata.get("element_path").unwrap(),
+            "article#post > h2"
+        );
+
+        assert_eq!(documents[3].page_content, "apt\ndnf");
+        assert_eq!(
+            documents[3].metadata.get("header_path").unwrap(),
+            "Guide > Install > Linux"
+        );
+        assert_eq!(
+            documents[3].metadata.get("element_path").unwrap(),
+            "article#post > section#linux > h3"
+        );
+
+        // Headings of the article no longer apply once it is closed
+        assert_eq!(documents[4].page_content, "Footer");
+        assert_eq!(documents[4].metadata.get("header_path").unwrap(), "Guide");
+        assert!(!documents[4].metadata.contains_key("Header 2"));
+    }
+
+    #[tokio::test]
+    async fn test_html_section_splitter_keep_headers() {
+        let splitter = HtmlSectionSplitter::default().with_strip_headers(false);
+        let chunks = splitter
+            .split_text("<h1>Title</h1><p>Body</p><h2>Sub</h2><p>More</p>")
+            .await
+            .unwrap();
+        assert_eq!(chunks, vec!["Title\nBody", "Sub\nMore"]);
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::schemas::Document;
+
+use super::{
+    add_chunk_provenance, RecursiveCharacterSplitterOptions, RecursiveCharacterTextSplitter,
+    TextSplitter, TextSplitterError,
+};
+
+/// Splits markdown on headings, recording the heading hierarchy of each chunk in its
+/// metadata: one entry per configured heading level (e.g. `"Header 1": "Install"`) and a
+/// `header_path` such as `"Guide > Install > Linux"`.
+///
+/// Headings inside fenced code blocks are ignored. Sections longer than the optional
+/// `section_splitter` chunk size are further split with a `RecursiveCharacterTextSplitter`,
+/// each piece keeping the section's metadata.
+///
+/// # Usage
+/// ```rust,ignore
+/// let splitter = MarkdownHeaderSplitter::default().with_strip_headers(false);
+/// let documents = splitter.split_documents(&[Document::new(markdown)]).await?;
+/// ```
+pub struct MarkdownHeaderSplitter {
+    headers_to_split_on: Vec<(String, String)>,
+    strip_headers: bool,
+    section_splitter: Option<RecursiveCharacterTextSplitter>,
+}
+
+impl Default for MarkdownHeaderSplitter {
+    fn default() -> Self {
+        MarkdownHeaderSplitter::new(vec![
+            ("#", "Header 1"),
+            ("##", "Header 2"),
+            ("###", "Header 3"),
+        ])
+    }
+}
+
+impl MarkdownHeaderSplitter {
+    /// Creates a splitter for the given heading markers and their metadata keys,
+    /// e.g. `vec![("#", "Header 1"), ("##", "Header 2")]`.
+    pub fn new<S: Into<String>>(headers_to_split_on: Vec<(S, S)>) -> Self {
+        let mut headers_to_split_on: Vec<(String, String)> = headers_to_split_on
+            .into_iter()
+            .map(|(marker, name)| (marker.into(), name.into()))
+            .collect();
+        // Longest markers first so "##" is not mistaken for "#"
+        headers_to_split_on.sort_by_key(|(marker, _)| std::cmp::Reverse(marker.len()));
+
+        MarkdownHeaderSplitter {
+            headers_to_split_on,
+            strip_headers: true,
+            section_splitter: None,
+        }
+    }
+
+    /// Removes the heading lines from the chunk content (the default).
+    pub fn with_strip_headers(mut self, strip_headers: bool) -> Self {
+        self.strip_headers = strip_headers;
+        self
+    }
+
+    /// Splits sections that are too long with a recursive character splitter.
+    pub fn with_section_splitter(mut self, options: RecursiveCharacterSplitterOptions) -> Self {
+        self.section_splitter = Some(RecursiveCharacterTextSplitter::new(options));
+        self
+    }
+
+    fn match_header<'a>(&self, line: &'a str) -> Option<(usize, &'a str)> {
+        let line = line.trim_start();
+        self.headers_to_split_on
+            .iter()
+            .enumerate()
+            .find_map(|(i, (marker, _))| {
+                let rest = line.strip_prefix(marker.as_str())?;
+                if rest.is_empty() || rest.starts_with(' ') {
+                    Some((i, rest.trim()))
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// Splits the text into sections along with the heading metadata of each section.
+    pub fn split_sections(&self, text: &str) -> Vec<(String, HashMap<String, Value>)> {
+        let mut sections = Vec::new();
+        // (marker length, metadata key, heading text)
+        let mut stack: Vec<(usize, String, String)> = Vec::new();
+        let mut current: Vec<&str> = Vec::new();
+        let mut fence: Option<&str> = None;
+
+        let flush = |current: &mut Vec<&str>,
+                     stack: &[(usize, String, String)],
+                     sections: &mut Vec<(String, HashMap<String, Value>)>| {
+            let content = current.join("\n").trim().to_string();
+            current.clear();
+            if content.is_empty() {
+                return;
+            }
+            let mut metadata: HashMap<String, Value> = stack
+                .iter()
+                .map(|(_, name, title)| (name.clone(), Value::from(title.as_str())))
+                .collect();
+            if !stack.is_empty() {
+                let path = stack
+                    .iter()
+                    .map(|(_, _, title)| title.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" > ");
+                metadata.insert("header_path".to_string(), Value::from(path));
+            }
+            sections.push((content, metadata));
+        };
+
+        for line in text.lines() {
+            let trimmed = line.trim_start();
+            if let Some(open) = fence {
+                if trimmed.starts_with(open) {
+                    fence = None;
+                }
+                current.push(line);
+                continue;
+            }
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                fence = Some(&trimmed[..3]);
+                current.push(line);
+                continue;
+            }
+
+            match self.match_header(line) {
+                Some((index, title)) => {
+                    flush(&mut current, &stack, &mut sections);
+                    let (marker, name) = &self.headers_to_split_on[index];
+                    stack.retain(|(level, _, _)| *level < marker.len());
+                    stack.push((marker.len(), name.clone(), title.to_string()));
+                    if !self.strip_headers {
+                        current.push(line);
+                    }
+                }
+                None => current.push(line),
+            }
+        }
+        flush(&mut current, &stack, &mut sections);
+
+        sections
+    }
+}
+
+#[async_trait]
+impl TextSplitter for MarkdownHeaderSplitter {
+    async fn split_text(&self, text: &str) -> Result<Vec<String>, TextSplitterError> {
+        let documents = self.create_documents(&[text.to_string()], &[]).await?;
+        Ok(documents.into_iter().map(|doc| doc.page_content).collect())
+    }
+
+    async fn create_documents(
+        &self,
+        text: &[String],
+        metadatas: &[HashMap<String, Value>],
+    ) -> Result<Vec<Document>, TextSplitterError> {
+        let mut metadatas = metadatas.to_vec();
+        if metadatas.is_empty() {
+            metadatas = vec![HashMap::new(); text.len()];
+        }
+
+        if text.len() != metadatas.len() {
+            return Err(TextSplitterError::MetadataTextMismatch);
+        }
+
+        let mut documents = Vec::new();
+        for (text, metadata) in text.iter().zip(metadatas) {
+            let mut text_documents = Vec::new();
+            for (section, section_metadata) in self.split_sections(text) {
+                let mut chunk_metadata = metadata.clone();
+                chunk_metadata.extend(section_metadata);
+
+                let chunks = match &self.section_splitter {
+                    Some(splitter) => splitter.split_text(&section).await?,
+                    None => vec![section],
+                };
+                for chunk in chunks {
+                    text_documents.push(Document::new(chunk).with_metadata(chunk_metadata.clone()));
+                }
+            }
+            add_chunk_provenance(&mut text_documents, text, &metadata);
+            documents.extend(text_documents);
+        }
+
+        Ok(documents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_markdown_header_splitter() {
+        let markdown = "Intro text\n\n# Guide\nWelcome\n\n## Install\n```sh\n# not a heading\ncargo add langchain-rust\n```\n\n### Linux\nUse apt\n\n## Usage\nCall it";
+
+        let splitter = MarkdownHeaderSplitter::default();
+        let documents = splitter
+            .create_documents(&[markdown.to_string()], &[])
+            .await
+            .unwrap();
+
+        assert_eq!(documents.len(), 5);
+        assert_eq!(documents[0].page_content, "Intro text");
+        assert!(!documents[0].metadata.contains_key("header_path"));
+
+        assert_eq!(
+            documents[2].page_content,
+            "```sh\n# not a heading\ncargo add langchain-rust\n```"
+        );
+        assert_eq!(documents[2].metadata.get("Header 1").unwrap(), "Guide");
+        assert_eq!(documents[2].metadata.get("Header 2").unwrap(), "Install");
+
+        assert_eq!(
+            documents[3].metadata.get("header_path").unwrap(),
+            "Guide > Install > Linux"
+        );
+
+        assert_eq!(documents[4].page_content, "Call it");
+        assert_eq!(documents[4].metadata.get("chunk_index").unwrap(), 4);
+        let start = documents[4].metadata["start_offset"].as_u64().unwrap() as usize;
+        assert!(markdown[start..].starts_with("Call it"));
+        assert_eq!(
+            documents[4].metadata.get("header_path").unwrap(),
+            "Guide > Usage"
+        );
+        assert!(!documents[4].metadata.contains_key("Header 3"));
+    }
+
+    #[tokio::test]
+    async fn test_markdown_header_splitter_keep_headers() {
+        let splitter = MarkdownHeaderSplitter::default().with_strip_headers(false);
+        let chunks = splitter
+            .split_text("# Title\nBody\n## Sub\nMore")
+            .await
+            .unwrap();
+        assert_eq!(chunks, vec!["# Title\nBody", "## Sub\nMore"]);
+    }
+}
@@ -1,13 +1,25 @@
+mod code_splitter;
 mod error;
+mod html_section_splitter;
+mod markdown_header_splitter;
 mod markdown_splitter;
 mod options;
 mod plain_text_splitter;
+mod recursive_character_splitter;
+mod semantic_splitter;
+mod sentence_splitter;
 mod text_splitter;
 mod token_splitter;
 
+pub use code_splitter::*;
 pub use error::*;
+pub use html_section_splitter::*;
+pub use markdown_header_splitter::*;
 pub use markdown_splitter::*;
 pub use options::*;
 pub use plain_text_splitter::*;
+pub use recursive_character_splitter::*;
+pub use semantic_splitter::*;
+pub use sentence_splitter::*;
 pub use text_splitter::*;
 pub use token_splitter::*;
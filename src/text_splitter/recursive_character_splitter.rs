@@ -0,0 +1,381 @@
+use std::collections::VecDeque;
+
+use async_trait::async_trait;
+use tiktoken_rs::{get_bpe_from_tokenizer, CoreBPE};
+
+use super::{SplitterOptions, TextSplitter, TextSplitterError};
+
+/// How the length of a chunk is measured.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LengthUnit {
+    /// Unicode characters.
+    Characters,
+    /// Tokens of the given tiktoken encoding, e.g. `cl100k_base`.
+    Tokens { encoding_name: String },
+}
+
+// Options is a struct that contains options for a recursive character text splitter.
+#[derive(Debug, Clone)]
+pub struct RecursiveCharacterSplitterOptions {
+    pub chunk_size: usize,
+    pub chunk_overlap: usize,
+    pub separators: Vec<String>,
+    pub keep_separator: bool,
+    pub length_unit: LengthUnit,
+    pub trim_chunks: bool,
+}
+
+impl Default for RecursiveCharacterSplitterOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RecursiveCharacterSplitterOptions {
+    pub fn new() -> Self {
+        RecursiveCharacterSplitterOptions {
+            chunk_size: 1000,
+            chunk_overlap: 200,
+            separators: vec![
+                "\n\n".to_string(),
+                "\n".to_string(),
+                " ".to_string(),
+                "".to_string(),
+            ],
+            keep_separator: true,
+            length_unit: LengthUnit::Characters,
+            trim_chunks: true,
+        }
+    }
+
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    pub fn with_chunk_overlap(mut self, chunk_overlap: usize) -> Self {
+        self.chunk_overlap = chunk_overlap;
+        self
+    }
+
+    /// Sets the separators to try, from the coarsest to the finest. An empty separator
+    /// splits between characters.
+    pub fn with_separators<S: Into<String>>(mut self, separators: Vec<S>) -> Self {
+        self.separators = separators.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Keeps the separators at the start of the chunk that follows them.
+    pub fn with_keep_separator(mut self, keep_separator: bool) -> Self {
+        self.keep_separator = keep_separator;
+        self
+    }
+
+    pub fn with_length_unit(mut self, length_unit: LengthUnit) -> Self {
+        self.length_unit = length_unit;
+        self
+    }
+
+    pub fn with_trim_chunks(mut self, trim_chunks: bool) -> Self {
+        self.trim_chunks = trim_chunks;
+        self
+    }
+}
+
+/// Splits text by trying each separator in order, recursing into pieces that are still
+/// too large with the next separator, then merging small pieces back into chunks of up to
+/// `chunk_size` with `chunk_overlap` between consecutive chunks.
+///
+/// This is the default splitter, used by `Loader::load_and_split_default`.
+pub struct RecursiveCharacterTextSplitter {
+    splitter_options: RecursiveCharacterSplitterOptions,
+}
+
+impl Default for RecursiveCharacterTextSplitter {
+    fn default() -> Self {
+        RecursiveCharacterTextSplitter::new(RecursiveCharacterSplitterOptions::default())
+    }
+}
+
+impl RecursiveCharacterTextSplitter {
+    pub fn new(options: RecursiveCharacterSplitterOptions) -> RecursiveCharacterTextSplitter {
+        RecursiveCharacterTextSplitter {
+            splitter_options: options,
+        }
+    }
+}
+
+pub(crate) enum LengthFunction {
+    Characters,
+    Tokens(CoreBPE),
+}
+
+impl LengthFunction {
+    pub(crate) fn try_from_unit(unit: &LengthUnit) -> Result<Self, TextSplitterError> {
+        match unit {
+            LengthUnit::Characters => Ok(LengthFunction::Characters),
+            LengthUnit::Tokens { encoding_name } => {
+                let tokenizer = SplitterOptions::get_tokenizer_from_str(encoding_name)
+                    .ok_or(TextSplitterError::TokenizerNotFound)?;
+                let bpe = get_bpe_from_tokenizer(tokenizer)
+                    .map_err(|_| TextSplitterError::InvalidTokenizer)?;
+                Ok(LengthFunction::Tokens(bpe))
+            }
+        }
+    }
+
+    pub(crate) fn len(&self, text: &str) -> usize {
+        match self {
+            LengthFunction::Characters => text.chars().count(),
+            LengthFunction::Tokens(bpe) => bpe.encode_ordinary(text).len(),
+        }
+    }
+}
+
+/// Splits `text` on `separator`, optionally keeping the separator at the start of each
+/// following piece. Empty pieces are dropped.
+pub(crate) fn split_with_separator(
+    text: &str,
+    separator: &str,
+    keep_separator: bool,
+) -> Vec<String> {
+    if separator.is_empty() {
+        return text.chars().map(String::from).collect();
+    }
+
+    let mut pieces = text.split(separator);
+    let mut splits = Vec::new();
+    if let Some(first) = pieces.next() {
+        splits.push(first.to_string());
+    }
+    for piece in pieces {
+        if keep_separator {
+            splits.push(format!("{separator}{piece}"));
+        } else {
+            splits.push(piece.to_string());
+        }
+    }
+    splits.into_iter().filter(|s| !s.is_empty()).collect()
+}
+
+/// Merges `splits` into chunks of at most `chunk_size`, carrying up to `chunk_overlap` of
+/// the previous chunk into the next one.
+pub(crate) fn merge_splits(
+    splits: &[String],
+    separator: &str,
+    chunk_size: usize,
+    chunk_overlap: usize,
+    trim_chunks: bool,
+    length: &LengthFunction,
+) -> Vec<String> {
+    let separator_len = length.len(separator);
+    let mut chunks = Vec::new();
+    let mut current: VecDeque<(&str, usize)> = VecDeque::new();
+    let mut total = 0;
+
+    let join = |current: &VecDeque<(&str, usize)>| -> Option<String> {
+        let chunk = current
+            .iter()
+            .map(|(split, _)| *split)
+            .collect::<Vec<_>>()
+            .join(separator);
+        let chunk = if trim_chunks {
+            chunk.trim().to_string()
+        } else {
+            chunk
+        };
+        (!chunk.is_empty()).then_some(chunk)
+    };
+
+    for split in splits {
+        let len = length.len(split);
+        let joined_len = |current: &VecDeque<(&str, usize)>, total: usize| {
+            total + len + if current.is_empty() { 0 } else { separator_len }
+        };
+
+        if joined_len(&current, total) > chunk_size && !current.is_empty() {
+            if total > chunk_size {
+                tracing::warn!(
+                    "Created a chunk of size {}, which is longer than the specified {}",
+                    total,
+                    chunk_size
+                );
+            }
+            if let Some(chunk) = join(&current) {
+                chunks.push(chunk);
+            }
+            while total > chunk_overlap || (joined_len(&current, total) > chunk_size && total > 0) {
+                let Some((_, first_len)) = current.pop_front() else {
+                    break;
+                };
+                total -= first_len + if current.is_empty() { 0 } else { separator_len };
+            }
+        }
+
+        total += len + if current.is_empty() { 0 } else { separator_len };
+        current.push_back((split.as_str(), len));
+    }
+
+    if let Some(chunk) = join(&current) {
+        chunks.push(chunk);
+    }
+
+    chunks
+}
+
+impl RecursiveCharacterTextSplitter {
+    fn split_recursive(
+        &self,
+        text: &str,
+        separators: &[String],
+        length: &LengthFunction,
+    ) -> Vec<String> {
+        let options = &self.splitter_options;
+
+        let mut separator = separators.last().map(String::as_str).unwrap_or_default();
+        let mut next_separators: &[String] = &[];
+        for (i, candidate) in separators.iter().enumerate() {
+            if candidate.is_empty() {
+                separator = candidate;
+                break;
+            }
+            if text.contains(candidate.as_str()) {
+                separator = candidate;
+                next_separators = &separators[i + 1..];
+                break;
+            }
+        }
+
+        let splits = split_with_separator(text, separator, options.keep_separator);
+        let merge_separator = if options.keep_separator {
+            ""
+        } else {
+            separator
+        };
+
+        let mut chunks = Vec::new();
+        let mut good_splits = Vec::new();
+        for split in splits {
+            if length.len(&split) < options.chunk_size {
+                good_splits.push(split);
+                continue;
+            }
+
+            if !good_splits.is_empty() {
+                chunks.extend(merge_splits(
+                    &good_splits,
+                    merge_separator,
+                    options.chunk_size,
+                    options.chunk_overlap,
+                    options.trim_chunks,
+                    length,
+                ));
+                good_splits.clear();
+            }
+            if next_separators.is_empty() {
+                chunks.push(split);
+            } else {
+                chunks.extend(self.split_recursive(&split, next_separators, length));
+            }
+        }
+
+        if !good_splits.is_empty() {
+            chunks.extend(merge_splits(
+                &good_splits,
+                merge_separator,
+                options.chunk_size,
+                options.chunk_overlap,
+                options.trim_chunks,
+                length,
+            ));
+        }
+
+        chunks
+    }
+}
+
+#[async_trait]
+impl TextSplitter for RecursiveCharacterTextSplitter {
+    async fn split_text(&self, text: &str) -> Result<Vec<String>, TextSplitterError> {
+        let options = &self.splitter_options;
+        if options.chunk_size == 0 || options.chunk_overlap > options.chunk_size {
+            return Err(TextSplitterError::InvalidSplitterOptions);
+        }
+
+        let length = LengthFunction::try_from_unit(&options.length_unit)?;
+        Ok(self.split_recursive(text, &options.separators, &length))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_recursive_character_splitter() {
+        let splitter = RecursiveCharacterTextSplitter::new(
+            RecursiveCharacterSplitterOptions::default()
+                .with_chunk_size(10)
+                .with_chunk_overlap(1),
+        );
+        let text = "Hi.\n\nI'm Harrison.\n\nHow? Are? You?\nOkay then f f f f.\nThis is a weird text to write, but gotta test the splittingggg some how.\n\nBye!\n\n-H.";
+        let chunks = splitter.split_text(text).await.unwrap();
+        assert_eq!(
+            chunks,
+            vec![
+                "Hi.",
+                "I'm",
+                "Harrison.",
+                "How? Are?",
+                "You?",
+                "Okay then",
+                "f f f f.",
+                "This is a",
+                "weird",
+                "text to",
+                "write,",
+                "but gotta",
+                "test the",
+                "splitting",
+                "gggg",
+                "some how.",
+                "Bye!",
+                "-H.",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recursive_character_splitter_overlap_and_tokens() {
+        let splitter = RecursiveCharacterTextSplitter::new(
+            RecursiveCharacterSplitterOptions::default()
+                .with_chunk_size(3)
+                .with_chunk_overlap(1)
+                .with_separators(vec![" "])
+                .with_keep_separator(false),
+        );
+        let chunks = splitter.split_text("a b c d e").await.unwrap();
+        assert_eq!(chunks, vec!["a b", "b c", "c d", "d e"]);
+
+        let splitter = RecursiveCharacterTextSplitter::new(
+            RecursiveCharacterSplitterOptions::default()
+                .with_chunk_size(4)
+                .with_chunk_overlap(0)
+                .with_length_unit(LengthUnit::Tokens {
+                    encoding_name: "cl100k_base".to_string(),
+                }),
+        );
+        let chunks = splitter
+            .split_text("one two three four five six seven eight")
+            .await
+            .unwrap();
+        assert_eq!(chunks, vec!["one two three four", "five six seven eight"]);
+
+        let splitter = RecursiveCharacterTextSplitter::new(
+            RecursiveCharacterSplitterOptions::default()
+                .with_chunk_size(2)
+                .with_chunk_overlap(3),
+        );
+        assert!(splitter.split_text("text").await.is_err());
+    }
+}
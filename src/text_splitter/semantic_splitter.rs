@@ -0,0 +1,226 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{embedding::Embedder, semantic_router::utils::cosine_similarity};
+
+use super::{TextSplitter, TextSplitterError};
+
+/// How the distance between consecutive sentences is turned into chunk breakpoints.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BreakpointThreshold {
+    /// Break where the distance is above the given percentile (0-100) of all distances.
+    Percentile(f64),
+    /// Break where the distance is more than the given number of standard deviations above the mean.
+    StandardDeviation(f64),
+    /// Break where the distance is above the mean plus the given multiple of the interquartile range.
+    Interquartile(f64),
+}
+
+impl BreakpointThreshold {
+    fn threshold(&self, distances: &[f64]) -> f64 {
+        match self {
+            BreakpointThreshold::Percentile(p) => percentile(distances, *p),
+            BreakpointThreshold::StandardDeviation(n) => {
+                let mean = mean(distances);
+                let variance = distances.iter().map(|d| (d - mean).powi(2)).sum::<f64>()
+                    / distances.len() as f64;
+                mean + n * variance.sqrt()
+            }
+            BreakpointThreshold::Interquartile(n) => {
+                let iqr = percentile(distances, 75.0) - percentile(distances, 25.0);
+                mean(distances) + n * iqr
+            }
+        }
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Percentile with linear interpolation between the closest ranks.
+fn percentile(values: &[f64], p: f64) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let rank = (p.clamp(0.0, 100.0) / 100.0) * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+// Options is a struct that contains options for a semantic splitter.
+#[derive(Debug, Clone)]
+pub struct SemanticSplitterOptions {
+    pub buffer_size: usize,
+    pub breakpoint_threshold: BreakpointThreshold,
+    pub min_chunk_sentences: usize,
+}
+
+impl Default for SemanticSplitterOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SemanticSplitterOptions {
+    pub fn new() -> Self {
+        SemanticSplitterOptions {
+            buffer_size: 1,
+            breakpoint_threshold: BreakpointThreshold::Percentile(95.0),
+            min_chunk_sentences: 1,
+        }
+    }
+
+    /// Sets how many neighbouring sentences on each side are embedded together with a
+    /// sentence, which smooths out the distances.
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+
+    pub fn with_breakpoint_threshold(mut self, breakpoint_threshold: BreakpointThreshold) -> Self {
+        self.breakpoint_threshold = breakpoint_threshold;
+        self
+    }
+
+    /// Sets the minimum number of sentences of a chunk; breakpoints closer than this are ignored.
+    pub fn with_min_chunk_sentences(mut self, min_chunk_sentences: usize) -> Self {
+        self.min_chunk_sentences = min_chunk_sentences.max(1);
+        self
+    }
+}
+
+/// Splits text into sentences, embeds each one (with its neighbours) and starts a new chunk
+/// wherever the cosine distance between consecutive sentences jumps above the threshold.
+///
+/// # Usage
+/// ```rust,ignore
+/// let splitter = SemanticSplitter::new(OpenAiEmbedder::default()).with_options(
+///     SemanticSplitterOptions::default()
+///         .with_breakpoint_threshold(BreakpointThreshold::StandardDeviation(3.0)),
+/// );
+/// let chunks = splitter.split_text(&text).await?;
+/// ```
+pub struct SemanticSplitter {
+    embedder: Arc<dyn Embedder>,
+    splitter_options: SemanticSplitterOptions,
+}
+
+impl SemanticSplitter {
+    pub fn new<E: Embedder + 'static>(embedder: E) -> Self {
+        SemanticSplitter {
+            embedder: Arc::new(embedder),
+            splitter_options: SemanticSplitterOptions::default(),
+        }
+    }
+
+    pub fn with_options(mut self, options: SemanticSplitterOptions) -> Self {
+        self.splitter_options = options;
+        self
+    }
+}
+
+#[async_trait]
+impl TextSplitter for SemanticSplitter {
+    async fn split_text(&self, text: &str) -> Result<Vec<String>, TextSplitterError> {
+        let sentences: Vec<&str> = text
+            .unicode_sentences()
+            .filter(|s| !s.trim().is_empty())
+            .collect();
+        if sentences.len() <= 1 {
+            return Ok(sentences.iter().map(|s| s.trim().to_string()).collect());
+        }
+
+        let buffer = self.splitter_options.buffer_size;
+        let combined: Vec<String> = (0..sentences.len())
+            .map(|i| {
+                let start = i.saturating_sub(buffer);
+                let end = (i + buffer + 1).min(sentences.len());
+                sentences[start..end].concat()
+            })
+            .collect();
+        let embeddings = self.embedder.embed_documents(&combined).await?;
+
+        let distances: Vec<f64> = embeddings
+            .windows(2)
+            .map(|pair| 1.0 - cosine_similarity(&pair[0], &pair[1]))
+            .collect();
+        let threshold = self
+            .splitter_options
+            .breakpoint_threshold
+            .threshold(&distances);
+
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        for (i, distance) in distances.iter().enumerate() {
+            let end = i + 1;
+            if *distance > threshold && end - start >= self.splitter_options.min_chunk_sentences {
+                chunks.push(sentences[start..end].concat().trim().to_string());
+                start = end;
+            }
+        }
+        chunks.push(sentences[start..].concat().trim().to_string());
+
+        Ok(chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::embedding::EmbedderError;
+
+    use super::*;
+
+    struct TopicEmbedder;
+
+    #[async_trait]
+    impl Embedder for TopicEmbedder {
+        async fn embed_documents(
+            &self,
+            documents: &[String],
+        ) -> Result<Vec<Vec<f64>>, EmbedderError> {
+            let mut embeddings = Vec::new();
+            for document in documents {
+                embeddings.push(self.embed_query(document).await?);
+            }
+            Ok(embeddings)
+        }
+
+        async fn embed_query(&self, text: &str) -> Result<Vec<f64>, EmbedderError> {
+            let cats = text.matches("cat").count() as f64;
+            let cars = text.matches("car").count() as f64;
+            Ok(vec![cats + 0.1, cars + 0.1])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_semantic_splitter() {
+        let text = "My cat sleeps all day. The cat likes fish. A cat purrs. \
+                    The car needs fuel. My car is red. That car is fast.";
+
+        let splitter = SemanticSplitter::new(TopicEmbedder).with_options(
+            SemanticSplitterOptions::default()
+                .with_buffer_size(0)
+                .with_breakpoint_threshold(BreakpointThreshold::Percentile(50.0)),
+        );
+        let chunks = splitter.split_text(text).await.unwrap();
+
+        assert_eq!(
+            chunks,
+            vec![
+                "My cat sleeps all day. The cat likes fish. A cat purrs.",
+                "The car needs fuel. My car is red. That car is fast."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_breakpoint_thresholds() {
+        let distances = [0.1, 0.2, 0.3, 0.4, 1.0];
+        assert!((BreakpointThreshold::Percentile(50.0).threshold(&distances) - 0.3).abs() < 1e-9);
+        assert!((BreakpointThreshold::Percentile(90.0).threshold(&distances) - 0.76).abs() < 1e-9);
+        assert!(BreakpointThreshold::StandardDeviation(1.0).threshold(&distances) < 1.0);
+        assert!(BreakpointThreshold::Interquartile(1.5).threshold(&distances) > 0.4);
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::VecDeque;
+
+use async_trait::async_trait;
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{LengthFunction, LengthUnit, TextSplitter, TextSplitterError};
+
+/// How the size of a sentence chunk is measured.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SentenceChunkUnit {
+    /// Number of sentences.
+    Sentences,
+    /// Length of the sentences, in characters or tokens.
+    Length(LengthUnit),
+}
+
+// Options is a struct that contains options for a sentence splitter.
+#[derive(Debug, Clone)]
+pub struct SentenceSplitterOptions {
+    pub chunk_size: usize,
+    pub chunk_overlap: usize,
+    pub chunk_unit: SentenceChunkUnit,
+}
+
+impl Default for SentenceSplitterOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SentenceSplitterOptions {
+    pub fn new() -> Self {
+        SentenceSplitterOptions {
+            chunk_size: 5,
+            chunk_overlap: 1,
+            chunk_unit: SentenceChunkUnit::Sentences,
+        }
+    }
+
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    pub fn with_chunk_overlap(mut self, chunk_overlap: usize) -> Self {
+        self.chunk_overlap = chunk_overlap;
+        self
+    }
+
+    pub fn with_chunk_unit(mut self, chunk_unit: SentenceChunkUnit) -> Self {
+        self.chunk_unit = chunk_unit;
+        self
+    }
+}
+
+/// Splits text into chunks of whole sentences, found with Unicode sentence segmentation,
+/// so a chunk never ends mid-sentence. This suits languages where character based
+/// splitting mangles text.
+///
+/// Chunks hold up to `chunk_size` sentences, or up to `chunk_size` characters or tokens,
+/// and repeat the trailing sentences of the previous chunk up to `chunk_overlap` of the
+/// same unit. A single sentence longer than `chunk_size` is kept whole in its own chunk.
+///
+/// # Usage
+/// ```rust,ignore
+/// let splitter = SentenceSplitter::new(
+///     SentenceSplitterOptions::default()
+///         .with_chunk_unit(SentenceChunkUnit::Length(LengthUnit::Tokens {
+///             encoding_name: "cl100k_base".to_string(),
+///         }))
+///         .with_chunk_size(256)
+///         .with_chunk_overlap(32),
+/// );
+/// let chunks = splitter.split_text(&text).await?;
+/// ```
+pub struct SentenceSplitter {
+    splitter_options: SentenceSplitterOptions,
+}
+
+impl Default for SentenceSplitter {
+    fn default() -> Self {
+        SentenceSplitter::new(SentenceSplitterOptions::default())
+    }
+}
+
+impl SentenceSplitter {
+    pub fn new(options: SentenceSplitterOptions) -> SentenceSplitter {
+        SentenceSplitter {
+            splitter_options: options,
+        }
+    }
+}
+
+#[async_trait]
+impl TextSplitter for SentenceSplitter {
+    async fn split_text(&self, text: &str) -> Result<Vec<String>, TextSplitterError> {
+        let options = &self.splitter_options;
+        if options.chunk_size == 0 || options.chunk_overlap >= options.chunk_size {
+            return Err(TextSplitterError::InvalidSplitterOptions);
+        }
+
+        let length_function = match &options.chunk_unit {
+            SentenceChunkUnit::Sentences => None,
+            SentenceChunkUnit::Length(unit) => Some(LengthFunction::try_from_unit(unit)?),
+        };
+        let sentences = text
+            .unicode_sentences()
+            .filter(|sentence| !sentence.trim().is_empty())
+            .map(|sentence| {
+                let len = length_function
+                    .as_ref()
+                    .map_or(1, |length_function| length_function.len(sentence.trim()));
+                (sentence, len)
+            });
+
+        let mut chunks = Vec::new();
+        let mut current: VecDeque<(&str, usize)> = VecDeque::new();
+        let mut total = 0;
+        for (sentence, len) in sentences {
+            if total + len > options.chunk_size && !current.is_empty() {
+                chunks.push(join_sentences(&current));
+                while total > options.chunk_overlap
+                    || (total > 0 && total + len > options.chunk_size)
+                {
+                    if let Some((_, removed)) = current.pop_front() {
+                        total -= removed;
+                    }
+                }
+            }
+            current.push_back((sentence, len));
+            total += len;
+        }
+        if !current.is_empty() {
+            chunks.push(join_sentences(&current));
+        }
+
+        Ok(chunks)
+    }
+}
+
+fn join_sentences(sentences: &VecDeque<(&str, usize)>) -> String {
+    sentences
+        .iter()
+        .map(|(sentence, _)| *sentence)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sentence_splitter_sentence_count() {
+        let splitter = SentenceSplitter::new(
+            SentenceSplitterOptions::default()
+                .with_chunk_size(2)
+                .with_chunk_overlap(1),
+        );
+        let chunks = splitter
+            .split_text("Anna went home. It was late! Was it raining? Yes.")
+            .await
+            .unwrap();
+        assert_eq!(
+            chunks,
+            vec![
+                "Anna went home. It was late!",
+                "It was late! Was it raining?",
+                "Was it raining? Yes."
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sentence_splitter_length() {
+        let splitter = SentenceSplitter::new(
+            SentenceSplitterOptions::default()
+                .with_chunk_unit(SentenceChunkUnit::Length(LengthUnit::Characters))
+                .with_chunk_size(12)
+                .with_chunk_overlap(0),
+        );
+        let chunks = splitter
+            .split_text("Guten Tag. Wie geht's? Ein sehr langer Satz ohne Ende.")
+            .await
+            .unwrap();
+        assert_eq!(
+            chunks,
+            vec![
+                "Guten Tag.",
+                "Wie geht's?",
+                "Ein sehr langer Satz ohne Ende."
+            ]
+        );
+
+        let invalid = SentenceSplitter::new(
+            SentenceSplitterOptions::default()
+                .with_chunk_size(2)
+                .with_chunk_overlap(2),
+        );
+        assert!(invalid.split_text("One. Two.").await.is_err());
+    }
+}
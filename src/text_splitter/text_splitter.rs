@@ -2,11 +2,21 @@ use std::collections::HashMap;
 
 use async_trait::async_trait;
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 
 use crate::schemas::Document;
 
 use super::TextSplitterError;
 
+/// Splits text into chunks.
+///
+/// The documents produced by `create_documents` and `split_documents` keep the metadata of
+/// the text they come from and record where they come from:
+/// - `chunk_index` and `total_chunks`: the position of the chunk among the chunks of its text.
+/// - `start_offset` and `end_offset`: the byte range of the chunk in its text, when the
+///   chunk appears verbatim in it.
+/// - `parent_id`: the `id` of the source document, or else its `id` metadata, or else the
+///   SHA-256 of its text.
 #[async_trait]
 pub trait TextSplitter: Send + Sync {
     async fn split_text(&self, text: &str) -> Result<Vec<String>, TextSplitterError>;
@@ -15,14 +25,25 @@ pub trait TextSplitter: Send + Sync {
         &self,
         documents: &[Document],
     ) -> Result<Vec<Document>, TextSplitterError> {
-        let mut texts: Vec<String> = Vec::new();
-        let mut metadatas: Vec<HashMap<String, Value>> = Vec::new();
-        documents.iter().for_each(|d| {
-            texts.push(d.page_content.clone());
-            metadatas.push(d.metadata.clone());
-        });
-
-        self.create_documents(&texts, &metadatas).await
+        let mut chunks: Vec<Document> = Vec::new();
+        for document in documents {
+            let mut document_chunks = self
+                .create_documents(
+                    std::slice::from_ref(&document.page_content),
+                    std::slice::from_ref(&document.metadata),
+                )
+                .await?;
+            if let Some(id) = &document.id {
+                for chunk in document_chunks.iter_mut() {
+                    chunk
+                        .metadata
+                        .insert("parent_id".to_string(), Value::from(id.as_str()));
+                }
+            }
+            chunks.extend(document_chunks);
+        }
+
+        Ok(chunks)
     }
 
     async fn create_documents(
@@ -42,12 +63,100 @@ pub trait TextSplitter: Send + Sync {
         let mut documents: Vec<Document> = Vec::new();
         for i in 0..text.len() {
             let chunks = self.split_text(&text[i]).await?;
-            for chunk in chunks {
-                let document = Document::new(chunk).with_metadata(metadatas[i].clone());
-                documents.push(document);
-            }
+            let mut chunk_documents: Vec<Document> = chunks
+                .into_iter()
+                .map(|chunk| Document::new(chunk).with_metadata(metadatas[i].clone()))
+                .collect();
+            add_chunk_provenance(&mut chunk_documents, &text[i], &metadatas[i]);
+            documents.extend(chunk_documents);
         }
 
         Ok(documents)
     }
 }
+
+/// Adds the provenance metadata described on `TextSplitter` to the chunks split from `text`.
+pub(crate) fn add_chunk_provenance(
+    chunks: &mut [Document],
+    text: &str,
+    parent_metadata: &HashMap<String, Value>,
+) {
+    let parent_id = parent_metadata
+        .get("id")
+        .cloned()
+        .unwrap_or_else(|| Value::from(format!("{:x}", Sha256::digest(text.as_bytes()))));
+    let total_chunks = chunks.len();
+
+    // Chunks come in order but may overlap, so each search starts just after the
+    // previous chunk's start.
+    let mut search_from = 0;
+    for (index, chunk) in chunks.iter_mut().enumerate() {
+        let metadata = &mut chunk.metadata;
+        metadata.insert("chunk_index".to_string(), Value::from(index));
+        metadata.insert("total_chunks".to_string(), Value::from(total_chunks));
+        metadata.insert("parent_id".to_string(), parent_id.clone());
+
+        let content = chunk.page_content.as_str();
+        let start = text[search_from..]
+            .find(content)
+            .map(|start| start + search_from)
+            .or_else(|| text.find(content));
+        if let Some(start) = start {
+            metadata.insert("start_offset".to_string(), Value::from(start));
+            metadata.insert("end_offset".to_string(), Value::from(start + content.len()));
+            search_from = start + text[start..].chars().next().map_or(0, char::len_utf8);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text_splitter::{RecursiveCharacterSplitterOptions, RecursiveCharacterTextSplitter};
+
+    #[tokio::test]
+    async fn test_chunk_provenance() {
+        let splitter = RecursiveCharacterTextSplitter::new(
+            RecursiveCharacterSplitterOptions::default()
+                .with_chunk_size(10)
+                .with_chunk_overlap(4),
+        );
+        let text = "café one two three four";
+        let documents = splitter
+            .split_documents(&[Document::new(text)
+                .with_metadata(HashMap::from([("id".to_string(), Value::from("doc-1"))]))])
+            .await
+            .unwrap();
+
+        assert!(documents.len() > 1);
+        for (index, document) in documents.iter().enumerate() {
+            let metadata = &document.metadata;
+            assert_eq!(metadata["chunk_index"], Value::from(index));
+            assert_eq!(metadata["total_chunks"], Value::from(documents.len()));
+            assert_eq!(metadata["parent_id"], Value::from("doc-1"));
+
+            let start = metadata["start_offset"].as_u64().unwrap() as usize;
+            let end = metadata["end_offset"].as_u64().unwrap() as usize;
+            assert_eq!(&text[start..end], document.page_content);
+        }
+
+        let documents = splitter
+            .split_documents(&[Document::new(text)
+                .with_id("doc-2")
+                .with_metadata(HashMap::from([("id".to_string(), Value::from("doc-1"))]))])
+            .await
+            .unwrap();
+        assert!(documents
+            .iter()
+            .all(|document| document.id.is_none() && document.metadata["parent_id"] == "doc-2"));
+
+        let documents = splitter
+            .create_documents(&[text.to_string()], &[])
+            .await
+            .unwrap();
+        assert_eq!(
+            documents[0].metadata["parent_id"],
+            Value::from(format!("{:x}", Sha256::digest(text.as_bytes())))
+        );
+    }
+}
@@ -1,9 +1,8 @@
-use std::error::Error;
-
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
+use crate::error::LangChainError;
 use crate::tools::Tool;
 
 pub struct CommandExecutor {
@@ -100,7 +99,7 @@ impl Tool for CommandExecutor {
     }
 
     async fn parse_input(&self, input: &str) -> Value {
-        log::info!("Parsing input: {}", input);
+        tracing::info!("Parsing input: {}", input);
 
         // Attempt to parse input string into CommandsWrapper struct first
         let wrapper_result = serde_json::from_str::<CommandsWrapper>(input);
@@ -109,7 +108,7 @@ impl Tool for CommandExecutor {
             // If successful, serialize the `commands` back into a serde_json::Value
             // this is for llm like open ai tools
             serde_json::to_value(wrapper.commands).unwrap_or_else(|err| {
-                log::error!("Serialization error: {}", err);
+                tracing::error!("Serialization error: {}", err);
                 Value::Null
             })
         } else {
@@ -119,7 +118,7 @@ impl Tool for CommandExecutor {
 
             commands_result.map_or_else(
                 |err| {
-                    log::error!("Failed to parse input: {}", err);
+                    tracing::error!("Failed to parse input: {}", err);
                     Value::Null
                 },
                 |commands| serde_json::to_value(commands).unwrap_or(Value::Null),
@@ -127,7 +126,7 @@ impl Tool for CommandExecutor {
         }
     }
 
-    async fn run(&self, input: Value) -> Result<String, Box<dyn Error>> {
+    async fn run(&self, input: Value) -> Result<String, LangChainError> {
         let commands: Vec<CommandInput> = serde_json::from_value(input)?;
         let mut result = String::new();
 
@@ -144,12 +143,9 @@ impl Tool for CommandExecutor {
             ));
 
             if !output.status.success() {
-                return Err(Box::new(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    format!(
-                        "Command {} failed with status: {}",
-                        command.cmd, output.status
-                    ),
+                return Err(LangChainError::ToolError(format!(
+                    "Command {} failed with status: {}",
+                    command.cmd, output.status
                 )));
             }
         }
@@ -1,7 +1,8 @@
-use std::error::Error;
+use crate::error::LangChainError;
+use crate::platform::send;
+use crate::tools::Tool;
 use async_trait::async_trait;
 use serde_json::{json, Value};
-use crate::tools::Tool;
 
 pub struct DataForSeo {
     access_token: String,
@@ -35,9 +36,14 @@ impl DataForSeo {
         self
     }
 
-    pub async fn simple_search(&self, query: &str) -> Result<String, Box<dyn Error>> {
+    #[tracing::instrument(
+        name = "dataforseo_search",
+        skip(self),
+        fields(tool = "GoogleSearch", request_id)
+    )]
+    pub async fn simple_search(&self, query: &str) -> Result<String, LangChainError> {
         let client = reqwest::Client::new();
-        
+
         let body = json!([{
             "language_code": self.language_code.as_deref().unwrap_or("en"),
             "location_name": self.location.as_deref().unwrap_or("United States"),
@@ -46,64 +52,74 @@ impl DataForSeo {
             "keyword": query,
             "depth": self.depth.unwrap_or(30)
         }]);
-    
-        println!("🔍 Request body: {}", serde_json::to_string_pretty(&body)?);
-    
-        let response = client
-            .post("https://api.dataforseo.com/v3/serp/google/organic/live/regular")
-            .header("Authorization", format!("Basic {}", self.access_token))
-            .json(&body)
-            .send()
-            .await?;
-    
-        println!("📡 Response status: {}", response.status());
-        
-        let results: Value = response.json().await?;
-        println!("📊 Raw API response: {}", serde_json::to_string_pretty(&results)?);
-        
+
+        tracing::trace!(body = %body, "Sending search request");
+
+        let results: Value = send(async {
+            let response = client
+                .post("https://api.dataforseo.com/v3/serp/google/organic/live/regular")
+                .header("Authorization", format!("Basic {}", self.access_token))
+                .json(&body)
+                .send()
+                .await?;
+
+            tracing::debug!(status = %response.status(), "Received search response");
+            response.json().await
+        })
+        .await?;
+
+        if let Some(request_id) = results["tasks"][0]["id"].as_str() {
+            tracing::Span::current().record("request_id", request_id);
+        }
+        tracing::trace!(response = %results, "Raw API response");
+
         process_dataforseo_response(&results)
     }
 }
 
-fn process_dataforseo_response(res: &Value) -> Result<String, Box<dyn Error>> {
-    println!("Processing response...");
-    
+fn process_dataforseo_response(res: &Value) -> Result<String, LangChainError> {
     // Check for API status
     if let Some(status_code) = res["status_code"].as_u64() {
-        println!("API status code: {}", status_code);
+        tracing::debug!(status_code, "API status code");
         if status_code != 20000 {
-            return Err(format!("API error: {}", res["status_message"].as_str().unwrap_or("Unknown error")).into());
+            return Err(format!(
+                "API error: {}",
+                res["status_message"].as_str().unwrap_or("Unknown error")
+            )
+            .into());
         }
     }
 
     if let Some(tasks) = res["tasks"].as_array() {
-        println!("Found {} tasks", tasks.len());
-        
+        tracing::debug!(tasks = tasks.len(), "Found tasks");
+
         if let Some(first_task) = tasks.first() {
-            println!("Task status: {}", first_task["status_code"].as_u64().unwrap_or(0));
-            
+            tracing::debug!(
+                status_code = first_task["status_code"].as_u64().unwrap_or(0),
+                "Task status"
+            );
+
             if let Some(results) = first_task["result"].as_array() {
-                println!("Found {} results", results.len());
-                
+                tracing::debug!(results = results.len(), "Found results");
+
                 if let Some(first_result) = results.first() {
                     if let Some(items) = first_result["items"].as_array() {
-                        println!("Found {} items", items.len());
-                        
+                        tracing::debug!(items = items.len(), "Found items");
+
                         // Collect all organic results
                         let mut organic_results = Vec::new();
                         for item in items {
-                            if let (Some(title), Some(link)) = (
-                                item["title"].as_str(),
-                                item["url"].as_str()
-                            ) {
-                                let snippet = item["description"].as_str().unwrap_or(""); 
+                            if let (Some(title), Some(link)) =
+                                (item["title"].as_str(), item["url"].as_str())
+                            {
+                                let snippet = item["description"].as_str().unwrap_or("");
                                 organic_results.push(format!(
                                     "Title: {}\nSnippet: {}\nLink: {}\n",
                                     title, snippet, link
                                 ));
                             }
                         }
-                        
+
                         if !organic_results.is_empty() {
                             return Ok(organic_results.join("\n"));
                         }
@@ -112,7 +128,7 @@ fn process_dataforseo_response(res: &Value) -> Result<String, Box<dyn Error>> {
             }
         }
     }
-    
+
     Err("No valid results found in the response structure".into())
 }
 
@@ -131,22 +147,23 @@ impl Tool for DataForSeo {
         )
     }
 
-    async fn run(&self, input: Value) -> Result<String, Box<dyn Error>> {
+    async fn run(&self, input: Value) -> Result<String, LangChainError> {
         let input = match input {
             Value::String(s) => s,
             Value::Object(map) => {
                 // Handle case where input is a JSON object with "input" field
                 if let Some(input_value) = map.get("input") {
-                    input_value.as_str()
+                    input_value
+                        .as_str()
                         .ok_or("Input field should be a string")?
                         .to_string()
                 } else {
                     return Err("Missing 'input' field in request".into());
                 }
-            },
-            _ => return Err("Input should be a string or object with 'input' field".into())
+            }
+            _ => return Err("Input should be a string or object with 'input' field".into()),
         };
-        
+
         self.simple_search(&input).await
     }
 }
@@ -176,4 +193,4 @@ mod tests {
             .unwrap();
         println!("{}", s);
     }
-}
\ No newline at end of file
+}
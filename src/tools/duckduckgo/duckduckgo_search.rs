@@ -1,4 +1,4 @@
-use std::{collections::HashMap, error::Error};
+use std::collections::HashMap;
 
 use async_trait::async_trait;
 use reqwest::Client;
@@ -7,6 +7,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use url::Url;
 
+use crate::error::LangChainError;
+use crate::platform::send;
 use crate::tools::Tool;
 
 pub struct DuckDuckGoSearchResults {
@@ -29,7 +31,7 @@ impl DuckDuckGoSearchResults {
         self
     }
 
-    pub async fn search(&self, query: &str) -> Result<String, Box<dyn Error>> {
+    pub async fn search(&self, query: &str) -> Result<String, LangChainError> {
         let mut url = Url::parse(&self.url)?;
 
         let mut query_params = HashMap::new();
@@ -37,8 +39,7 @@ impl DuckDuckGoSearchResults {
 
         url.query_pairs_mut().extend_pairs(query_params.iter());
 
-        let response = self.client.get(url).send().await?;
-        let body = response.text().await?;
+        let body = send(async { self.client.get(url).send().await?.text().await }).await?;
         let document = Html::parse_document(&body);
 
         let result_selector = Selector::parse(".web-result").unwrap();
@@ -108,7 +109,7 @@ impl Tool for DuckDuckGoSearchResults {
         )
     }
 
-    async fn run(&self, input: Value) -> Result<String, Box<dyn Error>> {
+    async fn run(&self, input: Value) -> Result<String, LangChainError> {
         let input = input.as_str().ok_or("Input should be a string")?;
         self.search(input).await
     }
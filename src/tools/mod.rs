@@ -1,22 +1,32 @@
 mod tool;
 pub use tool::*;
 
-pub use wolfram::*;
+#[cfg(feature = "wolfram")]
 mod wolfram;
+#[cfg(feature = "wolfram")]
+pub use wolfram::*;
 
+#[cfg(feature = "web-scraper")]
 mod scraper;
+#[cfg(feature = "web-scraper")]
 pub use scraper::*;
 
 mod sql;
 pub use sql::*;
 
+#[cfg(feature = "duckduckgo")]
 mod duckduckgo;
+#[cfg(feature = "duckduckgo")]
 pub use duckduckgo::*;
 
+#[cfg(feature = "serpapi")]
 mod serpapi;
+#[cfg(feature = "serpapi")]
 pub use serpapi::*;
 
+#[cfg(feature = "dataforseo")]
 mod dataforseo;
+#[cfg(feature = "dataforseo")]
 pub use dataforseo::*;
 
 mod command_executor;
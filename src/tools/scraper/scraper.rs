@@ -2,8 +2,10 @@ use async_trait::async_trait;
 use regex::Regex;
 use scraper::{ElementRef, Html, Selector};
 use serde_json::Value;
-use std::{error::Error, sync::Arc};
+use std::sync::Arc;
 
+use crate::error::LangChainError;
+use crate::platform::send;
 use crate::tools::Tool;
 
 pub struct WebScrapper {}
@@ -25,7 +27,7 @@ impl Tool for WebScrapper {
 		Input should be a working url.",
         )
     }
-    async fn run(&self, input: Value) -> Result<String, Box<dyn Error>> {
+    async fn run(&self, input: Value) -> Result<String, LangChainError> {
         let input = input.as_str().ok_or("Invalid input")?;
         match scrape_url(input).await {
             Ok(content) => Ok(content),
@@ -40,8 +42,8 @@ impl Into<Arc<dyn Tool>> for WebScrapper {
     }
 }
 
-async fn scrape_url(url: &str) -> Result<String, Box<dyn Error>> {
-    let res = reqwest::get(url).await?.text().await?;
+async fn scrape_url(url: &str) -> Result<String, LangChainError> {
+    let res = send(async { reqwest::get(url).await?.text().await }).await?;
 
     let document = Html::parse_document(&res);
     let body_selector = Selector::parse("body").unwrap();
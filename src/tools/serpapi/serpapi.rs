@@ -1,8 +1,8 @@
-use std::error::Error;
-
 use async_trait::async_trait;
 use serde_json::Value;
 
+use crate::error::LangChainError;
+use crate::platform::send;
 use crate::tools::Tool;
 
 pub struct SerpApi {
@@ -45,7 +45,7 @@ impl SerpApi {
         self
     }
 
-    pub async fn simple_search(&self, query: &str) -> Result<String, Box<dyn Error>> {
+    pub async fn simple_search(&self, query: &str) -> Result<String, LangChainError> {
         let mut url = format!(
             "https://serpapi.com/search.json?q={}&api_key={}",
             query, self.api_key
@@ -62,7 +62,7 @@ impl SerpApi {
         if let Some(google_domain) = &self.google_domain {
             url.push_str(&format!("&google_domain={}", google_domain));
         }
-        let results: Value = reqwest::get(&url).await?.json().await?;
+        let results: Value = send(async { reqwest::get(&url).await?.json().await }).await?;
 
         let res = process_response(&results)?;
 
@@ -95,7 +95,7 @@ fn get_answer_box(result: &Value) -> String {
     "".to_string()
 }
 
-fn process_response(res: &Value) -> Result<String, Box<dyn Error>> {
+fn process_response(res: &Value) -> Result<String, LangChainError> {
     if !get_answer_box(res).is_empty() {
         return Ok(get_answer_box(res));
     }
@@ -161,7 +161,7 @@ impl Tool for SerpApi {
         )
     }
 
-    async fn run(&self, input: Value) -> Result<String, Box<dyn Error>> {
+    async fn run(&self, input: Value) -> Result<String, LangChainError> {
         let input = input.as_str().ok_or("Input should be a string")?;
         self.simple_search(input).await
     }
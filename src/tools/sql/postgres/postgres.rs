@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use sqlx::{postgres::PgPoolOptions, Column, Pool, Postgres, Row, TypeInfo};
-use std::error::Error;
 
+use crate::error::LangChainError;
 use crate::tools::{Dialect, Engine};
 
 pub struct PostgreSQLEngine {
@@ -9,7 +9,7 @@ pub struct PostgreSQLEngine {
 }
 
 impl PostgreSQLEngine {
-    pub async fn new(dsn: &str) -> Result<Self, Box<dyn Error>> {
+    pub async fn new(dsn: &str) -> Result<Self, LangChainError> {
         let pool = PgPoolOptions::new().max_connections(5).connect(dsn).await?;
 
         Ok(PostgreSQLEngine { pool })
@@ -28,7 +28,7 @@ impl Engine for PostgreSQLEngine {
         Dialect::PostgreSQL
     }
 
-    async fn query(&self, query: &str) -> Result<(Vec<String>, Vec<Vec<String>>), Box<dyn Error>> {
+    async fn query(&self, query: &str) -> Result<(Vec<String>, Vec<Vec<String>>), LangChainError> {
         let rows = sqlx::query(query).fetch_all(&self.pool).await?;
 
         let mut cols = vec![];
@@ -75,7 +75,7 @@ impl Engine for PostgreSQLEngine {
         Ok((cols, results))
     }
 
-    async fn table_names(&self) -> Result<Vec<String>, Box<dyn Error>> {
+    async fn table_names(&self) -> Result<Vec<String>, LangChainError> {
         let query =
             "SELECT table_name FROM information_schema.tables WHERE table_schema = 'public'";
         let rows = sqlx::query(query).fetch_all(&self.pool).await?;
@@ -88,7 +88,7 @@ impl Engine for PostgreSQLEngine {
         Ok(table_names)
     }
 
-    async fn table_info(&self, table: &str) -> Result<String, Box<dyn Error>> {
+    async fn table_info(&self, table: &str) -> Result<String, LangChainError> {
         let query = format!(
             "SELECT column_name, data_type FROM information_schema.columns WHERE table_name = $1"
         );
@@ -113,7 +113,7 @@ impl Engine for PostgreSQLEngine {
         Ok(format!("CREATE TABLE {} ({})", table, info))
     }
 
-    fn close(&self) -> Result<(), Box<dyn Error>> {
+    fn close(&self) -> Result<(), LangChainError> {
         // sqlx Pool is automatically closed when it goes out of scope
         Ok(())
     }
@@ -1,8 +1,10 @@
-use std::{collections::HashSet, error::Error};
+use std::collections::HashSet;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
+use crate::error::LangChainError;
+
 #[derive(Serialize, Deserialize)]
 pub enum Dialect {
     #[serde(rename = "mysql")]
@@ -27,14 +29,14 @@ pub trait Engine: Send + Sync {
     // Dialect returns the dialect(e.g. mysql, sqlite, postgre) of the database.
     fn dialect(&self) -> Dialect;
     // Query executes the query and returns the columns and results.
-    async fn query(&self, query: &str) -> Result<(Vec<String>, Vec<Vec<String>>), Box<dyn Error>>;
+    async fn query(&self, query: &str) -> Result<(Vec<String>, Vec<Vec<String>>), LangChainError>;
     // TableNames returns all the table names of the database.
-    async fn table_names(&self) -> Result<Vec<String>, Box<dyn Error>>;
+    async fn table_names(&self) -> Result<Vec<String>, LangChainError>;
     // TableInfo returns the table information of the database.
     // Typically, it returns the CREATE TABLE statement.
-    async fn table_info(&self, tables: &str) -> Result<String, Box<dyn Error>>;
+    async fn table_info(&self, tables: &str) -> Result<String, LangChainError>;
     // Close closes the database.
-    fn close(&self) -> Result<(), Box<dyn Error>>;
+    fn close(&self) -> Result<(), LangChainError>;
 }
 
 pub struct SQLDatabase {
@@ -74,7 +76,7 @@ impl SQLDatabaseBuilder {
     }
 
     // Function to build the SQLDatabase instance
-    pub async fn build(self) -> Result<SQLDatabase, Box<dyn Error>> {
+    pub async fn build(self) -> Result<SQLDatabase, LangChainError> {
         let table_names_result = self.engine.table_names().await;
 
         // Handle potential error from table_names call
@@ -108,7 +110,7 @@ impl SQLDatabase {
         self.all_tables.iter().cloned().collect()
     }
 
-    pub async fn table_info(&self, tables: &[String]) -> Result<String, Box<dyn Error>> {
+    pub async fn table_info(&self, tables: &[String]) -> Result<String, LangChainError> {
         let mut tables: HashSet<String> = tables.to_vec().into_iter().collect();
         if tables.is_empty() {
             tables = self.all_tables.clone();
@@ -129,8 +131,8 @@ impl SQLDatabase {
         Ok(info)
     }
 
-    pub async fn query(&self, query: &str) -> Result<String, Box<dyn Error>> {
-        log::debug!("Query: {}", query);
+    pub async fn query(&self, query: &str) -> Result<String, LangChainError> {
+        tracing::debug!("Query: {}", query);
         let (cols, results) = self.engine.query(query).await?;
         let mut str = cols.join("\t") + "\n";
         for row in results {
@@ -140,13 +142,13 @@ impl SQLDatabase {
         Ok(str)
     }
 
-    pub fn close(&self) -> Result<(), Box<dyn Error>> {
+    pub fn close(&self) -> Result<(), LangChainError> {
         self.engine.close()
     }
 
-    pub async fn sample_rows(&self, table: &str) -> Result<String, Box<dyn Error>> {
+    pub async fn sample_rows(&self, table: &str) -> Result<String, LangChainError> {
         let query = format!("SELECT * FROM {} LIMIT {}", table, self.sample_rows_number);
-        log::debug!("Sample Rows Query: {}", query);
+        tracing::debug!("Sample Rows Query: {}", query);
         self.query(&query).await
     }
 }
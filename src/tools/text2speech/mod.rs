@@ -1,4 +1,6 @@
+#[cfg(feature = "openai")]
 mod openai;
+#[cfg(feature = "openai")]
 pub use openai::*;
 
 mod speech_storage;
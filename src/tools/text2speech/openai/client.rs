@@ -1,4 +1,4 @@
-use std::{error::Error, sync::Arc};
+use std::sync::Arc;
 
 use async_openai::types::CreateSpeechRequestArgs;
 use async_openai::Client;
@@ -9,6 +9,7 @@ pub use async_openai::{
 use async_trait::async_trait;
 use serde_json::Value;
 
+use crate::error::LangChainError;
 use crate::tools::{SpeechStorage, Tool};
 
 #[derive(Clone)]
@@ -84,7 +85,7 @@ impl<C: Config + Send + Sync> Tool for Text2SpeechOpenAI<C> {
             .to_string()
     }
 
-    async fn run(&self, input: Value) -> Result<String, Box<dyn Error>> {
+    async fn run(&self, input: Value) -> Result<String, LangChainError> {
         let input = input.as_str().ok_or("Invalid input")?;
         let client = Client::new();
         let response_format: SpeechResponseFormat = self.response_format;
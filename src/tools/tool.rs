@@ -1,9 +1,12 @@
-use std::error::Error;
-use std::string::String;
+use std::{string::String, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use serde_json::{json, Value};
 
+use crate::callbacks::trace_tool;
+use crate::error::LangChainError;
+use crate::resilience::{RetryPolicy, WithFallbacks, WithRetry, WithTimeout};
+
 #[async_trait]
 pub trait Tool: Send + Sync {
     /// Returns the name of the tool.
@@ -44,28 +47,31 @@ pub trait Tool: Send + Sync {
     ///
     /// This function utilizes `parse_input` to parse the input and then calls `run`.
     /// Its used by the Agent
-    async fn call(&self, input: &str) -> Result<String, Box<dyn Error>> {
-        let input = self.parse_input(input).await;
-        self.run(input).await
+    async fn call(&self, input: &str) -> Result<String, LangChainError> {
+        trace_tool(&self.name(), input, async {
+            let input = self.parse_input(input).await;
+            self.run(input).await
+        })
+        .await
     }
 
     /// Executes the core functionality of the tool.
     ///
     /// Example implementation:
     /// ```rust,ignore
-    /// async fn run(&self, input: Value) -> Result<String, Box<dyn Error>> {
+    /// async fn run(&self, input: Value) -> Result<String, LangChainError> {
     ///     let input_str = input.as_str().ok_or("Input should be a string")?;
     ///     self.simple_search(input_str).await
     /// }
     /// ```
-    async fn run(&self, input: Value) -> Result<String, Box<dyn Error>>;
+    async fn run(&self, input: Value) -> Result<String, LangChainError>;
 
     /// Parses the input string, which could be a JSON value or a raw string, depending on the LLM model.
     ///
     /// Implement this function to extract the parameters needed for your tool. If a simple
     /// string is sufficient, the default implementation can be used.
     async fn parse_input(&self, input: &str) -> Value {
-        log::info!("Using default implementation: {}", input);
+        tracing::info!("Using default implementation: {}", input);
         match serde_json::from_str::<Value>(input) {
             Ok(input) => {
                 if input["input"].is_string() {
@@ -77,4 +83,28 @@ pub trait Tool: Send + Sync {
             Err(_) => Value::String(input.to_string()),
         }
     }
+
+    /// Retries the calls that fail with a transient error, following `policy`.
+    fn with_retry(self, policy: RetryPolicy) -> WithRetry<Self>
+    where
+        Self: Sized,
+    {
+        WithRetry::new(self, policy)
+    }
+
+    /// Calls the `fallbacks` in order when a call fails.
+    fn with_fallbacks(self, fallbacks: Vec<Arc<dyn Tool>>) -> WithFallbacks<Self, Arc<dyn Tool>>
+    where
+        Self: Sized,
+    {
+        WithFallbacks::new(self, fallbacks)
+    }
+
+    /// Fails the calls that take longer than `timeout` with `LangChainError::Timeout`.
+    fn with_timeout(self, timeout: Duration) -> WithTimeout<Self>
+    where
+        Self: Sized,
+    {
+        WithTimeout::new(self, timeout)
+    }
 }
@@ -1,8 +1,9 @@
 use async_trait::async_trait;
 use serde_json::Value;
 
+use crate::error::LangChainError;
+use crate::platform::send;
 use crate::tools::Tool;
-use std::error::Error;
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 struct WolframError {
@@ -127,7 +128,7 @@ impl Tool for Wolfram {
             interpret.",
         )
     }
-    async fn run(&self, input: Value) -> Result<String, Box<dyn Error>> {
+    async fn run(&self, input: Value) -> Result<String, LangChainError> {
         let input = input.as_str().ok_or("Invalid input")?;
         let mut url = format!(
             "https://api.wolframalpha.com/v2/query?appid={}&input={}&output=JSON&format=plaintext&podstate=Result__Step-by-step+solution",
@@ -139,18 +140,16 @@ impl Tool for Wolfram {
             url += &format!("&excludepodid={}", self.exclude_pods.join(","));
         }
 
-        let response: WolframResponse = self.client.get(&url).send().await?.json().await?;
+        let response: WolframResponse =
+            send(async { self.client.get(&url).send().await?.json().await }).await?;
 
         if let WolframErrorStatus::Error(error) = response.queryresult.error {
-            return Err(Box::new(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Wolfram Error {}: {}", error.code, error.msg),
+            return Err(LangChainError::ToolError(format!(
+                "Wolfram Error {}: {}",
+                error.code, error.msg
             )));
         } else if !response.queryresult.success {
-            return Err(Box::new(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Wolfram Error invalid query input: The query requested can not be processed by Wolfram".to_string(),
-            )));
+            return Err(LangChainError::ToolError("Wolfram Error invalid query input: The query requested can not be processed by Wolfram".to_string()));
         }
 
         let pods_str: Vec<String> = response
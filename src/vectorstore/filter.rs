@@ -0,0 +1,246 @@
+use std::ops::Not;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A condition on document metadata, which each vector store translates to its native query
+/// syntax.
+///
+/// Conditions compare the metadata value under a key, and combine with `and`, `or` and `!`.
+///
+/// # Usage
+/// ```rust,ignore
+/// let filter = Filter::eq("source", "docs")
+///     .and(Filter::in_("lang", ["en", "de"]))
+///     .and(Filter::gt("year", 2020));
+/// let options = VecStoreOptions::new().with_filters(filter);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Filter {
+    Eq(String, Value),
+    Ne(String, Value),
+    Gt(String, Value),
+    Gte(String, Value),
+    Lt(String, Value),
+    Lte(String, Value),
+    In(String, Vec<Value>),
+    NotIn(String, Vec<Value>),
+    And(Vec<Filter>),
+    Or(Vec<Filter>),
+    Not(Box<Filter>),
+}
+
+impl Filter {
+    pub fn eq<K: Into<String>, V: Into<Value>>(key: K, value: V) -> Self {
+        Filter::Eq(key.into(), value.into())
+    }
+
+    pub fn ne<K: Into<String>, V: Into<Value>>(key: K, value: V) -> Self {
+        Filter::Ne(key.into(), value.into())
+    }
+
+    pub fn gt<K: Into<String>, V: Into<Value>>(key: K, value: V) -> Self {
+        Filter::Gt(key.into(), value.into())
+    }
+
+    pub fn gte<K: Into<String>, V: Into<Value>>(key: K, value: V) -> Self {
+        Filter::Gte(key.into(), value.into())
+    }
+
+    pub fn lt<K: Into<String>, V: Into<Value>>(key: K, value: V) -> Self {
+        Filter::Lt(key.into(), value.into())
+    }
+
+    pub fn lte<K: Into<String>, V: Into<Value>>(key: K, value: V) -> Self {
+        Filter::Lte(key.into(), value.into())
+    }
+
+    /// Matches the documents whose value is one of `values`.
+    pub fn in_<K, V, I>(key: K, values: I) -> Self
+    where
+        K: Into<String>,
+        V: Into<Value>,
+        I: IntoIterator<Item = V>,
+    {
+        Filter::In(key.into(), values.into_iter().map(Into::into).collect())
+    }
+
+    /// Matches the documents whose value is none of `values`.
+    pub fn not_in<K, V, I>(key: K, values: I) -> Self
+    where
+        K: Into<String>,
+        V: Into<Value>,
+        I: IntoIterator<Item = V>,
+    {
+        Filter::NotIn(key.into(), values.into_iter().map(Into::into).collect())
+    }
+
+    pub fn and(self, other: Filter) -> Self {
+        match self {
+            Filter::And(mut filters) => {
+                filters.push(other);
+                Filter::And(filters)
+            }
+            filter => Filter::And(vec![filter, other]),
+        }
+    }
+
+    pub fn or(self, other: Filter) -> Self {
+        match self {
+            Filter::Or(mut filters) => {
+                filters.push(other);
+                Filter::Or(filters)
+            }
+            filter => Filter::Or(vec![filter, other]),
+        }
+    }
+
+    /// Renders the filter as a SQL condition with `?` placeholders, pushing the values to bind
+    /// onto `params` in order. `field` renders the expression reading a metadata key, and may
+    /// push parameters of its own.
+    pub(crate) fn to_sql(
+        &self,
+        field: &dyn Fn(&str, &mut Vec<Value>) -> String,
+        params: &mut Vec<Value>,
+    ) -> String {
+        let mut compare = |key: &str, operator: &str, value: &Value| {
+            let field = field(key, params);
+            params.push(value.clone());
+            format!("{} {} ?", field, operator)
+        };
+        match self {
+            Filter::Eq(key, Value::Null) => format!("{} IS NULL", field(key, params)),
+            Filter::Ne(key, Value::Null) => format!("{} IS NOT NULL", field(key, params)),
+            Filter::Eq(key, value) => compare(key, "=", value),
+            Filter::Ne(key, value) => compare(key, "!=", value),
+            Filter::Gt(key, value) => compare(key, ">", value),
+            Filter::Gte(key, value) => compare(key, ">=", value),
+            Filter::Lt(key, value) => compare(key, "<", value),
+            Filter::Lte(key, value) => compare(key, "<=", value),
+            Filter::In(key, values) | Filter::NotIn(key, values) => {
+                let operator = match self {
+                    Filter::In(..) => "IN",
+                    _ => "NOT IN",
+                };
+                let field = field(key, params);
+                params.extend(values.iter().cloned());
+                let placeholders = vec!["?"; values.len()].join(", ");
+                format!("{} {} ({})", field, operator, placeholders)
+            }
+            Filter::And(filters) | Filter::Or(filters) => {
+                let (operator, empty) = match self {
+                    Filter::And(_) => (" AND ", "TRUE"),
+                    _ => (" OR ", "FALSE"),
+                };
+                if filters.is_empty() {
+                    return empty.to_string();
+                }
+                let conditions = filters
+                    .iter()
+                    .map(|filter| filter.to_sql(field, params))
+                    .collect::<Vec<_>>();
+                format!("({})", conditions.join(operator))
+            }
+            Filter::Not(filter) => format!("NOT ({})", filter.to_sql(field, params)),
+        }
+    }
+}
+
+impl Not for Filter {
+    type Output = Filter;
+
+    fn not(self) -> Self::Output {
+        match self {
+            Filter::Not(filter) => *filter,
+            filter => Filter::Not(Box::new(filter)),
+        }
+    }
+}
+
+/// Reads a metadata key of the `e` table alias, the key bound as a JSON path so any key is
+/// safe to query.
+#[cfg(any(feature = "sqlite-vss", feature = "sqlite-vec"))]
+pub(crate) fn sqlite_metadata_field(key: &str, params: &mut Vec<Value>) -> String {
+    params.push(Value::String(format!("$.\"{}\"", key)));
+    "json_extract(e.metadata, ?)".to_string()
+}
+
+/// Binds the parameters of `Filter::to_sql` to a SQLite query. JSON arrays and objects are
+/// bound as their JSON text, which is what `json_extract` returns for them.
+#[cfg(any(feature = "sqlite-vss", feature = "sqlite-vec"))]
+pub(crate) fn bind_sqlite_params<'q>(
+    mut query: sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+    params: Vec<Value>,
+) -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>> {
+    for param in params {
+        query = match param {
+            Value::Null => query.bind(None::<String>),
+            Value::Bool(value) => query.bind(value),
+            Value::Number(number) => match number.as_i64() {
+                Some(value) => query.bind(value),
+                None => query.bind(number.as_f64()),
+            },
+            Value::String(value) => query.bind(value),
+            value => query.bind(value.to_string()),
+        };
+    }
+    query
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_filter_builder() {
+        let filter = Filter::eq("source", "docs")
+            .and(Filter::in_("lang", ["en", "de"]))
+            .and(Filter::gt("year", 2020));
+        assert_eq!(
+            filter,
+            Filter::And(vec![
+                Filter::Eq("source".into(), json!("docs")),
+                Filter::In("lang".into(), vec![json!("en"), json!("de")]),
+                Filter::Gt("year".into(), json!(2020)),
+            ])
+        );
+        assert_eq!(!!filter.clone(), filter);
+
+        let round_trip: Filter =
+            serde_json::from_value(serde_json::to_value(&filter).unwrap()).unwrap();
+        assert_eq!(round_trip, filter);
+    }
+
+    #[test]
+    fn test_filter_to_sql() {
+        let filter = Filter::eq("source", "docs")
+            .and(Filter::in_("lang", ["en", "de"]).or(!Filter::eq("draft", Value::Null)));
+        let mut params = Vec::new();
+        let sql = filter.to_sql(
+            &|key, params| {
+                params.push(json!(format!("$.\"{}\"", key)));
+                "json_extract(metadata, ?)".to_string()
+            },
+            &mut params,
+        );
+        assert_eq!(
+            sql,
+            "(json_extract(metadata, ?) = ? AND (json_extract(metadata, ?) IN (?, ?) \
+             OR NOT (json_extract(metadata, ?) IS NULL)))"
+        );
+        assert_eq!(
+            params,
+            vec![
+                json!("$.\"source\""),
+                json!("docs"),
+                json!("$.\"lang\""),
+                json!("en"),
+                json!("de"),
+                json!("$.\"draft\""),
+            ]
+        );
+    }
+}
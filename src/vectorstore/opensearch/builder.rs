@@ -1,7 +1,7 @@
 use crate::embedding::Embedder;
+use crate::error::LangChainError;
 use crate::vectorstore::opensearch::Store;
 use opensearch::OpenSearch;
-use std::error::Error;
 use std::sync::Arc;
 
 pub struct StoreBuilder {
@@ -57,7 +57,7 @@ impl StoreBuilder {
     }
 
     // Finalize the builder and construct the Store object
-    pub async fn build(self) -> Result<Store, Box<dyn Error>> {
+    pub async fn build(self) -> Result<Store, LangChainError> {
         if self.client.is_none() {
             return Err("Client is required".into());
         }
@@ -2,10 +2,9 @@ use async_trait::async_trait;
 use opensearch::http::request::JsonBody;
 use opensearch::http::response::Response;
 use opensearch::indices::{IndicesCreateParts, IndicesDeleteParts};
-use opensearch::{BulkParts, SearchParts};
+use opensearch::{BulkParts, DeleteParts, SearchParts};
 use serde_json::{json, Value};
 use std::collections::HashMap;
-use std::error::Error;
 use std::sync::Arc;
 
 pub use opensearch::auth::Credentials;
@@ -15,8 +14,9 @@ pub use opensearch::OpenSearch;
 
 use crate::{
     embedding::embedder_trait::Embedder,
+    error::LangChainError,
     schemas::Document,
-    vectorstore::{VecStoreOptions, VectorStore},
+    vectorstore::{Filter, VecStoreOptions, VectorStore},
 };
 
 pub struct Store {
@@ -33,7 +33,7 @@ pub struct Store {
 // https://opensearch.org/docs/latest/clients/rust/
 
 impl Store {
-    pub async fn delete_index(&self) -> Result<Response, Box<dyn Error>> {
+    pub async fn delete_index(&self) -> Result<Response, LangChainError> {
         let response = self
             .client
             .indices()
@@ -41,12 +41,12 @@ impl Store {
             .send()
             .await?;
 
-        let result = response.error_for_status_code().map_err(|e| Box::new(e))?;
+        let result = response.error_for_status_code()?;
 
         Ok(result)
     }
 
-    pub async fn create_index(&self) -> Result<Response, Box<dyn Error>> {
+    pub async fn create_index(&self) -> Result<Response, LangChainError> {
         let body = json!({
             "settings": {
                 "index.knn": true,
@@ -91,7 +91,7 @@ impl Store {
             .send()
             .await?;
 
-        let result = response.error_for_status_code().map_err(|e| Box::new(e))?;
+        let result = response.error_for_status_code()?;
 
         Ok(result)
     }
@@ -103,22 +103,24 @@ impl VectorStore for Store {
         &self,
         docs: &[Document],
         opt: &VecStoreOptions,
-    ) -> Result<Vec<String>, Box<dyn Error>> {
+    ) -> Result<Vec<String>, LangChainError> {
         let texts: Vec<String> = docs.iter().map(|d| d.page_content.clone()).collect();
         let embedder = opt.embedder.as_ref().unwrap_or(&self.embedder);
         let vectors = embedder.embed_documents(&texts).await?;
 
         if vectors.len() != docs.len() {
-            return Err(Box::new(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Number of vectors and documents do not match",
-            )));
+            return Err(LangChainError::VectorStoreError(
+                "Number of vectors and documents do not match".to_string(),
+            ));
         }
 
         let mut body: Vec<JsonBody<_>> = Vec::with_capacity(docs.len() * 2);
 
         for (doc, vector) in docs.iter().zip(vectors.iter()) {
-            let operation = json!({"index": {}});
+            let operation = match &doc.id {
+                Some(id) => json!({"index": {"_id": id}}),
+                None => json!({"index": {}}),
+            };
             body.push(operation.into());
 
             let document = json!({
@@ -135,8 +137,7 @@ impl VectorStore for Store {
             .body(body)
             .send()
             .await?
-            .error_for_status_code()
-            .map_err(|e| Box::new(e))?;
+            .error_for_status_code()?;
 
         let response_body = response.json::<Value>().await?;
 
@@ -155,14 +156,14 @@ impl VectorStore for Store {
         query: &str,
         limit: usize,
         opt: &VecStoreOptions,
-    ) -> Result<Vec<Document>, Box<dyn Error>> {
+    ) -> Result<Vec<Document>, LangChainError> {
         let query_vector = self.embedder.embed_query(query).await?;
         let query = build_similarity_search_query(
             query_vector,
             &self.vector_field,
             limit,
             self.k,
-            opt.filters.clone(),
+            opt.filters.as_ref().map(opensearch_filter),
         );
 
         let response = self
@@ -197,6 +198,7 @@ impl VectorStore for Store {
                 .unwrap();
                 let score = serde_json::from_value::<f64>(item["_score"].clone()).unwrap();
                 Document {
+                    id: item["_id"].as_str().map(str::to_string),
                     page_content,
                     metadata,
                     score,
@@ -206,6 +208,20 @@ impl VectorStore for Store {
 
         Ok(documents)
     }
+
+    async fn delete(&self, ids: &[String], _opt: &VecStoreOptions) -> Result<(), LangChainError> {
+        for id in ids {
+            let response = self
+                .client
+                .delete(DeleteParts::IndexId(&self.index, id))
+                .send()
+                .await?;
+            if response.status_code() != opensearch::http::StatusCode::NOT_FOUND {
+                response.error_for_status_code()?;
+            }
+        }
+        Ok(())
+    }
 }
 
 fn build_similarity_search_query(
@@ -245,3 +261,32 @@ fn build_similarity_search_query(
         }
     }
 }
+
+/// Translates a filter to the query DSL on the `metadata` fields. Equality uses `term`
+/// queries, which match the exact value, so string fields compared whole should be mapped as
+/// `keyword`.
+fn opensearch_filter(filter: &Filter) -> Value {
+    let field = |key: &str| format!("metadata.{}", key);
+    let range = |key: &str, operator: &str, value: &Value| json!({ "range": { field(key): { operator: value } } });
+    let must_not = |query: Value| json!({ "bool": { "must_not": [query] } });
+    match filter {
+        Filter::Eq(key, value) => json!({ "term": { field(key): value } }),
+        Filter::Ne(key, value) => must_not(json!({ "term": { field(key): value } })),
+        Filter::Gt(key, value) => range(key, "gt", value),
+        Filter::Gte(key, value) => range(key, "gte", value),
+        Filter::Lt(key, value) => range(key, "lt", value),
+        Filter::Lte(key, value) => range(key, "lte", value),
+        Filter::In(key, values) => json!({ "terms": { field(key): values } }),
+        Filter::NotIn(key, values) => must_not(json!({ "terms": { field(key): values } })),
+        Filter::And(filters) => {
+            json!({ "bool": { "must": filters.iter().map(opensearch_filter).collect::<Vec<_>>() } })
+        }
+        Filter::Or(filters) => json!({
+            "bool": {
+                "should": filters.iter().map(opensearch_filter).collect::<Vec<_>>(),
+                "minimum_should_match": 1,
+            }
+        }),
+        Filter::Not(filter) => must_not(opensearch_filter(filter)),
+    }
+}
@@ -1,25 +1,30 @@
 use std::sync::Arc;
 
-use serde_json::Value;
+use serde::{Deserialize, Serialize};
 
 use crate::embedding::embedder_trait::Embedder;
 
+use super::Filter;
+
 /// The `VecStoreOptions` struct is responsible for determining options when
 /// interacting with a Vector Store. The options include `name_space`, `score_threshold`,
-/// `filters`, and `embedder`.
+/// `filters`, and `embedder`. The `embedder` is not serialized. See `Filter` for how to build
+/// metadata filters.
 ///
 /// # Usage
 /// ```rust,ignore
 /// let options = VecStoreOptions::new()
 ///     .with_name_space("my_custom_namespace")
 ///     .with_score_threshold(0.5)
-///     .with_filters(json!({"genre": "Sci-Fi"}))
+///     .with_filters(Filter::eq("genre", "Sci-Fi"))
 ///     .with_embedder(my_embedder);
 /// ```
+#[derive(Serialize, Deserialize)]
 pub struct VecStoreOptions {
     pub name_space: Option<String>,
     pub score_threshold: Option<f32>,
-    pub filters: Option<Value>,
+    pub filters: Option<Filter>,
+    #[serde(skip)]
     pub embedder: Option<Arc<dyn Embedder>>,
 }
 
@@ -49,7 +54,7 @@ impl VecStoreOptions {
         self
     }
 
-    pub fn with_filters(mut self, filters: Value) -> Self {
+    pub fn with_filters(mut self, filters: Filter) -> Self {
         self.filters = Some(filters);
         self
     }
@@ -1,9 +1,11 @@
-use std::{collections::HashMap, env, error::Error, sync::Arc};
+use std::{collections::HashMap, env, sync::Arc};
 
 use serde_json::{json, Value};
 use sqlx::{postgres::PgPoolOptions, Pool, Postgres, Row, Transaction};
 
-use crate::{embedding::embedder_trait::Embedder, vectorstore::VecStoreOptions};
+use crate::{
+    embedding::embedder_trait::Embedder, error::LangChainError, vectorstore::VecStoreOptions,
+};
 
 use super::{
     HNSWIndex, Store, PG_LOCKID_EXTENSION, PG_LOCK_ID_COLLECTION_TABLE, PG_LOCK_ID_EMBEDDING_TABLE,
@@ -104,7 +106,7 @@ impl StoreBuilder {
     }
 
     // Finalize the builder and construct the Store object
-    pub async fn build(self) -> Result<Store, Box<dyn Error>> {
+    pub async fn build(self) -> Result<Store, LangChainError> {
         if self.embedder.is_none() {
             return Err("Embedder is required".into());
         }
@@ -137,7 +139,7 @@ impl StoreBuilder {
         })
     }
 
-    async fn get_pool(&self) -> Result<Pool<Postgres>, Box<dyn Error>> {
+    async fn get_pool(&self) -> Result<Pool<Postgres>, LangChainError> {
         match &self.pool {
             Some(existing_pool) => {
                 // If `self.pool` is Some, use the existing pool
@@ -167,7 +169,7 @@ impl StoreBuilder {
     async fn create_or_get_collection(
         &self,
         tx: &mut Transaction<'_, Postgres>,
-    ) -> Result<String, Box<dyn Error>> {
+    ) -> Result<String, LangChainError> {
         let sql = format!(
             r#"INSERT INTO {} (uuid, name, cmetadata)
         VALUES($1, $2, $3) ON CONFLICT (name) DO
@@ -196,7 +198,7 @@ impl StoreBuilder {
     async fn remove_collection(
         &self,
         tx: &mut Transaction<'_, Postgres>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), LangChainError> {
         sqlx::query(&format!(
             "DELETE FROM {} WHERE name = $1",
             self.collection_table_name
@@ -211,7 +213,7 @@ impl StoreBuilder {
     pub async fn create_vector_extension_if_not_exists(
         &self,
         tx: &mut Transaction<'_, Postgres>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), LangChainError> {
         // Acquire an advisory lock to prevent concurrent creation of the vector extension
         sqlx::query("SELECT pg_advisory_xact_lock($1)")
             .bind(PG_LOCKID_EXTENSION)
@@ -229,7 +231,7 @@ impl StoreBuilder {
     async fn create_collection_table_if_not_exists(
         &self,
         tx: &mut Transaction<'_, Postgres>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), LangChainError> {
         // inspired by
         // https://github.com/langchain-ai/langchain/blob/v0.0.340/libs/langchain/langchain/vectorstores/pgvector.py#L167
         // The advisor lock fixes issue arising from concurrent
@@ -267,7 +269,7 @@ impl StoreBuilder {
     async fn create_embedding_table_if_not_exists(
         &self,
         tx: &mut Transaction<'_, Postgres>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), LangChainError> {
         sqlx::query("SELECT pg_advisory_xact_lock($1)")
             .bind(PG_LOCK_ID_EMBEDDING_TABLE)
             .execute(&mut **tx)
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, error::Error, sync::Arc};
+use std::{collections::HashMap, sync::Arc};
 
 use async_trait::async_trait;
 use pgvector::Vector;
@@ -8,6 +8,7 @@ use uuid::Uuid;
 
 use crate::{
     embedding::embedder_trait::Embedder,
+    error::LangChainError,
     schemas::Document,
     vectorstore::{VecStoreOptions, VectorStore},
 };
@@ -43,20 +44,6 @@ impl HNSWIndex {
 }
 
 impl Store {
-    // getFilters return metadata filters, now only support map[key]value pattern
-    // TODO: should support more types like {"key1": {"key2":"values2"}} or {"key": ["value1", "values2"]}.
-    fn get_filters(&self, opt: &VecStoreOptions) -> Result<HashMap<String, Value>, Box<dyn Error>> {
-        match &opt.filters {
-            Some(Value::Object(map)) => {
-                // Convert serde_json Map to HashMap<String, Value>
-                let filters = map.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-                Ok(filters)
-            }
-            None => Ok(HashMap::new()), // No filters provided
-            _ => Err("Invalid filters format".into()), // Filters provided but not in the expected format
-        }
-    }
-
     fn get_name_space(&self, opt: &VecStoreOptions) -> String {
         match &opt.name_space {
             Some(name_space) => name_space.clone(),
@@ -64,7 +51,7 @@ impl Store {
         }
     }
 
-    fn get_score_threshold(&self, opt: &VecStoreOptions) -> Result<f32, Box<dyn Error>> {
+    fn get_score_threshold(&self, opt: &VecStoreOptions) -> Result<f32, LangChainError> {
         match &opt.score_threshold {
             Some(score_threshold) => {
                 if *score_threshold < 0.0 || *score_threshold > 1.0 {
@@ -76,7 +63,7 @@ impl Store {
         }
     }
 
-    async fn drop_tables(&self) -> Result<(), Box<dyn Error>> {
+    async fn drop_tables(&self) -> Result<(), LangChainError> {
         sqlx::query(&format!(
             r#"DROP TABLE IF EXISTS {}"#,
             self.embedder_table_name
@@ -94,7 +81,7 @@ impl Store {
         Ok(())
     }
 
-    async fn remove_collection(&self) -> Result<(), Box<dyn Error>> {
+    async fn remove_collection(&self) -> Result<(), LangChainError> {
         sqlx::query(r#"DELETE FROM collection WHERE uuid = $1"#)
             .bind(&self.collection_uuid)
             .execute(&self.pool)
@@ -108,12 +95,12 @@ impl VectorStore for Store {
         &self,
         docs: &[Document],
         opt: &VecStoreOptions,
-    ) -> Result<Vec<String>, Box<dyn Error>> {
+    ) -> Result<Vec<String>, LangChainError> {
         if opt.score_threshold.is_some() || opt.filters.is_some() || opt.name_space.is_some() {
-            return Err(Box::new(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "score_threshold, filters, and name_space are not supported in pgvector",
-            )));
+            return Err(LangChainError::VectorStoreError(
+                "score_threshold, filters, and name_space are not supported in pgvector"
+                    .to_string(),
+            ));
         }
         let texts: Vec<String> = docs.iter().map(|d| d.page_content.clone()).collect();
 
@@ -122,10 +109,9 @@ impl VectorStore for Store {
         let vectors = embedder.embed_documents(&texts).await?;
 
         if vectors.len() != docs.len() {
-            return Err(Box::new(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Number of vectors and documents do not match",
-            )));
+            return Err(LangChainError::VectorStoreError(
+                "Number of vectors and documents do not match".to_string(),
+            ));
         }
 
         let mut tx = self.pool.begin().await?;
@@ -133,7 +119,7 @@ impl VectorStore for Store {
         let mut ids = Vec::with_capacity(docs.len());
 
         for (doc, vector) in docs.iter().zip(vectors.iter()) {
-            let id = Uuid::new_v4().to_string();
+            let id = doc.id.clone().unwrap_or_else(|| Uuid::new_v4().to_string());
             ids.push(id.clone());
 
             let vector_value =
@@ -141,7 +127,9 @@ impl VectorStore for Store {
 
             sqlx::query(&format!(
                 r#"INSERT INTO {} 
-(uuid, document, embedding, cmetadata, collection_id) VALUES ($1, $2, $3, $4, $5)"#,
+(uuid, document, embedding, cmetadata, collection_id) VALUES ($1, $2, $3, $4, $5)
+ON CONFLICT (uuid) DO UPDATE SET document = EXCLUDED.document, embedding = EXCLUDED.embedding,
+cmetadata = EXCLUDED.cmetadata, collection_id = EXCLUDED.collection_id"#,
                 self.embedder_table_name
             ))
             .bind(&id)
@@ -163,9 +151,14 @@ impl VectorStore for Store {
         query: &str,
         limit: usize,
         opt: &VecStoreOptions,
-    ) -> Result<Vec<Document>, Box<dyn Error>> {
+    ) -> Result<Vec<Document>, LangChainError> {
+        if opt.filters.is_some() {
+            return Err(LangChainError::VectorStoreError(
+                "filters are not supported in pgvector".to_string(),
+            ));
+        }
         let namespace = opt.name_space.as_deref().unwrap_or("default");
-        
+
         let sql = format!(
             r#"SELECT 
                 content,
@@ -179,9 +172,9 @@ impl VectorStore for Store {
                 distance ASC
             LIMIT $3"#
         );
-    
+
         let query_vector = self.embedder.embed_query(query).await?;
-    
+
         let rows = sqlx::query(&sql)
             .bind(&Vector::from(
                 query_vector
@@ -193,25 +186,37 @@ impl VectorStore for Store {
             .bind(limit as i32)
             .fetch_all(&self.pool)
             .await?;
-    
+
         let docs = rows
             .into_iter()
             .map(|row| {
                 let page_content: String = row.try_get(0)?;
                 let namespace: String = row.try_get(1)?;
                 let distance: f64 = row.try_get(2)?;
-    
+
                 let mut metadata = HashMap::new();
                 metadata.insert("namespace".to_string(), Value::String(namespace));
-    
+
                 Ok(Document {
+                    id: None,
                     page_content,
                     metadata,
-                    score: distance,  // Lower distance means more similar
+                    score: distance, // Lower distance means more similar
                 })
             })
             .collect::<Result<Vec<Document>, sqlx::Error>>()?;
-    
+
         Ok(docs)
     }
+
+    async fn delete(&self, ids: &[String], _opt: &VecStoreOptions) -> Result<(), LangChainError> {
+        sqlx::query(&format!(
+            r#"DELETE FROM {} WHERE uuid = ANY($1)"#,
+            self.embedder_table_name
+        ))
+        .bind(ids)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
 }
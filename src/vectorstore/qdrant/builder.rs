@@ -1,8 +1,8 @@
 use crate::embedding::Embedder;
+use crate::error::LangChainError;
 use crate::vectorstore::qdrant::Store;
 use qdrant_client::qdrant::{CreateCollectionBuilder, Distance, Filter, VectorParamsBuilder};
 use qdrant_client::Qdrant;
-use std::error::Error;
 use std::sync::Arc;
 
 pub struct StoreBuilder {
@@ -88,7 +88,7 @@ impl StoreBuilder {
     }
 
     /// Build the Store object.
-    pub async fn build(mut self) -> Result<Store, Box<dyn Error>> {
+    pub async fn build(mut self) -> Result<Store, LangChainError> {
         let client = self.client.take().ok_or("'client' is required")?;
         let embedder = self.embedder.take().ok_or("'embedder' is required")?;
         let collection_name = self
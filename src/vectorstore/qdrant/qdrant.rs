@@ -1,8 +1,11 @@
 use async_trait::async_trait;
 use qdrant_client::client::Payload;
-use qdrant_client::qdrant::{Filter, PointStruct, SearchPointsBuilder, UpsertPointsBuilder};
-use serde_json::json;
-use std::error::Error;
+use qdrant_client::qdrant::{
+    point_id::PointIdOptions, r#match::MatchValue, Condition, DeletePointsBuilder, Filter, PointId,
+    PointStruct, PointsIdsList, Range, RepeatedIntegers, RepeatedStrings, SearchPointsBuilder,
+    UpsertPointsBuilder,
+};
+use serde_json::{json, Value};
 use std::sync::Arc;
 
 pub use qdrant_client::Qdrant;
@@ -13,8 +16,9 @@ pub use qdrant_client::Qdrant as QdrantClient;
 
 use crate::{
     embedding::embedder_trait::Embedder,
+    error::LangChainError,
     schemas::Document,
-    vectorstore::{VecStoreOptions, VectorStore},
+    vectorstore::{self, VecStoreOptions, VectorStore},
 };
 use uuid::Uuid;
 
@@ -30,16 +34,21 @@ pub struct Store {
 #[async_trait]
 impl VectorStore for Store {
     /// Add documents to the store.
+    /// Documents with an `id` replace the point with the same ID, which Qdrant requires to be
+    /// a UUID or an unsigned integer; the others get a new UUID.
     /// Returns a list of document IDs added to the Qdrant collection.
     async fn add_documents(
         &self,
         docs: &[Document],
         opt: &VecStoreOptions,
-    ) -> Result<Vec<String>, Box<dyn Error>> {
+    ) -> Result<Vec<String>, LangChainError> {
         let embedder = opt.embedder.as_ref().unwrap_or(&self.embedder);
         let texts: Vec<String> = docs.iter().map(|d| d.page_content.clone()).collect();
 
-        let ids = docs.iter().map(|_| Uuid::new_v4().to_string());
+        let ids: Vec<String> = docs
+            .iter()
+            .map(|d| d.id.clone().unwrap_or_else(|| Uuid::new_v4().to_string()))
+            .collect();
         let vectors = embedder.embed_documents(&texts).await?.into_iter();
         let payloads = docs.iter().map(|d| {
             json!({
@@ -50,9 +59,9 @@ impl VectorStore for Store {
 
         let mut points: Vec<PointStruct> = Vec::with_capacity(docs.len());
 
-        for (id, (vector, payload)) in ids.clone().zip(vectors.zip(payloads)) {
+        for (id, (vector, payload)) in ids.iter().zip(vectors.zip(payloads)) {
             let vector: Vec<f32> = vector.into_iter().map(|f| f as f32).collect();
-            let point = PointStruct::new(id, vector, Payload::try_from(payload).unwrap());
+            let point = PointStruct::new(point_id(id), vector, Payload::try_from(payload).unwrap());
             points.push(point);
         }
 
@@ -60,7 +69,7 @@ impl VectorStore for Store {
             .upsert_points(UpsertPointsBuilder::new(&self.collection_name, points).wait(true))
             .await?;
 
-        Ok(ids.collect())
+        Ok(ids)
     }
 
     /// Perform a similarity search on the store.
@@ -70,19 +79,11 @@ impl VectorStore for Store {
         query: &str,
         limit: usize,
         opt: &VecStoreOptions,
-    ) -> Result<Vec<Document>, Box<dyn Error>> {
+    ) -> Result<Vec<Document>, LangChainError> {
         if opt.name_space.is_some() {
             return Err("Qdrant doesn't support namespaces".into());
         }
 
-        if opt.filters.is_some() {
-            return Err(
-                "'qdrant_client' doesn't support 'serde_json::Value' filters. 
-            Use `search_filter` when constructing VectorStore instead"
-                    .into(),
-            );
-        }
-
         let embedder = opt.embedder.as_ref().unwrap_or(&self.embedder);
         let query_vector: Vec<f32> = embedder
             .embed_query(query)
@@ -97,8 +98,16 @@ impl VectorStore for Store {
         if let Some(score_threshold) = opt.score_threshold {
             operation = operation.score_threshold(score_threshold);
         }
-        if let Some(filter) = &self.search_filter {
-            operation = operation.filter(filter.clone());
+        let filter = match &opt.filters {
+            Some(filter) => Some(qdrant_filter(filter, &self.metadata_field)?),
+            None => None,
+        };
+        match (self.search_filter.clone(), filter) {
+            (Some(search_filter), Some(filter)) => {
+                operation = operation.filter(Filter::must([search_filter.into(), filter.into()]));
+            }
+            (Some(filter), None) | (None, Some(filter)) => operation = operation.filter(filter),
+            (None, None) => {}
         }
         let results = self.client.search_points(operation).await?;
 
@@ -113,7 +122,15 @@ impl VectorStore for Store {
                     serde_json::from_value(payload[&self.metadata_field].clone().into_json())
                         .unwrap();
                 let score = scored_point.score as f64;
+                let id = scored_point
+                    .id
+                    .and_then(|id| id.point_id_options)
+                    .map(|id| match id {
+                        PointIdOptions::Num(num) => num.to_string(),
+                        PointIdOptions::Uuid(uuid) => uuid,
+                    });
                 Document {
+                    id,
                     page_content,
                     metadata,
                     score,
@@ -123,4 +140,104 @@ impl VectorStore for Store {
 
         Ok(documents)
     }
+
+    async fn delete(&self, ids: &[String], _opt: &VecStoreOptions) -> Result<(), LangChainError> {
+        let ids = PointsIdsList {
+            ids: ids.iter().map(|id| point_id(id)).collect(),
+        };
+        self.client
+            .delete_points(
+                DeletePointsBuilder::new(&self.collection_name)
+                    .points(ids)
+                    .wait(true),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+fn point_id(id: &str) -> PointId {
+    match id.parse::<u64>() {
+        Ok(num) => PointId::from(num),
+        Err(_) => PointId::from(id.to_string()),
+    }
+}
+
+/// Translates a filter to a Qdrant filter on the payload under `metadata_field`. Qdrant
+/// matches strings, integers and booleans, and compares numbers in ranges.
+fn qdrant_filter(
+    filter: &vectorstore::Filter,
+    metadata_field: &str,
+) -> Result<Filter, LangChainError> {
+    use vectorstore::Filter as F;
+
+    let field = |key: &str| format!("{}.{}", metadata_field, key);
+    let range = |key: &str, value: &Value, set: fn(&mut Range, f64)| {
+        let value = value.as_f64().ok_or_else(|| {
+            LangChainError::VectorStoreError(format!("Qdrant can't compare {} in a range", value))
+        })?;
+        let mut range = Range::default();
+        set(&mut range, value);
+        Ok::<_, LangChainError>(Filter::must([Condition::range(field(key), range)]))
+    };
+    let filter = match filter {
+        F::Eq(key, Value::Null) => Filter::must([Condition::is_null(field(key))]),
+        F::Ne(key, Value::Null) => Filter::must_not([Condition::is_null(field(key))]),
+        F::Eq(key, value) => Filter::must([Condition::matches(field(key), match_value(value)?)]),
+        F::Ne(key, value) => {
+            Filter::must_not([Condition::matches(field(key), match_value(value)?)])
+        }
+        F::Gt(key, value) => range(key, value, |range, value| range.gt = Some(value))?,
+        F::Gte(key, value) => range(key, value, |range, value| range.gte = Some(value))?,
+        F::Lt(key, value) => range(key, value, |range, value| range.lt = Some(value))?,
+        F::Lte(key, value) => range(key, value, |range, value| range.lte = Some(value))?,
+        F::In(key, values) => Filter::must([Condition::matches(field(key), match_values(values)?)]),
+        F::NotIn(key, values) => {
+            Filter::must_not([Condition::matches(field(key), match_values(values)?)])
+        }
+        F::And(filters) => Filter::must(
+            filters
+                .iter()
+                .map(|filter| qdrant_filter(filter, metadata_field).map(Condition::from))
+                .collect::<Result<Vec<_>, _>>()?,
+        ),
+        F::Or(filters) => Filter::should(
+            filters
+                .iter()
+                .map(|filter| qdrant_filter(filter, metadata_field).map(Condition::from))
+                .collect::<Result<Vec<_>, _>>()?,
+        ),
+        F::Not(filter) => Filter::must_not([qdrant_filter(filter, metadata_field)?.into()]),
+    };
+    Ok(filter)
+}
+
+fn match_value(value: &Value) -> Result<MatchValue, LangChainError> {
+    match value {
+        Value::String(value) => Ok(MatchValue::Keyword(value.clone())),
+        Value::Bool(value) => Ok(MatchValue::Boolean(*value)),
+        Value::Number(number) if number.is_i64() => {
+            Ok(MatchValue::Integer(number.as_i64().unwrap()))
+        }
+        value => Err(LangChainError::VectorStoreError(format!(
+            "Qdrant can't match {}",
+            value
+        ))),
+    }
+}
+
+fn match_values(values: &[Value]) -> Result<MatchValue, LangChainError> {
+    if let Some(strings) = values
+        .iter()
+        .map(|value| value.as_str().map(str::to_string))
+        .collect::<Option<Vec<_>>>()
+    {
+        return Ok(MatchValue::Keywords(RepeatedStrings { strings }));
+    }
+    if let Some(integers) = values.iter().map(Value::as_i64).collect::<Option<Vec<_>>>() {
+        return Ok(MatchValue::Integers(RepeatedIntegers { integers }));
+    }
+    Err(LangChainError::VectorStoreError(
+        "Qdrant can only match lists of strings or of integers".to_string(),
+    ))
 }
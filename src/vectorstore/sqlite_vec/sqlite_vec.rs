@@ -1,4 +1,4 @@
-use std::{collections::HashMap, error::Error, sync::Arc};
+use std::{collections::HashMap, sync::Arc};
 
 use async_trait::async_trait;
 use serde_json::{json, Value};
@@ -6,8 +6,9 @@ use sqlx::{Pool, Row, Sqlite};
 
 use crate::{
     embedding::embedder_trait::Embedder,
+    error::LangChainError,
     schemas::Document,
-    vectorstore::{VecStoreOptions, VectorStore},
+    vectorstore::{bind_sqlite_params, sqlite_metadata_field, VecStoreOptions, VectorStore},
 };
 
 pub struct Store {
@@ -18,12 +19,12 @@ pub struct Store {
 }
 
 impl Store {
-    pub async fn initialize(&self) -> Result<(), Box<dyn Error>> {
+    pub async fn initialize(&self) -> Result<(), LangChainError> {
         self.create_table_if_not_exists().await?;
         Ok(())
     }
 
-    async fn create_table_if_not_exists(&self) -> Result<(), Box<dyn Error>> {
+    async fn create_table_if_not_exists(&self) -> Result<(), LangChainError> {
         let table = &self.table;
 
         sqlx::query(&format!(
@@ -69,18 +70,6 @@ impl Store {
 
         Ok(())
     }
-
-    fn get_filters(&self, opt: &VecStoreOptions) -> Result<HashMap<String, Value>, Box<dyn Error>> {
-        match &opt.filters {
-            Some(Value::Object(map)) => {
-                // Convert serde_json Map to HashMap<String, Value>
-                let filters = map.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-                Ok(filters)
-            }
-            None => Ok(HashMap::new()), // No filters provided
-            _ => Err("Invalid filters format".into()), // Filters provided but not in the expected format
-        }
-    }
 }
 
 #[async_trait]
@@ -89,17 +78,16 @@ impl VectorStore for Store {
         &self,
         docs: &[Document],
         opt: &VecStoreOptions,
-    ) -> Result<Vec<String>, Box<dyn Error>> {
+    ) -> Result<Vec<String>, LangChainError> {
         let texts: Vec<String> = docs.iter().map(|d| d.page_content.clone()).collect();
 
         let embedder = opt.embedder.as_ref().unwrap_or(&self.embedder);
 
         let vectors = embedder.embed_documents(&texts).await?;
         if vectors.len() != docs.len() {
-            return Err(Box::new(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Number of vectors and documents do not match",
-            )));
+            return Err(LangChainError::VectorStoreError(
+                "Number of vectors and documents do not match".to_string(),
+            ));
         }
 
         let table = &self.table;
@@ -109,14 +97,38 @@ impl VectorStore for Store {
         let mut ids = Vec::with_capacity(docs.len());
 
         for (doc, vector) in docs.iter().zip(vectors.iter()) {
+            // Rows are keyed by their rowid, so a provided id must be an integer; it replaces
+            // the row with the same rowid.
+            let rowid = doc
+                .id
+                .as_ref()
+                .map(|id| {
+                    id.parse::<i64>().map_err(|_| {
+                        LangChainError::VectorStoreError(format!(
+                            "Document id `{}` is not an integer",
+                            id
+                        ))
+                    })
+                })
+                .transpose()?;
+            if let Some(rowid) = rowid {
+                for table in [table.to_string(), format!("vec_{table}")] {
+                    sqlx::query(&format!("DELETE FROM {table} WHERE rowid = ?"))
+                        .bind(rowid)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+            }
+
             let text_embedding = json!(&vector);
             let id = sqlx::query(&format!(
                 r#"
                     INSERT INTO {table}
-                        (text, metadata, text_embedding)
+                        (rowid, text, metadata, text_embedding)
                     VALUES
-                        (?,?,?)"#
+                        (?,?,?,?)"#
             ))
+            .bind(rowid)
             .bind(&doc.page_content)
             .bind(json!(&doc.metadata))
             .bind(text_embedding.to_string())
@@ -137,25 +149,20 @@ impl VectorStore for Store {
         query: &str,
         limit: usize,
         opt: &VecStoreOptions,
-    ) -> Result<Vec<Document>, Box<dyn Error>> {
+    ) -> Result<Vec<Document>, LangChainError> {
         let table = &self.table;
 
         let query_vector = json!(self.embedder.embed_query(query).await?);
 
-        let filter = self.get_filters(opt)?;
+        let mut filter_params = Vec::new();
+        let metadata_query = match &opt.filters {
+            Some(filter) => filter.to_sql(&sqlite_metadata_field, &mut filter_params),
+            None => "TRUE".to_string(),
+        };
 
-        let mut metadata_query = filter
-            .iter()
-            .map(|(k, v)| format!("json_extract(e.metadata, '$.{}') = '{}'", k, v))
-            .collect::<Vec<String>>()
-            .join(" AND ");
-
-        if metadata_query.is_empty() {
-            metadata_query = "TRUE".to_string();
-        }
-
-        let rows = sqlx::query(&format!(
+        let query = format!(
             r#"SELECT
+                    e.rowid,
                     text,
                     metadata,
                     distance
@@ -164,15 +171,16 @@ impl VectorStore for Store {
                 WHERE v.text_embedding match '{query_vector}' AND k = ? AND {metadata_query}
                 ORDER BY distance
                 LIMIT ?"#
-        ))
-        .bind(limit as i32)
-        .bind(limit as i32)
-        .fetch_all(&self.pool)
-        .await?;
+        );
+        let rows = bind_sqlite_params(sqlx::query(&query).bind(limit as i32), filter_params)
+            .bind(limit as i32)
+            .fetch_all(&self.pool)
+            .await?;
 
         let docs = rows
             .into_iter()
             .map(|row| {
+                let rowid: i64 = row.try_get("rowid")?;
                 let page_content: String = row.try_get("text")?;
                 let metadata_json: Value = row.try_get("metadata")?;
                 let score: f64 = row.try_get("distance")?;
@@ -184,6 +192,7 @@ impl VectorStore for Store {
                 };
 
                 Ok(Document {
+                    id: Some(rowid.to_string()),
                     page_content,
                     metadata,
                     score,
@@ -193,4 +202,32 @@ impl VectorStore for Store {
 
         Ok(docs)
     }
+
+    async fn delete(&self, ids: &[String], _opt: &VecStoreOptions) -> Result<(), LangChainError> {
+        let table = &self.table;
+        let rowids = ids
+            .iter()
+            .map(|id| {
+                id.parse::<i64>().map_err(|_| {
+                    LangChainError::VectorStoreError(format!(
+                        "Document id `{}` is not an integer",
+                        id
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut tx = self.pool.begin().await?;
+        for rowid in rowids {
+            for table in [table.to_string(), format!("vec_{table}")] {
+                sqlx::query(&format!("DELETE FROM {table} WHERE rowid = ?"))
+                    .bind(rowid)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
 }
@@ -1,4 +1,4 @@
-use std::{error::Error, str::FromStr, sync::Arc};
+use std::{str::FromStr, sync::Arc};
 
 use sqlx::{
     sqlite::{SqliteConnectOptions, SqlitePoolOptions},
@@ -7,6 +7,7 @@ use sqlx::{
 
 use super::Store;
 use crate::embedding::embedder_trait::Embedder;
+use crate::error::LangChainError;
 
 pub struct StoreBuilder {
     pool: Option<Pool<Sqlite>>,
@@ -55,7 +56,7 @@ impl StoreBuilder {
     }
 
     // Finalize the builder and construct the Store object
-    pub async fn build(self) -> Result<Store, Box<dyn Error>> {
+    pub async fn build(self) -> Result<Store, LangChainError> {
         if self.embedder.is_none() {
             return Err("Embedder is required".into());
         }
@@ -68,7 +69,7 @@ impl StoreBuilder {
         })
     }
 
-    async fn get_pool(&self) -> Result<Pool<Sqlite>, Box<dyn Error>> {
+    async fn get_pool(&self) -> Result<Pool<Sqlite>, LangChainError> {
         match &self.pool {
             Some(pool) => Ok(pool.clone()),
             None => {
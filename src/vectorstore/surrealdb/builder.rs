@@ -1,8 +1,9 @@
-use std::{error::Error, sync::Arc};
+use std::sync::Arc;
 
 use surrealdb::{Connection, Surreal};
 
 use crate::embedding::embedder_trait::Embedder;
+use crate::error::LangChainError;
 
 use super::Store;
 
@@ -107,7 +108,7 @@ impl<C: Connection> StoreBuilder<C> {
     }
 
     // Finalize the builder and construct the Store object
-    pub async fn build(self) -> Result<Store<C>, Box<dyn Error>> {
+    pub async fn build(self) -> Result<Store<C>, LangChainError> {
         if self.embedder.is_none() {
             return Err("Embedder is required".into());
         }
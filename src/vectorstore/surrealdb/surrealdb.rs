@@ -1,14 +1,15 @@
-use std::{collections::HashMap, error::Error, sync::Arc};
+use std::{collections::HashMap, sync::Arc};
 
 use async_trait::async_trait;
 use serde::Deserialize;
-use serde_json::Value;
+use serde_json::{Map, Value};
 use surrealdb::{Connection, Surreal};
 
 use crate::{
     embedding::embedder_trait::Embedder,
+    error::LangChainError,
     schemas::Document,
-    vectorstore::{VecStoreOptions, VectorStore},
+    vectorstore::{Filter, VecStoreOptions, VectorStore},
 };
 
 // INSERT INTO documents {
@@ -42,12 +43,12 @@ impl<C: Connection> Store<C> {
             .unwrap_or_else(|| "collection".to_string())
     }
 
-    pub async fn initialize(&self) -> Result<(), Box<dyn Error>> {
+    pub async fn initialize(&self) -> Result<(), LangChainError> {
         self.create_collection_table_if_not_exists().await?;
         Ok(())
     }
 
-    async fn create_collection_table_if_not_exists(&self) -> Result<(), Box<dyn Error>> {
+    async fn create_collection_table_if_not_exists(&self) -> Result<(), LangChainError> {
         if !self.schemafull {
             return Ok(());
         }
@@ -70,7 +71,7 @@ impl<C: Connection> Store<C> {
             }
             None => {
                 let collection_table_name = &self.collection_name;
-                dbg!(&collection_table_name);
+                tracing::debug!(table = %collection_table_name, "Defining collection table");
                 self.db
                     .query(format!(
                         r#"
@@ -95,17 +96,16 @@ impl<C: Connection> VectorStore for Store<C> {
         &self,
         docs: &[Document],
         opt: &VecStoreOptions,
-    ) -> Result<Vec<String>, Box<dyn Error>> {
+    ) -> Result<Vec<String>, LangChainError> {
         let texts: Vec<String> = docs.iter().map(|d| d.page_content.clone()).collect();
 
         let embedder = opt.embedder.as_ref().unwrap_or(&self.embedder);
 
         let vectors = embedder.embed_documents(&texts).await?;
         if vectors.len() != docs.len() {
-            return Err(Box::new(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Number of vectors and documents do not match",
-            )));
+            return Err(LangChainError::VectorStoreError(
+                "Number of vectors and documents do not match".to_string(),
+            ));
         }
 
         let mut ids = Vec::with_capacity(docs.len());
@@ -122,13 +122,15 @@ impl<C: Connection> VectorStore for Store<C> {
                     let mut result = self
                         .db
                         .query(format!(
-                            r#"CREATE {collection_table_name} CONTENT {{
+                            r#"{} CONTENT {{
                                 text: $text,
                                 embedding: $embedding,
                                 metadata: $metadata,
                             }}
-                            RETURN record::id(id) as id"#
+                            RETURN record::id(id) as id"#,
+                            record_target(collection_table_name, doc)
                         ))
+                        .bind(("id", doc.id.to_owned()))
                         .bind(("text", doc.page_content.to_owned()))
                         .bind(("embedding", vector.to_owned()))
                         .bind(("metadata", metadata.to_owned()))
@@ -143,13 +145,15 @@ impl<C: Connection> VectorStore for Store<C> {
                     let mut result = self
                         .db
                         .query(format!(
-                            r#"CREATE {collection_table_name} CONTENT {{
+                            r#"{} CONTENT {{
                                 text: $text,
                                 embedding: $embedding,
                                 metadata: $metadata,
                             }}
-                            RETURN record::id(id) as id"#
+                            RETURN record::id(id) as id"#,
+                            record_target(collection_table_name, doc)
                         ))
+                        .bind(("id", doc.id.to_owned()))
                         .bind(("text", doc.page_content.to_owned()))
                         .bind(("embedding", vector.to_owned()))
                         .bind(("metadata", doc.metadata.to_owned()))
@@ -170,7 +174,7 @@ impl<C: Connection> VectorStore for Store<C> {
         query: &str,
         limit: usize,
         opt: &VecStoreOptions,
-    ) -> Result<Vec<Document>, Box<dyn Error>> {
+    ) -> Result<Vec<Document>, LangChainError> {
         let collection_name = &self.collection_name;
         let collection_table_name = self.get_collection_table_name();
 
@@ -181,6 +185,12 @@ impl<C: Connection> VectorStore for Store<C> {
             None => "",
         };
 
+        let mut filter_params = Map::new();
+        let filter_predicate = match &opt.filters {
+            Some(filter) => format!(" AND {} ", surreal_filter(filter, &mut filter_params)),
+            None => String::new(),
+        };
+
         let mut result = self
             .db
             .query(format!(
@@ -188,7 +198,7 @@ impl<C: Connection> VectorStore for Store<C> {
         SELECT record::id(id) as id, text, metadata,
         vector::similarity::cosine(embedding, $embedding) as similarity
         FROM {collection_table_name}
-        WHERE vector::similarity::cosine(embedding, $embedding) >= $score_threshold {collection_predicate}
+        WHERE vector::similarity::cosine(embedding, $embedding) >= $score_threshold {collection_predicate} {filter_predicate}
         ORDER BY similarity DESC LIMIT $k
             "#
             ))
@@ -197,6 +207,7 @@ impl<C: Connection> VectorStore for Store<C> {
             .bind(("score_threshold", opt.score_threshold.unwrap_or(0.0)))
             .bind(("k", limit))
             .bind(("embedding", query_vector.to_owned()))
+            .bind(filter_params)
             .await?
             .check()?;
 
@@ -205,6 +216,7 @@ impl<C: Connection> VectorStore for Store<C> {
         let documents = query_result
             .into_iter()
             .map(|row| Document {
+                id: Some(row.id),
                 page_content: row.text,
                 metadata: row.metadata,
                 score: row.similarity,
@@ -213,6 +225,20 @@ impl<C: Connection> VectorStore for Store<C> {
 
         Ok(documents)
     }
+
+    async fn delete(&self, ids: &[String], _opt: &VecStoreOptions) -> Result<(), LangChainError> {
+        let collection_table_name = self.get_collection_table_name();
+        for id in ids {
+            self.db
+                .query(format!(
+                    "DELETE type::thing('{collection_table_name}', $id)"
+                ))
+                .bind(("id", id.to_owned()))
+                .await?
+                .check()?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -222,3 +248,52 @@ struct Row {
     metadata: HashMap<String, Value>,
     similarity: f64,
 }
+
+/// The statement writing a document: documents with an `id` replace the record with that id,
+/// the others get a new record.
+fn record_target(table: &str, doc: &Document) -> String {
+    match doc.id {
+        Some(_) => format!("UPSERT type::thing('{table}', $id)"),
+        None => format!("CREATE {table}"),
+    }
+}
+
+/// Renders a filter as a SurrealQL condition on the document metadata, adding the keys and
+/// values it compares to `params` as `$filter_<n>`.
+fn surreal_filter(filter: &Filter, params: &mut Map<String, Value>) -> String {
+    fn bind(params: &mut Map<String, Value>, value: Value) -> String {
+        let name = format!("filter_{}", params.len());
+        params.insert(name.clone(), value);
+        format!("${}", name)
+    }
+    let mut compare = |key: &str, operator: &str, value: Value| {
+        let key = bind(params, Value::String(key.to_string()));
+        let value = bind(params, value);
+        format!("metadata[{}] {} {}", key, operator, value)
+    };
+    match filter {
+        Filter::Eq(key, value) => compare(key, "=", value.clone()),
+        Filter::Ne(key, value) => compare(key, "!=", value.clone()),
+        Filter::Gt(key, value) => compare(key, ">", value.clone()),
+        Filter::Gte(key, value) => compare(key, ">=", value.clone()),
+        Filter::Lt(key, value) => compare(key, "<", value.clone()),
+        Filter::Lte(key, value) => compare(key, "<=", value.clone()),
+        Filter::In(key, values) => compare(key, "IN", Value::Array(values.clone())),
+        Filter::NotIn(key, values) => compare(key, "NOT IN", Value::Array(values.clone())),
+        Filter::And(filters) | Filter::Or(filters) => {
+            let (operator, empty) = match filter {
+                Filter::And(_) => (" AND ", "true"),
+                _ => (" OR ", "false"),
+            };
+            if filters.is_empty() {
+                return empty.to_string();
+            }
+            let conditions = filters
+                .iter()
+                .map(|filter| surreal_filter(filter, params))
+                .collect::<Vec<_>>();
+            format!("({})", conditions.join(operator))
+        }
+        Filter::Not(filter) => format!("!({})", surreal_filter(filter, params)),
+    }
+}
@@ -1,7 +1,8 @@
-use std::error::Error;
-
 use async_trait::async_trait;
 
+use crate::error::LangChainError;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::platform::block_on;
 use crate::schemas::{self, Document};
 
 use super::VecStoreOptions;
@@ -10,18 +11,67 @@ use super::VecStoreOptions;
 // form of vector embeddings.
 #[async_trait]
 pub trait VectorStore: Send + Sync {
+    /// Adds the documents and returns their IDs. Documents with an `id` are stored under it,
+    /// replacing any document with the same ID; the others get an ID from the store.
     async fn add_documents(
         &self,
         docs: &[Document],
         opt: &VecStoreOptions,
-    ) -> Result<Vec<String>, Box<dyn Error>>;
+    ) -> Result<Vec<String>, LangChainError>;
 
     async fn similarity_search(
         &self,
         query: &str,
         limit: usize,
         opt: &VecStoreOptions,
-    ) -> Result<Vec<Document>, Box<dyn Error>>;
+    ) -> Result<Vec<Document>, LangChainError>;
+
+    /// Deletes the documents with the given IDs, as returned by `add_documents`. IDs that are
+    /// not in the store are ignored.
+    async fn delete(&self, _ids: &[String], _opt: &VecStoreOptions) -> Result<(), LangChainError> {
+        Err(LangChainError::VectorStoreError(
+            "delete is not supported by this vector store".to_string(),
+        ))
+    }
+
+    /// Adds the documents from synchronous code, blocking the thread until they are stored.
+    ///
+    /// # Panics
+    ///
+    /// Panics when called from within an async runtime; await `add_documents` there.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn add_documents_blocking(
+        &self,
+        docs: &[Document],
+        opt: &VecStoreOptions,
+    ) -> Result<Vec<String>, LangChainError> {
+        block_on(self.add_documents(docs, opt))
+    }
+
+    /// Searches from synchronous code, blocking the thread until the search completes.
+    ///
+    /// # Panics
+    ///
+    /// Panics when called from within an async runtime; await `similarity_search` there.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn similarity_search_blocking(
+        &self,
+        query: &str,
+        limit: usize,
+        opt: &VecStoreOptions,
+    ) -> Result<Vec<Document>, LangChainError> {
+        block_on(self.similarity_search(query, limit, opt))
+    }
+
+    /// Deletes the documents from synchronous code, blocking the thread until they are deleted.
+    ///
+    /// # Panics
+    ///
+    /// Panics when called from within an async runtime; await `delete` there.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn delete_blocking(&self, ids: &[String], opt: &VecStoreOptions) -> Result<(), LangChainError> {
+        block_on(self.delete(ids, opt))
+    }
 }
 impl<VS> From<VS> for Box<dyn VectorStore>
 where
@@ -79,7 +129,7 @@ impl Retriever {
 
 #[async_trait]
 impl schemas::Retriever for Retriever {
-    async fn get_relevant_documents(&self, query: &str) -> Result<Vec<Document>, Box<dyn Error>> {
+    async fn get_relevant_documents(&self, query: &str) -> Result<Vec<Document>, LangChainError> {
         self.vstore
             .similarity_search(query, self.num_docs, &self.options)
             .await